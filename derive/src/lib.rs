@@ -0,0 +1,205 @@
+//! Derive macros for egui_mobius_template
+//!
+//! Use them through the re-export in `egui_mobius_template`, whose
+//! paths the generated code refers to.
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// Generate a reactive state struct from a plain struct
+///
+/// For a plain struct `Settings` this generates `ReactiveSettings`, with
+/// the same visibility, holding every field in a `Dynamic`, and:
+///
+/// * `new(initial: Settings)` and `From<Settings>`
+/// * a getter `field()` returning a copy of the current value and a
+///   setter `set_field(value)` notifying subscribers, for every field
+///
+/// Options on the struct, `#[reactive(...)]`:
+///
+/// * `name = "AppState"` - name of the generated struct
+/// * `snapshot` - `snapshot() -> Settings` and `restore(Settings)`; derive
+///   `Serialize`/`Deserialize` on the plain struct to persist snapshots
+/// * `log` - `log_changes(&TerminalWidget)`, logging every change of a
+///   field as `Settings.field = <Debug value>`. Fields marked with
+///   `#[reactive(skip_log)]` are not logged.
+///
+/// ```ignore
+/// #[derive(Clone, Default, ReactiveState)]
+/// #[reactive(name = "AppState", snapshot, log)]
+/// pub struct AppStateFields {
+///     pub slider_value : f32,
+///     #[reactive(skip_log)]
+///     pub current_time : String,
+/// }
+///
+/// let state = AppState::new(AppStateFields::default());
+/// state.set_slider_value(2.0);
+/// assert_eq!(state.slider_value(), 2.0);
+/// ```
+#[proc_macro_derive(ReactiveState, attributes(reactive))]
+pub fn derive_reactive_state(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+/// Options given in `#[reactive(...)]` on the struct
+struct StructOptions {
+    name     : Option<Ident>,
+    snapshot : bool,
+    log      : bool,
+}
+
+fn struct_options(input: &DeriveInput) -> syn::Result<StructOptions> {
+    let mut options = StructOptions { name: None, snapshot: false, log: false };
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("reactive")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let name: LitStr = meta.value()?.parse()?;
+                options.name = Some(Ident::new(&name.value(), name.span()));
+            } else if meta.path.is_ident("snapshot") {
+                options.snapshot = true;
+            } else if meta.path.is_ident("log") {
+                options.log = true;
+            } else {
+                return Err(meta.error("expected `name = \"...\"`, `snapshot` or `log`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn skip_log(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("reactive")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip_log") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip_log`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let plain = &input.ident;
+    let vis = &input.vis;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ReactiveState does not support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "ReactiveState requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "ReactiveState can only be derived for structs",
+            ))
+        }
+    };
+
+    let options = struct_options(&input)?;
+    let name = options.name.unwrap_or_else(|| format_ident!("Reactive{}", plain));
+    let dynamic = quote!(::egui_mobius_template::reactive::Dynamic);
+
+    let names: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let types: Vec<&syn::Type> = fields.iter().map(|f| &f.ty).collect();
+    let field_vis: Vec<&syn::Visibility> = fields.iter().map(|f| &f.vis).collect();
+    let setters: Vec<Ident> = names.iter().map(|n| format_ident!("set_{}", n)).collect();
+    let struct_doc = format!("Reactive version of [`{}`], generated by `#[derive(ReactiveState)]`", plain);
+    let getter_docs: Vec<String> = names.iter().map(|n| format!("Current value of `{}`", n)).collect();
+    let setter_docs: Vec<String> = names.iter().map(|n| format!("Set `{}`, notifying its subscribers", n)).collect();
+
+    let snapshot = options.snapshot.then(|| quote! {
+        /// Copy of the current values of all fields
+        pub fn snapshot(&self) -> #plain {
+            #plain { #( #names: self.#names.get(), )* }
+        }
+
+        /// Set all fields to the values of `state`
+        pub fn restore(&self, state: #plain) {
+            #( self.#names.set(state.#names); )*
+        }
+    });
+
+    let log = if options.log {
+        let mut logged = Vec::new();
+        for field in fields {
+            if !skip_log(field)? {
+                logged.push(field.ident.as_ref().unwrap());
+            }
+        }
+        let labels: Vec<String> = logged.iter().map(|n| format!("{}.{}", plain, n)).collect();
+        Some(quote! {
+            /// Log every change of a field to `terminal`
+            pub fn log_changes(&self, terminal: &::egui_mobius_template::TerminalWidget) {
+                #({
+                    let value = self.#logged.clone();
+                    let terminal = terminal.clone();
+                    ::egui_mobius_template::reactive::ValueExt::on_change(&self.#logged, move || {
+                        ::egui_mobius_template::terminal_log!(terminal, "{} = {:?}", #labels, value.get());
+                        terminal.repaint.request_repaint();
+                    });
+                })*
+            }
+        })
+    } else {
+        // Validate field options even when they have no effect
+        for field in fields {
+            skip_log(field)?;
+        }
+        None
+    };
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[derive(Clone)]
+        #vis struct #name {
+            #( #field_vis #names: #dynamic<#types>, )*
+        }
+
+        impl #name {
+            /// Create the reactive state with the values of `initial`
+            pub fn new(initial: #plain) -> Self {
+                Self { #( #names: #dynamic::new(initial.#names), )* }
+            }
+
+            #(
+                #[doc = #getter_docs]
+                pub fn #names(&self) -> #types {
+                    self.#names.get()
+                }
+
+                #[doc = #setter_docs]
+                pub fn #setters(&self, value: #types) {
+                    self.#names.set(value);
+                }
+            )*
+
+            #snapshot
+            #log
+        }
+
+        impl ::std::convert::From<#plain> for #name {
+            fn from(initial: #plain) -> Self {
+                Self::new(initial)
+            }
+        }
+    })
+}
@@ -0,0 +1,90 @@
+# Messages de l’exemple, ajoutés à ceux de l’application dans main.rs
+
+## Panneaux
+
+panel-control = Contrôle
+panel-settings = Paramètres
+panel-logger = Journal
+panel-about = À propos
+control-heading = Panneau de contrôle
+about-heading = Framework réactif et asynchrone
+version = Version : { $version }
+about-credit = Un modèle d’application réactive et asynchrone pour egui_mobius par Saturn Rocket Company
+
+## Actions et historique
+
+category-logger = Journal
+category-control = Contrôle
+action-clear-logger = Effacer le journal
+action-toggle-run = Démarrer/arrêter
+history-slider-value = Valeur du curseur
+history-selected-option = Option choisie
+
+## Journal
+
+logger-heading = Journal des événements ({ $count ->
+    [one] 1 événement
+   *[other] { $count } événements
+})
+logger-clear = Effacer le journal
+logger-time-updates = Horodatages
+logger-ui-events = Événements de l’interface
+
+## Paramètres
+
+settings-heading = Paramètres
+settings-language = Langue :
+settings-log-colors = 🎨 Couleurs du journal
+settings-palette = Palette :
+settings-choose-preset = Choisir un préréglage…
+settings-contrast-warning = Contraste { $level } avec le fond du panneau, WCAG AA exige { $required }:1
+settings-auto-fix = Corriger le contraste
+settings-auto-fix-hint = Éclaircir ou assombrir les couleurs jusqu’à atteindre WCAG AA
+settings-contrast-level = Contraste { $level } avec le fond du thème { $theme ->
+    [dark] sombre
+   *[light] clair
+}
+settings-contrast-summary = { $aa } couleurs sur { $total } atteignent WCAG AA et { $aaa } AAA avec le thème { $theme ->
+    [dark] sombre
+   *[light] clair
+}
+settings-auto-fix-aaa = Corriger jusqu’à AAA
+settings-auto-fix-aaa-hint = Éclaircir ou assombrir les couleurs jusqu’à atteindre WCAG AAA
+settings-contrast-adjusted = { $count ->
+    [one] 1 couleur du journal ajustée
+   *[other] { $count } couleurs du journal ajustées
+} pour le contraste
+settings-theme-applied = Thème « { $name } » appliqué
+settings-theme-exported = Thème exporté vers { $path }
+settings-theme-imported = Thème importé vers { $path }
+settings-theme-failed = Thèmes : { $error }
+settings-system-info = Infos système
+settings-slider-value = Valeur du curseur :
+settings-select-option = Choisir une option :
+settings-option-a = Option A
+settings-option-b = Option B
+settings-option-c = Option C
+settings-custom-event = Événement personnalisé
+
+## Barre d’état
+
+status-running = ▶ En marche
+status-stopped = ⏹ Arrêté
+status-run-state-tooltip = Marche/arrêt, modifiable dans les paramètres
+status-clock-tooltip = Horloge en arrière-plan, son format se règle dans les paramètres
+status-runtime-running = ⚙ Exécution active
+status-runtime-stopped = ⚙ Exécution arrêtée
+status-runtime-tooltip = Exécution Mobius traitant les messages en arrière-plan
+
+## Horloge et exécution
+
+settings-time-format = ⚙️ Format de l’heure
+settings-12-hour = 12 heures
+settings-24-hour = 24 heures
+settings-system-info-section = 🖥️ Infos système
+settings-os = Système :
+settings-background-task = Tâche de fond :
+state-running = En marche
+state-stopped = Arrêtée
+notify-runtime-stopped = L’exécution Mobius s’est arrêtée
+notify-runtime-stopped-body = Les messages en arrière-plan ne sont plus traités
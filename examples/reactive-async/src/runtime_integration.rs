@@ -7,7 +7,7 @@
 //! background task and update the UI accordingly.
 use crate::{types::ClockMessage, state::AppState};
 use egui_mobius::{EventRoute, MobiusRuntime, MobiusHandle};
-use egui_mobius_template::LogType;
+use egui_mobius_template::{i18n, tr, LogType, Notification, Notifier, TerminalWidget};
 use eframe::egui;
 use std::sync::{Arc, mpsc};
 use tokio::sync::Notify;
@@ -28,15 +28,20 @@ pub struct RuntimeManager {
     handle    : Option<Arc<MobiusHandle<ClockMessage>>>,
     shutdown  : Arc<Notify>,
     state     : Arc<AppState>,
+    terminal  : TerminalWidget,
+    notifier  : Notifier,
 }
 
 impl RuntimeManager {
-    pub fn new(state: Arc<AppState>) -> Self {
+    /// Manager whose clock task updates `state` and logs into `terminal`
+    pub fn new(state: Arc<AppState>, terminal: TerminalWidget, notifier: Notifier) -> Self {
         Self {
             runtime  : None,
             handle   : None,
             shutdown : Arc::new(Notify::new()),
             state,
+            terminal,
+            notifier,
         }
     }
 
@@ -58,22 +63,17 @@ impl RuntimeManager {
         // rest.
         let current_time = self.state.current_time.clone().to_owned();  // Create owned Dynamic
         let use_24h = self.state.use_24h.clone().to_owned();  // Create owned Dynamic
-        let terminal = self.state.terminal_widget.clone();
+        let mut terminal = self.terminal.clone();
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                 let now = chrono::Local::now();
-                let time_str = if use_24h.get() {
-                    now.format("%H:%M:%S").to_string()
-                } else {
-                    now.format("%I:%M:%S %p").to_string().trim_start_matches('0').to_string()
-                };
+                let time_str = i18n::format_clock(&now, use_24h.get());
                 current_time.set(time_str.clone());
                 log::debug!("Time updated: {}", time_str);
                 
                 // Format timestamp with date for the log
-                let timestamp_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                let mut terminal = terminal.lock().unwrap();
+                let timestamp_str = i18n::format_datetime(&now);
                 terminal.add_log(
                     format!("UI System(Timestamp) Event : {}", timestamp_str),
                     LogType::Primary
@@ -121,9 +121,13 @@ impl RuntimeManager {
         //     }
         // });
 
-        // Start runtime
+        // Start runtime. `stop` aborts the task, so returning from
+        // `run` means the runtime ended while the app was still running.
+        let notifier = self.notifier.clone();
         let rt = tokio::spawn(async move {
             runtime.run().await;
+            notifier.notify(Notification::warning(tr!("notify-runtime-stopped"))
+                .body(tr!("notify-runtime-stopped-body")));
         });
 
         // Optional Control - Start background clock (presently not used)
@@ -144,6 +148,11 @@ impl RuntimeManager {
         // }
     }
 
+    /// True while the Mobius runtime task is running
+    pub fn is_healthy(&self) -> bool {
+        self.runtime.as_ref().is_some_and(|runtime| !runtime.is_finished())
+    }
+
     pub fn stop(&mut self) {
         if let Some(handle) = self.handle.take() {
             handle.shutdown();
@@ -1,31 +1,25 @@
-use egui_mobius_reactive::Dynamic;
-use egui_mobius_template::{LogColors, TerminalWidget};
-use std::sync::{Arc, Mutex};
+use egui_mobius_template::ReactiveState;
 use std::collections::VecDeque;
 use crate::types::{LogEntry, SerializableColor};
 use chrono::Local;
 use eframe::egui;
 
-/// AppState struct holds all the reactive state for the application
-pub struct AppState {
-    pub current_time : Dynamic<String>,
-    pub use_24h      : Dynamic<bool>,
-    pub logs      : Dynamic<VecDeque<LogEntry>>,
-    pub terminal_widget: Arc<Mutex<TerminalWidget>>,
+/// AppStateValues
+///
+/// Plain values of the application state. `#[derive(ReactiveState)]`
+/// generates `AppState`, holding each field in a `Dynamic` with typed
+/// getters and setters such as `use_24h()` and `set_use_24h()`.
+#[derive(Clone, Default, ReactiveState)]
+#[reactive(name = "AppState")]
+pub struct AppStateValues {
+    pub current_time : String,
+    pub use_24h      : bool,
+    pub logs         : VecDeque<LogEntry>,
 }
 
 impl AppState {
-    pub fn new(ctx: egui::Context) -> Self {
-        Self {
-            current_time: Dynamic::new(String::new()),
-            use_24h: Dynamic::new(false),
-            logs: Dynamic::new(VecDeque::with_capacity(1000)),
-            terminal_widget: Arc::new(Mutex::new(TerminalWidget::new(ctx, LogColors::default()))),
-        }
-    }
-
     pub fn log(&self, message: String) {
-        let mut logs = self.logs.get();
+        let mut logs = self.logs();
         logs.push_back(LogEntry {
             timestamp: Local::now(),
             source: "app".to_string(),
@@ -35,6 +29,6 @@ impl AppState {
         if logs.len() > 1000 {
             logs.pop_front();
         }
-        self.logs.set(logs);
+        self.set_logs(logs);
     }
 }
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use eframe::egui;
+use egui_mobius_template::SettingsSection;
 
 // Wrapper type for Color32 that can be serialized
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,3 +42,31 @@ pub struct LogEntry {
     pub message: String,
     pub color: Option<SerializableColor>,
 }
+
+/// Values of the settings panel controls that are restored on the
+/// next launch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlSettings {
+    pub slider_value    : f32,
+    pub selected_option : usize,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self { slider_value: 1.0, selected_option: 0 }
+    }
+}
+
+impl SettingsSection for ControlSettings {
+    const KEY: &'static str = "control";
+}
+
+/// Clock display preferences
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClockSettings {
+    pub use_24h: bool,
+}
+
+impl SettingsSection for ClockSettings {
+    const KEY: &'static str = "clock";
+}
@@ -1,29 +1,58 @@
 use eframe::egui;
-use egui_mobius_template::{TerminalWidget, LogType};
+use egui::{Key, KeyboardShortcut, Modifiers};
+use std::collections::VecDeque;
+use egui_mobius_template::{tr, Action, AppContext, Menu, Panel, LogType, MAX_LOGS, ERROR_COLOR};
 
-pub struct LoggerPanel<'a> {
-    terminal_widget: &'a mut TerminalWidget,
+/// LoggerPanel
+///
+/// Dock panel listing the terminal log, split into time updates and UI
+/// events. Several loggers may be open side by side.
+#[derive(Default)]
+pub struct LoggerPanel;
+
+impl LoggerPanel {
+    /// Logger actions offered in the command palette and the Edit menu
+    pub fn actions() -> [Action; 1] {
+        [
+            Action::new("logger.clear", "action-clear-logger", |ctx| {
+                ctx.terminal.logs.set(VecDeque::with_capacity(MAX_LOGS));
+                ctx.terminal.repaint.request_repaint();
+            })
+            .category("category-logger")
+            .menu(Menu::Edit)
+            .shortcut(KeyboardShortcut::new(Modifiers::COMMAND, Key::L)),
+        ]
+    }
 }
 
-impl<'a> LoggerPanel<'a> {
-    pub fn new(terminal_widget: &'a mut TerminalWidget) -> Self {
-        Self { terminal_widget }
+impl Panel for LoggerPanel {
+    fn id(&self) -> &'static str {
+        "logger"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-logger")
     }
 
-    pub fn render(ui: &mut egui::Ui, terminal_widget: &'a mut TerminalWidget) {
-        let mut panel = Self::new(terminal_widget);
-        panel.ui(ui);
+    fn icon(&self) -> &'static str {
+        "📜"
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    fn allows_duplicates(&self) -> bool {
+        true
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        let terminal_widget = &mut *ctx.terminal;
+
         // Get current state
-        let logs = self.terminal_widget.logs.get();
-        ui.heading(format!("Event Log ({} events)", logs.len()));
+        let logs = terminal_widget.logs.get();
+        ui.heading(tr!("logger-heading", count = logs.len()));
 
-        if ui.button("Clear Logger").clicked() {
-            let mut logs = self.terminal_widget.logs.get();
+        if ctx.recorder.press("logger.clear", ui.button(tr!("logger-clear")).clicked()) {
+            let mut logs = terminal_widget.logs.get();
             logs.clear();
-            self.terminal_widget.logs.set(logs);
+            terminal_widget.logs.set(logs);
         }
 
         ui.add_space(8.0);
@@ -36,9 +65,9 @@ impl<'a> LoggerPanel<'a> {
 
                 // Headers
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Time Updates").strong().monospace());
+                    ui.label(egui::RichText::new(tr!("logger-time-updates")).strong().monospace());
                     ui.add_space(20.0);
-                    ui.label(egui::RichText::new("UI Events").strong().monospace());
+                    ui.label(egui::RichText::new(tr!("logger-ui-events")).strong().monospace());
                 });
                 ui.add_space(8.0);
 
@@ -57,7 +86,7 @@ impl<'a> LoggerPanel<'a> {
                         ui.set_min_width(200.0);
                         for (msg, _) in time_updates.iter().rev() {
                             let text = egui::RichText::new(msg).monospace();
-                            ui.label(text.color(self.terminal_widget.colors.get().clock));
+                            ui.label(text.color(terminal_widget.colors.get().clock));
                         }
                     });
 
@@ -69,7 +98,7 @@ impl<'a> LoggerPanel<'a> {
                         ui.set_min_width(400.0);
                         for (msg, log_type) in ui_events.iter().rev() {
                             let text = egui::RichText::new(msg).monospace();
-                            let colors = self.terminal_widget.colors.get();
+                            let colors = terminal_widget.colors.get();
                             let color = match log_type {
                                 LogType::Slider => colors.slider,
                                 LogType::OptionA => colors.option_a,
@@ -77,6 +106,7 @@ impl<'a> LoggerPanel<'a> {
                                 LogType::OptionC => colors.option_c,
                                 LogType::Primary => colors.clock,
                                 LogType::Secondary => colors.custom_event,
+                                LogType::Error => ERROR_COLOR,
                                 _ => colors.custom_event,
                             };
                             ui.label(text.color(color));
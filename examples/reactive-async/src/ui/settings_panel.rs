@@ -1,30 +1,111 @@
 use eframe::egui;
 use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
-use egui_mobius_template::{TerminalWidget, LogType, MAX_LOGS, LogColors};
+use std::convert::Infallible;
+use std::sync::Arc;
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::{tr, Action, AppContext, Panel, Recorder, TerminalWidget, LogType, MAX_LOGS};
+use egui_mobius_template::i18n;
+use egui_mobius_template::accessibility::{contrast_ratio, ContrastLevel, Palette, WCAG_AA, WCAG_AAA};
+use egui_mobius_template::themes::{ThemeEvent, ThemeManager};
 use egui_mobius_widgets::{StatefulButton, StyledButton};
-#[allow(unused_imports)]
-use crate::{set_timestamp_log, set_slider_log, set_combo_log, log_to_terminal};
-use crate::logging_macros::Latch;
+use egui_mobius_template::{set_timestamp_log, set_slider_log, set_combo_log, terminal_log};
 use crate::state::AppState;
 
 
-pub struct SettingsPanel<'a> {
+/// ControlState
+///
+/// Values edited by the settings panel, shared through the resources
+/// of the app context.
+pub struct ControlState {
+    pub slider_value    : Dynamic<f32>,
+    pub selected_option : Dynamic<usize>,
+    pub is_running      : bool,
+}
+
+/// SettingsPanel
+///
+/// Dock panel with the log colors, the controls and the run/stop button.
+#[derive(Default)]
+pub struct SettingsPanel;
+
+impl SettingsPanel {
+    /// Settings actions offered in the command palette
+    pub fn actions() -> [Action; 1] {
+        [
+            Action::new("control.toggle_run", "action-toggle-run", |ctx| {
+                let Some(control) = ctx.resources.get_mut::<ControlState>() else {
+                    return;
+                };
+                control.is_running = !control.is_running;
+                terminal_log!(ctx.terminal, type: LogType::RunStop,
+                    "System {}", if control.is_running { "started" } else { "stopped" });
+            })
+            .category("category-control")
+            .shortcut(egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F5)),
+        ]
+    }
+}
+
+impl Panel for SettingsPanel {
+    fn id(&self) -> &'static str {
+        "settings"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-settings")
+    }
+
+    fn icon(&self) -> &'static str {
+        "⚙"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        let Some(state) = ctx.resources.get::<Arc<AppState>>().cloned() else {
+            return;
+        };
+        let Some(control) = ctx.resources.get_mut::<ControlState>() else {
+            return;
+        };
+
+        let mut slider_value = control.slider_value.get();
+        let mut selected_option = control.selected_option.get();
+        SettingsView::new(
+            ctx.terminal,
+            &mut slider_value,
+            &mut selected_option,
+            &mut control.is_running,
+            ctx.themes,
+            ctx.recorder,
+            state,
+        ).ui(ui);
+
+        if slider_value != control.slider_value.get() {
+            control.slider_value.set(slider_value);
+        }
+        if selected_option != control.selected_option.get() {
+            control.selected_option.set(selected_option);
+        }
+    }
+}
+
+struct SettingsView<'a> {
     terminal_widget: &'a mut TerminalWidget,
     slider_value: &'a mut f32,
     selected_option: &'a mut usize,
     is_running: &'a mut bool,
-    colors: &'a Arc<Mutex<LogColors>>,
+    themes: &'a mut ThemeManager,
+    recorder: &'a Recorder,
     state: Arc<AppState>,
 }
 
-impl<'a> SettingsPanel<'a> {
-    pub fn new(
+impl<'a> SettingsView<'a> {
+    fn new(
         terminal_widget: &'a mut TerminalWidget,
         slider_value: &'a mut f32,
         selected_option: &'a mut usize,
         is_running: &'a mut bool,
-        colors: &'a Arc<Mutex<LogColors>>,
+        themes: &'a mut ThemeManager,
+        recorder: &'a Recorder,
         state: Arc<AppState>,
     ) -> Self {
         Self {
@@ -32,45 +113,40 @@ impl<'a> SettingsPanel<'a> {
             slider_value,
             selected_option,
             is_running,
-            colors,
+            themes,
+            recorder,
             state,
         }
     }
 
-    pub fn render(
-        ui: &mut egui::Ui,
-        terminal_widget: &'a mut TerminalWidget,
-        slider_value: &'a mut f32,
-        selected_option: &'a mut usize,
-        is_running: &'a mut bool,
-        colors: &'a Arc<Mutex<LogColors>>,
-        state: Arc<AppState>,
-    ) {
-        let mut panel = Self::new(terminal_widget, slider_value, selected_option, is_running, colors, state);
-        panel.ui(ui);
-    }
-
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    fn ui(&mut self, ui: &mut egui::Ui) {
         ui.vertical(|ui| {
             // Clock Display
             ui.add_space(10.0);
-            let time_str = self.state.current_time.get();
+            let time_str = self.state.current_time();
             ui.heading(&time_str);
             ui.add_space(20.0);
 
-            ui.heading("Settings");
+            ui.heading(tr!("settings-heading"));
+            ui.add_space(8.0);
+
+            // Language of the UI, switched without a restart
+            ui.horizontal(|ui| {
+                ui.label(tr!("settings-language"));
+                i18n::language_selector(ui);
+            });
             ui.add_space(8.0);
 
             // Time Format Section
-            ui.collapsing("⚙️ Time Format", |ui| {
+            ui.collapsing(tr!("settings-time-format"), |ui| {
                 ui.horizontal(|ui| {
-                    let mut use_24h = self.state.use_24h.get();
-                    if ui.radio_value(&mut use_24h, false, "12-hour").clicked() {
-                        self.state.use_24h.set(use_24h);
+                    let mut use_24h = self.state.use_24h();
+                    if ui.radio_value(&mut use_24h, false, tr!("settings-12-hour")).clicked() {
+                        self.state.set_use_24h(use_24h);
                         self.state.log("Changed time format to 12-hour".to_string());
                     }
-                    if ui.radio_value(&mut use_24h, true, "24-hour").clicked() {
-                        self.state.use_24h.set(use_24h);
+                    if ui.radio_value(&mut use_24h, true, tr!("settings-24-hour")).clicked() {
+                        self.state.set_use_24h(use_24h);
                         self.state.log("Changed time format to 24-hour".to_string());
                     }
                 });
@@ -79,14 +155,14 @@ impl<'a> SettingsPanel<'a> {
             ui.add_space(8.0);
 
             // System Info Section
-            ui.collapsing("🖥️ System Info", |ui| {
+            ui.collapsing(tr!("settings-system-info-section"), |ui| {
                 ui.horizontal(|ui| {
-                    ui.label("OS:");
+                    ui.label(tr!("settings-os"));
                     ui.label("linux");
                 });
                 ui.horizontal(|ui| {
-                    ui.label("Background Task:");
-                    ui.label(if *self.is_running { "Running" } else { "Stopped" });
+                    ui.label(tr!("settings-background-task"));
+                    ui.label(if *self.is_running { tr!("state-running") } else { tr!("state-stopped") });
                 });
             });
 
@@ -94,66 +170,110 @@ impl<'a> SettingsPanel<'a> {
 
             // Color settings
             ui.push_id("log_colors_section", |ui| {
-                ui.collapsing("🎨 Log Colors", |ui| {
-                    // Get a copy of the colors first
-                    let mut colors = if let Ok(colors) = self.colors.lock() {
-                        colors.clone()
-                    } else {
-                        return; // Lock failed, skip this frame
-                    };
-                    
-                    let mut changed = false;
-
-                    ui.horizontal(|ui| {
-                        ui.label("Slider:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.slider).changed();
-                    });
+                ui.collapsing(tr!("settings-log-colors"), |ui| {
+                    // Edit a copy, written back below if anything changed
+                    let mut colors = self.terminal_widget.colors.get();
 
-                    ui.horizontal(|ui| {
-                        ui.label("Option A:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.option_a).changed();
-                    });
-
-                    ui.horizontal(|ui| {
-                        ui.label("Option B:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.option_b).changed();
-                    });
+                    let mut changed = false;
+                    let mut note = None;
+                    // Contrast is checked against the active theme
+                    let background = ui.visuals().panel_fill;
+                    let dark_mode = ui.visuals().dark_mode;
+                    let theme = if dark_mode { "dark" } else { "light" };
 
+                    // Preset palettes, including colorblind-safe ones
                     ui.horizontal(|ui| {
-                        ui.label("Option C:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.option_c).changed();
+                        ui.label(tr!("settings-palette"));
+                        egui::ComboBox::from_id_salt("log_color_palette")
+                            .selected_text(tr!("settings-choose-preset"))
+                            .show_ui(ui, |ui| {
+                                for palette in Palette::ALL {
+                                    if ui.selectable_label(false, palette.label()).clicked() {
+                                        colors = palette.colors(dark_mode);
+                                        changed = true;
+                                    }
+                                }
+                            });
                     });
+                    ui.add_space(4.0);
 
-                    ui.horizontal(|ui| {
-                        ui.label("Custom Event:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.custom_event).changed();
-                    });
+                    // One picker per entry with its WCAG level against the
+                    // panel background, as a warning below AA
+                    for (name, color) in colors.entries_mut() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}:", tr!(name)));
+                            changed |= ui.color_edit_button_srgba(color).changed();
 
-                    ui.horizontal(|ui| {
-                        ui.label("Run/Stop:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.run_stop_log).changed();
-                    });
+                            let ratio = contrast_ratio(*color, background);
+                            let level = ContrastLevel::from_ratio(ratio);
+                            if level < ContrastLevel::Aa {
+                                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}:1", i18n::format_number(ratio as f64, 1)))
+                                    .on_hover_text(tr!("settings-contrast-warning", level = level.label(), required = i18n::format_number(WCAG_AA as f64, 1)));
+                            } else {
+                                ui.weak(format!("{} {}:1", level.label(), i18n::format_number(ratio as f64, 1)))
+                                    .on_hover_text(tr!("settings-contrast-level", level = level.label(), theme = theme));
+                            }
+                        });
+                    }
 
+                    let report = colors.contrast_report(background);
+                    let aa = report.iter().filter(|(_, ratio)| *ratio >= WCAG_AA).count();
+                    let aaa = report.iter().filter(|(_, ratio)| *ratio >= WCAG_AAA).count();
+                    ui.label(tr!("settings-contrast-summary", aa = aa, aaa = aaa, total = report.len(), theme = theme));
                     ui.horizontal(|ui| {
-                        ui.label("Time Format:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.time_format).changed();
+                        for (target, label, hint, enabled) in [
+                            (WCAG_AA, "settings-auto-fix", "settings-auto-fix-hint", aa < report.len()),
+                            (WCAG_AAA, "settings-auto-fix-aaa", "settings-auto-fix-aaa-hint", aaa < report.len()),
+                        ] {
+                            if ui.add_enabled(enabled, egui::Button::new(tr!(label)))
+                                .on_hover_text(tr!(hint))
+                                .clicked()
+                            {
+                                let fixed = colors.fix_contrast(background, target);
+                                note = Some(tr!("settings-contrast-adjusted", count = fixed));
+                                changed = true;
+                            }
+                        }
                     });
 
-                    ui.horizontal(|ui| {
-                        ui.label("Clock:");
-                        changed |= ui.color_edit_button_srgba(&mut colors.clock).changed();
-                    });
+                    // Theme files: export the current colors, import and
+                    // preview shared themes before applying them
+                    ui.add_space(4.0);
+                    ui.separator();
+                    match self.themes.ui(ui, &colors) {
+                        Some(ThemeEvent::Applied(theme)) => {
+                            note = Some(tr!("settings-theme-applied", name = theme.name.as_str()));
+                            colors = theme.log_colors;
+                            changed = true;
+                        }
+                        Some(ThemeEvent::Exported(path)) => {
+                            self.terminal_widget.add_log(tr!("settings-theme-exported", path = path.display().to_string()), LogType::Default);
+                        }
+                        Some(ThemeEvent::Imported(path)) => {
+                            self.terminal_widget.add_log(tr!("settings-theme-imported", path = path.display().to_string()), LogType::Default);
+                        }
+                        Some(ThemeEvent::Failed(e)) => {
+                            self.terminal_widget.add_log(tr!("settings-theme-failed", error = e.to_string()), LogType::Error);
+                        }
+                        None => {}
+                    }
 
                     if changed {
-                        // Update terminal widget colors
-                        self.terminal_widget.update_colors(colors.clone());
-                        
-                        // Update shared colors
-                        if let Ok(mut shared_colors) = self.colors.lock() {
-                            *shared_colors = colors;
-                        }
-                        
-                        self.terminal_widget.repaint.request_repaint();
+                        // Update terminal widget colors and log the change in
+                        // one transaction, rebuilding the log text only once
+                        let terminal = &self.terminal_widget;
+                        let _ = terminal.transaction(|tx| {
+                            tx.set(&terminal.colors, colors);
+                            if let Some(note) = note {
+                                tx.update(&terminal.logs, |logs| {
+                                    if logs.len() >= MAX_LOGS {
+                                        logs.pop_front();
+                                    }
+                                    logs.push_back((note, LogType::Default));
+                                });
+                            }
+                            Ok::<_, Infallible>(())
+                        });
                     }
                 });
             });
@@ -161,7 +281,7 @@ impl<'a> SettingsPanel<'a> {
 
             // System Info
             ui.horizontal(|ui| {
-                if ui.button("System Info").clicked() {
+                if self.recorder.press("settings.system_info", ui.button(tr!("settings-system-info")).clicked()) {
                     set_timestamp_log!(self.terminal_widget, "Ui System(Timestamp) Event ", LogType::Timestamp);
                 }
             });
@@ -169,7 +289,7 @@ impl<'a> SettingsPanel<'a> {
 
             // Clear Logger button
             ui.horizontal(|ui| {
-                if ui.button("Clear Logger").clicked() {
+                if self.recorder.press("settings.clear_logger", ui.button(tr!("logger-clear")).clicked()) {
                     self.terminal_widget.logs.set(VecDeque::with_capacity(MAX_LOGS));
                     self.terminal_widget.repaint.request_repaint();
                 }
@@ -178,7 +298,7 @@ impl<'a> SettingsPanel<'a> {
 
             // Slider control
             ui.horizontal(|ui| {
-                ui.label("Slider Value:");
+                ui.label(tr!("settings-slider-value"));
                 if ui.add(egui::Slider::new(self.slider_value, 1.0..=100.0)
                     .text("x1000")).changed() {
                     let log_string_and_value = format!("Ui Slider Event {:.1}", self.slider_value);
@@ -189,10 +309,10 @@ impl<'a> SettingsPanel<'a> {
             ui.add_space(16.0);
 
             // Combo box with options
-            ui.label("Select an option:");
+            ui.label(tr!("settings-select-option"));
             ui.horizontal(|ui| {
-                for (idx, label) in ["Option A", "Option B", "Option C"].iter().enumerate() {
-                    if ui.selectable_label(*self.selected_option == idx, *label).clicked() {
+                for (idx, label) in ["settings-option-a", "settings-option-b", "settings-option-c"].into_iter().enumerate() {
+                    if ui.selectable_label(*self.selected_option == idx, tr!(label)).clicked() {
                         *self.selected_option = idx;
                         let log_type = match idx {
                             0 => LogType::OptionA,
@@ -208,17 +328,16 @@ impl<'a> SettingsPanel<'a> {
             ui.add_space(16.0);
 
             // Custom event button with styled appearance
-            let event_button = StyledButton::new("Custom Event")
+            let event_button = StyledButton::new(tr!("settings-custom-event"))
                 .hover_color(egui::Color32::from_rgb(100, 200, 255))
                 .normal_color(egui::Color32::from_rgb(150, 150, 255))
                 .rounding(5.0)
                 .margin(egui::Vec2::new(4.0, 2.0))
                 .min_size(egui::vec2(120.0, 24.0));
 
-            if event_button.show(ui).clicked() {
-                let mut logs = self.terminal_widget.logs.get();
-                logs.push_back((format!("Custom event triggered with slider={:.1}", self.slider_value), LogType::CustomEvent));
-                self.terminal_widget.logs.set(logs);
+            if self.recorder.press("settings.custom_event", event_button.show(ui).clicked()) {
+                terminal_log!(self.terminal_widget, type: LogType::CustomEvent,
+                    "Custom event triggered with slider={:.1}", self.slider_value);
                 self.terminal_widget.repaint.request_repaint();
             }
             ui.add_space(8.0);
@@ -235,12 +354,10 @@ impl<'a> SettingsPanel<'a> {
             
             stateful_button.set_started(*self.is_running);
 
-            if stateful_button.show(ui).clicked() {
+            if self.recorder.press("settings.run_stop", stateful_button.show(ui).clicked()) {
                 *self.is_running = !*self.is_running;
-                let msg = format!("System {}", if *self.is_running { "started" } else { "stopped" });
-                let mut logs = self.terminal_widget.logs.get();
-                logs.push_back((msg, LogType::RunStop));
-                self.terminal_widget.logs.set(logs);
+                terminal_log!(self.terminal_widget, type: LogType::RunStop,
+                    "System {}", if *self.is_running { "started" } else { "stopped" });
                 self.terminal_widget.repaint.request_repaint();
             }
         });
@@ -0,0 +1,73 @@
+# Meldungen des Beispiels, in main.rs zu denen der Anwendung hinzugefügt
+
+## Panels
+
+panel-control = Steuerung
+panel-settings = Einstellungen
+panel-logger = Protokoll
+panel-about = Über
+control-heading = Steuerung
+about-heading = MobiusLoop
+version = Version: { $version }
+about-credit = Eine reaktive Anwendungsvorlage für egui_mobius von Saturn Rocket Company
+
+## Aktionen und Verlauf
+
+category-logger = Protokoll
+category-control = Steuerung
+action-clear-logger = Protokoll leeren
+action-toggle-run = Start/Stopp umschalten
+history-slider-value = Wert des Schiebereglers
+history-selected-option = Gewählte Option
+
+## Protokoll
+
+logger-heading = Ereignisprotokoll ({ $count ->
+    [one] 1 Ereignis
+   *[other] { $count } Ereignisse
+})
+logger-clear = Protokoll leeren
+logger-time-updates = Zeitmeldungen
+logger-ui-events = UI-Ereignisse
+
+## Einstellungen
+
+settings-heading = Einstellungen
+settings-language = Sprache:
+settings-log-colors = 🎨 Protokollfarben
+settings-palette = Palette:
+settings-choose-preset = Vorlage wählen…
+settings-contrast-warning = Kontrast { $level } zum Hintergrund, WCAG AA verlangt { $required }:1
+settings-auto-fix = Kontrast korrigieren
+settings-auto-fix-hint = Farben aufhellen oder abdunkeln, bis sie WCAG AA erreichen
+settings-contrast-level = Kontrast { $level } zum Hintergrund des { $theme ->
+    [dark] dunklen
+   *[light] hellen
+} Themas
+settings-contrast-summary = { $aa } von { $total } Farben erreichen WCAG AA und { $aaa } AAA im { $theme ->
+    [dark] dunklen
+   *[light] hellen
+} Thema
+settings-auto-fix-aaa = Auf AAA korrigieren
+settings-auto-fix-aaa-hint = Farben aufhellen oder abdunkeln, bis sie WCAG AAA erreichen
+settings-contrast-adjusted = { $count ->
+    [one] 1 Protokollfarbe
+   *[other] { $count } Protokollfarben
+} für besseren Kontrast angepasst
+settings-theme-applied = Farbschema „{ $name }“ angewendet
+settings-theme-exported = Farbschema nach { $path } exportiert
+settings-theme-imported = Farbschema nach { $path } importiert
+settings-theme-failed = Farbschemata: { $error }
+settings-system-info = Systeminfo
+settings-slider-value = Wert des Schiebereglers:
+settings-select-option = Option wählen:
+settings-option-a = Option A
+settings-option-b = Option B
+settings-option-c = Option C
+settings-custom-event = Eigenes Ereignis
+
+## Statusleiste
+
+status-running = ▶ Läuft
+status-stopped = ⏹ Gestoppt
+status-run-state-tooltip = Start/Stopp, änderbar in den Einstellungen
@@ -0,0 +1,73 @@
+# Messages of the example, added to those of the shell in main.rs
+
+## Panels
+
+panel-control = Control
+panel-settings = Settings
+panel-logger = Logger
+panel-about = About
+control-heading = Control Panel
+about-heading = MobiusLoop
+version = Version: { $version }
+about-credit = A Reactive Application Template for egui_mobius by Saturn Rocket Company
+
+## Actions and history
+
+category-logger = Logger
+category-control = Control
+action-clear-logger = Clear logger
+action-toggle-run = Toggle run/stop
+history-slider-value = Slider value
+history-selected-option = Selected option
+
+## Logger
+
+logger-heading = Event Log ({ $count ->
+    [one] 1 event
+   *[other] { $count } events
+})
+logger-clear = Clear Logger
+logger-time-updates = Time Updates
+logger-ui-events = UI Events
+
+## Settings
+
+settings-heading = Settings
+settings-language = Language:
+settings-log-colors = 🎨 Log Colors
+settings-palette = Palette:
+settings-choose-preset = Choose preset…
+settings-contrast-warning = { $level } contrast against the panel background, WCAG AA needs { $required }:1
+settings-auto-fix = Auto-fix contrast
+settings-auto-fix-hint = Lighten or darken colors until they reach WCAG AA
+settings-contrast-level = { $level } contrast against the { $theme ->
+    [dark] dark
+   *[light] light
+} theme background
+settings-contrast-summary = { $aa } of { $total } colors reach WCAG AA and { $aaa } reach AAA on the { $theme ->
+    [dark] dark
+   *[light] light
+} theme
+settings-auto-fix-aaa = Fix to AAA
+settings-auto-fix-aaa-hint = Lighten or darken colors until they reach WCAG AAA
+settings-contrast-adjusted = Adjusted { $count ->
+    [one] 1 log color
+   *[other] { $count } log colors
+} for contrast
+settings-theme-applied = Applied theme "{ $name }"
+settings-theme-exported = Exported theme to { $path }
+settings-theme-imported = Imported theme to { $path }
+settings-theme-failed = Themes: { $error }
+settings-system-info = System Info
+settings-slider-value = Slider Value:
+settings-select-option = Select an option:
+settings-option-a = Option A
+settings-option-b = Option B
+settings-option-c = Option C
+settings-custom-event = Custom Event
+
+## Status bar
+
+status-running = ▶ Running
+status-stopped = ⏹ Stopped
+status-run-state-tooltip = Run/stop state, changed in Settings
@@ -0,0 +1,125 @@
+//! The reactive template's settings and logger panels, driven headlessly.
+use crate::ui::logger_panel::LoggerPanel;
+use crate::ui::settings_panel::{ControlState, SettingsPanel};
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::accessibility::Palette;
+use egui_mobius_template::i18n;
+use egui_mobius_template::recorder::ReplaySpeed;
+use egui_mobius_template::testing::{Harness, TestApp};
+use egui_mobius_template::{LogType, Panel};
+
+/// The template's messages, added by its `main` in the application
+fn add_messages() {
+    i18n::add_messages("en-US", include_str!("../locales/en-US.ftl")).unwrap();
+}
+
+fn app(harness: &Harness) -> TestApp {
+    add_messages();
+    TestApp::new(harness.ctx()).with(ControlState {
+        slider_value: Dynamic::new(1.0),
+        selected_option: Dynamic::new(0),
+        is_running: false,
+    })
+}
+
+fn control(app: &TestApp) -> &ControlState {
+    app.services.resources.get::<ControlState>().unwrap()
+}
+
+#[test]
+fn settings_panel_is_rendered() {
+    let mut harness = Harness::new();
+    let mut app = app(&harness);
+    harness.run_panel(&mut SettingsPanel, &mut app);
+
+    assert_eq!(SettingsPanel.title(), "Settings");
+    harness.assert_snapshot("reactive_settings_panel");
+}
+
+#[test]
+fn settings_panel_controls_change_state_and_log() {
+    let mut harness = Harness::new();
+    let mut app = app(&harness);
+    let mut panel = SettingsPanel;
+    harness.run_panel(&mut panel, &mut app);
+
+    harness.click("Option B");
+    harness.run_panel(&mut panel, &mut app);
+    assert_eq!(control(&app).selected_option.get(), 1);
+    assert_eq!(app.logs(), ["Selected: Option B"]);
+
+    harness.click("Custom Event");
+    harness.run_panel(&mut panel, &mut app);
+    assert_eq!(app.logs().last().unwrap(), "Custom event triggered with slider=1.0");
+
+    harness.click("Clear Logger");
+    harness.run_panel(&mut panel, &mut app);
+    assert!(app.logs().is_empty());
+}
+
+#[test]
+fn palettes_are_chosen_from_the_log_colors_section() {
+    let mut harness = Harness::new();
+    let mut app = app(&harness);
+    let mut panel = SettingsPanel;
+    harness.run_panel(&mut panel, &mut app);
+    assert!(!harness.contains("Choose preset…"));
+
+    harness.click("🎨 Log Colors");
+    harness.run_panel(&mut panel, &mut app);
+    harness.click("Choose preset…");
+    harness.run_panel(&mut panel, &mut app);
+    harness.click(&Palette::Tritanopia.label());
+    harness.run_panel(&mut panel, &mut app);
+
+    assert_eq!(app.terminal.colors.get(), Palette::Tritanopia.colors(harness.ctx().style().visuals.dark_mode));
+}
+
+#[test]
+fn recorded_sessions_are_replayed_headlessly() {
+    let mut harness = Harness::new();
+    let mut app = app(&harness);
+    app.services.recorder.track(&control(&app).slider_value, "control.slider_value");
+    app.services.recorder.track(&control(&app).selected_option, "control.selected_option");
+    let mut panel = SettingsPanel;
+    harness.run_panel(&mut panel, &mut app);
+
+    app.services.recorder.start("Reactive");
+    harness.click("Option C");
+    harness.run_panel(&mut panel, &mut app);
+    harness.click("Custom Event");
+    harness.run_panel(&mut panel, &mut app);
+    let recording = app.services.recorder.stop().unwrap();
+
+    // Replay in a fresh application, one input per frame
+    let mut harness = Harness::new();
+    let mut app = self::app(&harness);
+    app.services.recorder.track(&control(&app).slider_value, "control.slider_value");
+    app.services.recorder.track(&control(&app).selected_option, "control.selected_option");
+    app.services.recorder.replay(recording, ReplaySpeed::Stepped).unwrap();
+    harness.run_panel(&mut panel, &mut app);
+
+    assert!(!app.services.recorder.is_replaying());
+    assert_eq!(control(&app).selected_option.get(), 2);
+    assert_eq!(app.logs(), ["Custom event triggered with slider=1.0"]);
+}
+
+#[test]
+fn logger_panel_lists_and_clears_the_log() {
+    add_messages();
+    let mut harness = Harness::new();
+    let mut app = TestApp::new(harness.ctx());
+    app.terminal.add_log("Ui Slider Event 42.0".to_string(), LogType::Slider);
+    app.terminal.add_log("12:00:00".to_string(), LogType::Timestamp);
+    app.terminal.add_log("Failed to save".to_string(), LogType::Error);
+
+    let mut panel = LoggerPanel;
+    harness.run_panel(&mut panel, &mut app);
+    assert!(harness.contains("Event Log (3 events)"));
+    harness.assert_snapshot("reactive_logger_panel");
+
+    harness.click("Clear Logger");
+    harness.run_panel(&mut panel, &mut app);
+    assert!(app.logs().is_empty());
+    assert!(harness.contains("Event Log (0 events)"));
+}
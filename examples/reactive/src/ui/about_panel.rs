@@ -1,4 +1,5 @@
 use eframe::egui;
+use egui_mobius_template::{tr, AppContext, Panel};
 use once_cell::sync::Lazy;
 
 static LOGO: Lazy<egui::Image<'static>> = Lazy::new(|| {
@@ -8,10 +9,30 @@ static LOGO: Lazy<egui::Image<'static>> = Lazy::new(|| {
         .clone()
 });
 
+/// AboutPanel
+///
+/// Dock panel with the logo and version of the application.
+#[derive(Default)]
 pub struct AboutPanel;
 
-impl AboutPanel {
-    pub fn render(ui: &mut egui::Ui) {
+impl Panel for AboutPanel {
+    fn id(&self) -> &'static str {
+        "about"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-about")
+    }
+
+    fn icon(&self) -> &'static str {
+        "ℹ"
+    }
+
+    fn allows_duplicates(&self) -> bool {
+        true
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, _ctx: &mut AppContext) {
         ui.vertical_centered(|ui| {
             ui.add_space(20.0);
             
@@ -25,16 +46,16 @@ impl AboutPanel {
                         ui.add(Lazy::force(&LOGO).clone());
                         
                         ui.add_space(10.0);
-                        ui.heading("MobiusLoop");
+                        ui.heading(tr!("about-heading"));
                         ui.add_space(10.0);
                         
-                        ui.label("Version: 0.1.0");
+                        ui.label(tr!("version", version = "0.1.0"));
                         ui.add_space(10.0);
                         
                         // Add Saturn Rocket Company credit
                         ui.label(
                             egui::RichText::new(
-                                "A Reactive Application Template for egui_mobius by Saturn Rocket Company"
+                                tr!("about-credit")
                             )
                             .size(12.0)
                             .strong()
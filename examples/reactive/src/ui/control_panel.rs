@@ -1,28 +1,32 @@
 use eframe::egui;
-use egui_mobius_template::TerminalWidget;
+use egui_mobius_template::{tr, AppContext, Panel};
 
-#[allow(dead_code)]
-pub struct ControlPanel<'a> {
-    terminal_widget: &'a mut TerminalWidget,
-}
+/// ControlPanel
+///
+/// Dock panel for application specific controls.
+#[derive(Default)]
+pub struct ControlPanel;
 
-impl<'a> ControlPanel<'a> {
-    pub fn new(terminal_widget: &'a mut TerminalWidget) -> Self {
-        Self { terminal_widget }
+impl Panel for ControlPanel {
+    fn id(&self) -> &'static str {
+        "control"
     }
 
-    pub fn render(ui: &mut egui::Ui, terminal_widget: &'a mut TerminalWidget) {
-        let mut panel = Self::new(terminal_widget);
-        panel.ui(ui);
+    fn title(&self) -> String {
+        tr!("panel-control")
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui) {
+    fn icon(&self) -> &'static str {
+        "🔧"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, _ctx: &mut AppContext) {
         ui.vertical(|ui| {
-            ui.heading("Control Panel");
+            ui.heading(tr!("control-heading"));
             ui.add_space(8.0);
 
             // Add control panel specific UI elements here
-            ui.label("Version: 0.1.0");
+            ui.label(tr!("version", version = "0.1.0"));
         });
     }
 }
\ No newline at end of file
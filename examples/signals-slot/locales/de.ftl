@@ -0,0 +1,43 @@
+# Meldungen des Beispiels, in main.rs zu denen der Anwendung hinzugefügt
+
+## Panels
+
+panel-parameters = Parameter
+panel-plot = Antwort der Schaltung
+parameters-heading = Parameter der Schaltung
+parameters-resistance = Widerstand:
+parameters-capacitance = Kapazität:
+parameters-inductance = Induktivität:
+parameters-voltage = Eingangsspannung:
+parameters-time = Simulationsdauer:
+parameters-method = Integrationsverfahren:
+parameters-method-euler = Euler
+parameters-method-rk4 = RK4
+parameters-method-trapezoidal = Trapez
+parameters-run = START
+parameters-running = LÄUFT...
+plot-time-axis = Zeit (s)
+plot-value-axis = Spannung (V) / Strom (A)
+plot-voltage = Spannung
+plot-current = Strom
+plot-no-results = Noch keine Simulationsergebnisse. START klicken, um die Simulation zu beginnen.
+
+## Aktionen und Verlauf
+
+category-simulation = Simulation
+action-run-simulation = Simulation starten
+history-circuit-parameters = Parameter der Schaltung
+
+## Benachrichtigungen und Statusleiste
+
+notify-simulation-finished = Simulation beendet
+notify-time-steps = { $count ->
+    [one] 1 Zeitschritt
+   *[other] { $count } Zeitschritte
+}
+notify-show-plot = Diagramm zeigen
+notify-simulation-failed = Simulation fehlgeschlagen
+notify-show-parameters = Parameter zeigen
+notify-retry = Wiederholen
+status-simulation-ready = ⏹ Simulation bereit
+status-simulation-running = ▶ Simulation läuft
@@ -0,0 +1,43 @@
+# Messages of the example, added to those of the shell in main.rs
+
+## Panels
+
+panel-parameters = Parameters
+panel-plot = Circuit Response
+parameters-heading = Circuit Parameters
+parameters-resistance = Resistance:
+parameters-capacitance = Capacitance:
+parameters-inductance = Inductance:
+parameters-voltage = Input Voltage:
+parameters-time = Simulation Time:
+parameters-method = Integration Method:
+parameters-method-euler = Euler
+parameters-method-rk4 = RK4
+parameters-method-trapezoidal = Trapezoidal
+parameters-run = RUN
+parameters-running = RUNNING...
+plot-time-axis = Time (s)
+plot-value-axis = Voltage (V) / Current (A)
+plot-voltage = Voltage
+plot-current = Current
+plot-no-results = No simulation results yet. Click RUN to begin simulation.
+
+## Actions and history
+
+category-simulation = Simulation
+action-run-simulation = Run simulation
+history-circuit-parameters = Circuit parameters
+
+## Notifications and status bar
+
+notify-simulation-finished = Simulation finished
+notify-time-steps = { $count ->
+    [one] 1 time step
+   *[other] { $count } time steps
+}
+notify-show-plot = Show plot
+notify-simulation-failed = Simulation failed
+notify-show-parameters = Show parameters
+notify-retry = Retry
+status-simulation-ready = ⏹ Simulation ready
+status-simulation-running = ▶ Simulation running
@@ -0,0 +1,43 @@
+# Messages de l’exemple, ajoutés à ceux de l’application dans main.rs
+
+## Panneaux
+
+panel-parameters = Paramètres
+panel-plot = Réponse du circuit
+parameters-heading = Paramètres du circuit
+parameters-resistance = Résistance :
+parameters-capacitance = Capacité :
+parameters-inductance = Inductance :
+parameters-voltage = Tension d’entrée :
+parameters-time = Durée de simulation :
+parameters-method = Méthode d’intégration :
+parameters-method-euler = Euler
+parameters-method-rk4 = RK4
+parameters-method-trapezoidal = Trapèzes
+parameters-run = LANCER
+parameters-running = EN COURS...
+plot-time-axis = Temps (s)
+plot-value-axis = Tension (V) / Courant (A)
+plot-voltage = Tension
+plot-current = Courant
+plot-no-results = Pas encore de résultats. Cliquer sur LANCER pour démarrer la simulation.
+
+## Actions et historique
+
+category-simulation = Simulation
+action-run-simulation = Lancer la simulation
+history-circuit-parameters = Paramètres du circuit
+
+## Notifications et barre d’état
+
+notify-simulation-finished = Simulation terminée
+notify-time-steps = { $count ->
+    [one] 1 pas de temps
+   *[other] { $count } pas de temps
+}
+notify-show-plot = Afficher le graphique
+notify-simulation-failed = Échec de la simulation
+notify-show-parameters = Afficher les paramètres
+notify-retry = Réessayer
+status-simulation-ready = ⏹ Simulation prête
+status-simulation-running = ▶ Simulation en cours
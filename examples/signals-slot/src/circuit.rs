@@ -24,7 +24,7 @@ impl Circuit {
         let mut k2 = Array1::<f64>::zeros(2);
         let mut k3 = Array1::<f64>::zeros(2);
         let mut k4 = Array1::<f64>::zeros(2);
-        let mut temp = Array1::<f64>::zeros(2);
+        let mut temp: Array1<f64>;
 
         // Initial conditions
         time_series.push(0.0);
@@ -3,135 +3,124 @@ mod state;
 mod types;
 mod slots;
 mod ui;
+#[cfg(test)]
+mod tests;
 
-use eframe::egui;
+use egui_dock::{DockState, NodeIndex};
 use egui_mobius::{Signal, Slot, factory};
+use egui_mobius_template::{tr, MobiusApp, StatusItem};
+use egui_mobius_template::history::{HistoryPanel, MergeRule};
+use egui_mobius_template::notifications::{Notification, NotificationsPanel};
+use egui_mobius_template::shortcuts::ShortcutsPanel;
 use std::sync::Arc;
-use env_logger;
 
 use crate::{
-    state::AppState,
+    state::{AppState, AppStateValues},
     slots::simulation::simulation_slot_thread,
-    types::CircuitMessage,
-    ui::{parameter_panel::show_parameter_panel, plot_panel::show_plot_panel},
+    types::{CircuitMessage, SimulationState},
+    ui::{parameter_panel::ParameterPanel, plot_panel::PlotPanel},
 };
 
-struct RlcApp {
-    state: Arc<AppState>,
-    signal_to_sim: Signal<CircuitMessage>,
-    slot_from_sim: Slot<CircuitMessage>,
+/// Default dock layout: parameters and history on the left, the plot
+/// on the right
+fn default_layout() -> DockState<&'static str> {
+    let mut layout = DockState::new(vec!["parameters", "history"]);
+    layout.main_surface_mut().split_right(NodeIndex::root(), 0.3, vec!["plot"]);
+    layout
 }
 
-impl RlcApp {
-    fn new(
-        _cc: &eframe::CreationContext<'_>,
-        state: Arc<AppState>,
-        signal_to_sim: Signal<CircuitMessage>,
-        mut slot_from_sim: Slot<CircuitMessage>,
-    ) -> Self {
-        // Set up message handlers for UI updates
-        let state_clone = state.clone();
-        slot_from_sim.start(move |msg| {
-            match msg {
-                CircuitMessage::SimulationStarted => {
-                    state_clone.start_simulation();
-                }
-                CircuitMessage::SimulationCompleted(results) => {
-                    state_clone.set_simulation_results(results);
-                }
-                CircuitMessage::SimulationError(error) => {
-                    state_clone.set_error(error);
-                }
-                _ => {}
-            }
-        });
-
-        // Trigger initial simulation
-        let initial_params = state.parameters.get();
-        signal_to_sim.send(CircuitMessage::ParamsUpdated(initial_params));
-
-        Self {
-            state,
-            signal_to_sim,
-            slot_from_sim,
-        }
-    }
-}
-
-impl eframe::App for RlcApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.with_layout(
-                egui::Layout::left_to_right(egui::Align::TOP),
-                |ui| {
-                    // Left panel - Parameters
-                    let panel_width = 300.0;
-                    let panel_height = ui.available_height();
-                    
-                    egui::Frame::new()
-                        .fill(ui.style().visuals.window_fill())
-                        .show(ui, |ui| {
-                            ui.set_width(panel_width);
-                            ui.set_min_height(panel_height);
-                            egui::ScrollArea::vertical()
-                                .auto_shrink([false; 2])
-                                .show(ui, |ui| {
-                                    show_parameter_panel(ui, &self.state, &self.signal_to_sim);
-                                });
-                        });
-
-                    ui.separator();
-
-                    // Right panel - Plot
-                    let available_width = ui.available_width();
-                    egui::Frame::new()
-                        .fill(ui.style().visuals.window_fill())
-                        .show(ui, |ui| {
-                            ui.set_min_width(available_width);
-                            ui.set_min_height(panel_height);
-                            show_plot_panel(ui, &self.state);
-                        });
-                });
-        });
-    }
+/// Keeps the slot receiving the simulation thread's messages alive
+struct SimulationSlot {
+    _slot: Slot<CircuitMessage>,
 }
 
 fn main() {
     env_logger::init();
 
-    let state = AppState::new();
-    
+    let state = Arc::new(AppState::new(AppStateValues::default()));
+
     // Create signal/slot pairs for simulation thread
     let (signal_to_sim, slot_to_sim) = factory::create_signal_slot::<CircuitMessage>();
-    let (_slot_from_sim, signal_from_sim) = factory::create_signal_slot::<CircuitMessage>();
+    let (signal_from_sim, slot_from_sim) = factory::create_signal_slot::<CircuitMessage>();
 
-    // Start simulation thread
+    // Start simulation thread, which reports back to the UI slot
     let state_clone = state.clone();
     simulation_slot_thread(
         slot_to_sim,
-        signal_to_sim.clone(),
+        signal_from_sim,
         state_clone,
     );
 
-    let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_titlebar_buttons_shown(true)
-            .with_min_inner_size((900.0, 600.0))
-            .with_resizable(true)
-            .with_max_inner_size((1200.0, 800.0)),
-        ..Default::default()
-    };
+    let result = MobiusApp::builder()
+        .title("RLC Circuit Simulator")
+        .config_dir_name("egui_mobius_signals_slot")
+        .single_instance()
+        .messages("en-US", include_str!("../locales/en-US.ftl"))
+        .messages("de", include_str!("../locales/de.ftl"))
+        .messages("fr", include_str!("../locales/fr.ftl"))
+        .min_size([900.0, 600.0])
+        .panel::<ParameterPanel>()
+        .panel::<PlotPanel>()
+        .panel::<HistoryPanel>()
+        .panel::<ShortcutsPanel>()
+        .panel::<NotificationsPanel>()
+        .actions(ParameterPanel::actions())
+        .default_layout(default_layout())
+        .setup(move |_cc, app| {
+            // Restore the circuit parameters of the last session and
+            // record their changes for undo/redo, merging drags of a value
+            app.settings.bind(&state.parameters);
+            app.history.track(&state.parameters, "history-circuit-parameters", MergeRule::continuous());
+            app.recorder.track(&state.parameters, "circuit.parameters");
+
+            // Set up message handlers for UI updates; the outcome of a
+            // run is notified whichever panels are open
+            let mut slot_from_sim = slot_from_sim;
+            let state_clone = state.clone();
+            let notifier = app.notifier.clone();
+            slot_from_sim.start(move |msg| {
+                match msg {
+                    CircuitMessage::SimulationStarted => {
+                        state_clone.start_simulation();
+                    }
+                    CircuitMessage::SimulationCompleted(results) => {
+                        notifier.notify(Notification::success(tr!("notify-simulation-finished"))
+                            .body(tr!("notify-time-steps", count = results.time_series.len()))
+                            .action(tr!("notify-show-plot"), "panel:plot"));
+                        state_clone.set_simulation_results(results);
+                    }
+                    CircuitMessage::SimulationError(error) => {
+                        notifier.notify(Notification::error(tr!("notify-simulation-failed"))
+                            .body(error.clone())
+                            .action(tr!("notify-show-parameters"), "panel:parameters")
+                            .action(tr!("notify-retry"), "action:simulation.run"));
+                        state_clone.set_error(error);
+                    }
+                    _ => {}
+                }
+            });
+
+            // Trigger initial simulation
+            let initial_params = state.parameters.get();
+            let _ = signal_to_sim.send(CircuitMessage::ParamsUpdated(initial_params));
+
+            // State the panels share besides the app context services
+            app.resources.insert(state);
+            app.resources.insert::<Signal<CircuitMessage>>(signal_to_sim);
+            app.resources.insert(SimulationSlot { _slot: slot_from_sim });
+        })
+        .on_update(|_ctx, app| {
+            if let Some(state) = app.resources.get::<Arc<AppState>>() {
+                let text = match state.sim_state.get() {
+                    SimulationState::Ready => tr!("status-simulation-ready"),
+                    SimulationState::Running => tr!("status-simulation-running"),
+                };
+                app.status.set(StatusItem::new("simulation", text).command("panel:parameters"));
+            }
+        })
+        .run();
 
-    if let Err(e) = eframe::run_native(
-        "RLC Circuit Simulator",
-        options,
-        Box::new(|cc| Ok(Box::new(RlcApp::new(
-            cc,
-            state,
-            signal_to_sim,
-            signal_from_sim,
-        )))),
-    ) {
+    if let Err(e) = result {
         eprintln!("Failed to run eframe: {:?}", e);
     }
 }
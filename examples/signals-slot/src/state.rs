@@ -1,47 +1,39 @@
-use egui_mobius_reactive::Dynamic;
-use std::sync::Arc;
+use egui_mobius_template::ReactiveState;
 use crate::types::{CircuitParameters, SimulationResults, SimulationState};
 
-#[derive(Clone)]
-pub struct AppState {
-    pub parameters: Dynamic<CircuitParameters>,
-    pub results: Dynamic<Option<SimulationResults>>,
-    pub sim_state: Dynamic<SimulationState>,
-    pub error_message: Dynamic<Option<String>>,
+/// AppStateValues
+///
+/// Plain values of the application state. `#[derive(ReactiveState)]`
+/// generates `AppState`, holding each field in a `Dynamic` with typed
+/// getters and setters such as `parameters()` and `set_parameters()`.
+#[derive(Clone, Default, ReactiveState)]
+#[reactive(name = "AppState")]
+pub struct AppStateValues {
+    pub parameters: CircuitParameters,
+    pub results: Option<SimulationResults>,
+    pub sim_state: SimulationState,
+    pub error_message: Option<String>,
 }
 
 impl AppState {
-    pub fn new() -> Arc<Self> {
-        Arc::new(Self {
-            parameters: Dynamic::new(CircuitParameters::default()),
-            results: Dynamic::new(None),
-            sim_state: Dynamic::new(SimulationState::Ready),
-            error_message: Dynamic::new(None),
-        })
-    }
-
-    pub fn update_parameters(&self, params: CircuitParameters) {
-        self.parameters.set(params);
-    }
-
     pub fn set_simulation_results(&self, results: SimulationResults) {
         // Update results only if different to prevent unnecessary redraws
-        if self.results.get().as_ref() != Some(&results) {
-            self.results.set(Some(results));
-            self.error_message.set(None);
-            self.sim_state.set(SimulationState::Ready);
+        if self.results().as_ref() != Some(&results) {
+            self.set_results(Some(results));
+            self.set_error_message(None);
+            self.set_sim_state(SimulationState::Ready);
         }
     }
 
     pub fn set_error(&self, error: String) {
-        self.error_message.set(Some(error));
-        self.sim_state.set(SimulationState::Ready);
+        self.set_error_message(Some(error));
+        self.set_sim_state(SimulationState::Ready);
     }
 
     pub fn start_simulation(&self) {
-        self.sim_state.set(SimulationState::Running);
-        self.error_message.set(None);
-        let params = self.parameters.get();
+        self.set_sim_state(SimulationState::Running);
+        self.set_error_message(None);
+        let params = self.parameters();
         println!("Starting simulation with parameters: R = {}, L = {}, C = {}, V = {}, t_max = {}", 
             params.resistance, params.inductance, params.capacitance, params.voltage, params.t_max);
     }
@@ -0,0 +1,135 @@
+//! The signals-slot template's parameter and plot panels, driven
+//! headlessly.
+use crate::state::{AppState, AppStateValues};
+use crate::types::{CircuitMessage, SimulationResults, SimulationState};
+use crate::ui::parameter_panel::show_parameter_panel;
+use crate::ui::plot_panel::show_plot_panel;
+use egui::Key;
+use egui_mobius::factory;
+use egui_mobius_template::i18n;
+use egui_mobius_template::recorder::{Recorder, ReplaySpeed};
+use egui_mobius_template::testing::Harness;
+use std::sync::Arc;
+
+/// The template's messages, added by its `main` in the application
+fn add_messages() {
+    i18n::add_messages("en-US", include_str!("../locales/en-US.ftl")).unwrap();
+}
+
+fn state() -> Arc<AppState> {
+    add_messages();
+    Arc::new(AppState::new(AppStateValues::default()))
+}
+
+#[test]
+fn parameter_panel_is_rendered() {
+    let state = state();
+    let (signal, _slot) = factory::create_signal_slot::<CircuitMessage>();
+    let recorder = Recorder::new();
+    let mut harness = Harness::new();
+    harness.run(|ui| show_parameter_panel(ui, &state, &signal, &recorder));
+
+    harness.assert_snapshot("signals_slot_parameter_panel");
+}
+
+#[test]
+fn typed_parameters_are_applied() {
+    let state = state();
+    let (signal, _slot) = factory::create_signal_slot::<CircuitMessage>();
+    let recorder = Recorder::new();
+    let mut harness = Harness::new();
+    let mut show = |ui: &mut egui::Ui| show_parameter_panel(ui, &state, &signal, &recorder);
+    harness.run(&mut show);
+
+    // Clicking a value edits it with its text selected
+    harness.click("1.1e-1");
+    harness.run(&mut show);
+    harness.type_text("2.5");
+    harness.press_key(Key::Enter);
+    harness.run(&mut show);
+
+    assert_eq!(state.parameters().resistance, 2.5);
+    assert!(harness.contains("2.5e0"));
+}
+
+#[test]
+fn run_button_starts_a_simulation() {
+    let state = state();
+    let (signal, slot) = factory::create_signal_slot::<CircuitMessage>();
+    let recorder = Recorder::new();
+    let mut harness = Harness::new();
+    let mut show = |ui: &mut egui::Ui| show_parameter_panel(ui, &state, &signal, &recorder);
+    harness.run(&mut show);
+
+    harness.click("RUN");
+    harness.run(&mut show);
+    let message = slot.receiver.lock().unwrap().try_recv();
+    assert!(matches!(message, Ok(CircuitMessage::SimulationStarted)));
+
+    // The button is disabled while the simulation runs
+    state.set_sim_state(SimulationState::Running);
+    harness.run(&mut show);
+    assert!(harness.contains("RUNNING..."));
+    harness.click("RUNNING...");
+    harness.run(&mut show);
+    assert!(slot.receiver.lock().unwrap().try_recv().is_err());
+}
+
+#[test]
+fn recorded_edits_and_runs_are_replayed_headlessly() {
+    let state = state();
+    let (signal, _slot) = factory::create_signal_slot::<CircuitMessage>();
+    let mut harness = Harness::new();
+    let recorder = Recorder::new().with_repaint(harness.ctx());
+    recorder.track(&state.parameters, "circuit.parameters");
+    let mut show = |ui: &mut egui::Ui| {
+        recorder.poll();
+        show_parameter_panel(ui, &state, &signal, &recorder);
+    };
+    harness.run(&mut show);
+
+    recorder.start("Signals-Slot");
+    harness.click("1.1e-1");
+    harness.run(&mut show);
+    harness.type_text("2.5");
+    harness.press_key(Key::Enter);
+    harness.run(&mut show);
+    harness.click("RUN");
+    harness.run(&mut show);
+    let recording = recorder.stop().unwrap();
+
+    // Replay against fresh state, one input per frame
+    let state = self::state();
+    let (signal, slot) = factory::create_signal_slot::<CircuitMessage>();
+    let mut harness = Harness::new();
+    let recorder = Recorder::new().with_repaint(harness.ctx());
+    recorder.track(&state.parameters, "circuit.parameters");
+    recorder.replay(recording, ReplaySpeed::Stepped).unwrap();
+    harness.run(|ui| {
+        recorder.poll();
+        show_parameter_panel(ui, &state, &signal, &recorder);
+    });
+
+    assert!(!recorder.is_replaying());
+    assert_eq!(state.parameters().resistance, 2.5);
+    let message = slot.receiver.lock().unwrap().try_recv();
+    assert!(matches!(message, Ok(CircuitMessage::SimulationStarted)));
+}
+
+#[test]
+fn plot_panel_shows_results() {
+    let state = state();
+    let mut harness = Harness::new();
+    harness.run(|ui| show_plot_panel(ui, &state));
+    assert!(harness.contains("No simulation results yet"));
+
+    state.set_simulation_results(SimulationResults {
+        time_series: vec![0.0, 1e-3, 2e-3, 3e-3],
+        voltage_series: vec![0.0, 2.5, 4.0, 5.0],
+        current_series: vec![0.0, 1.0, 0.5, 0.2],
+    });
+    harness.run(|ui| show_plot_panel(ui, &state));
+    assert!(!harness.contains("No simulation results yet"));
+    assert!(harness.contains("Voltage"));
+    harness.assert_snapshot("signals_slot_plot_panel");
+}
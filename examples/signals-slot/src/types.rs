@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use egui_mobius_template::SettingsSection;
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub enum SimulationState {
+    #[default]
     Ready,
     Running,
 }
@@ -13,7 +15,7 @@ pub enum IntegrationMethod {
     TrapezoidalDamping,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CircuitParameters {
     pub capacitance: f64,  // Capacitance (F)
     pub inductance: f64,   // Inductance (H)
@@ -38,6 +40,11 @@ impl Default for CircuitParameters {
     }
 }
 
+// Circuit parameters are restored on the next launch
+impl SettingsSection for CircuitParameters {
+    const KEY: &'static str = "circuit";
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimulationResults {
     pub time_series: Vec<f64>,
@@ -56,6 +63,7 @@ pub enum CircuitMessage {
 }
 
 impl CircuitMessage {
+    #[allow(dead_code)]
     pub fn route(&self) -> &str {
         match self {
             CircuitMessage::ParamsUpdated(_) => "params_updated",
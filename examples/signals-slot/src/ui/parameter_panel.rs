@@ -1,5 +1,6 @@
 use egui::{Ui, Grid};
 use egui_mobius::signals::Signal;
+use egui_mobius_template::{tr, Action, AppContext, Menu, Panel, Recorder};
 use std::sync::Arc;
 
 use crate::{
@@ -7,19 +8,68 @@ use crate::{
     types::{IntegrationMethod, CircuitMessage, SimulationState},
 };
 
-pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<CircuitMessage>) {
+/// ParameterPanel
+///
+/// Dock panel with the circuit parameters and the RUN button.
+#[derive(Default)]
+pub struct ParameterPanel;
+
+impl ParameterPanel {
+    /// Simulation actions offered in the command palette and the File menu
+    pub fn actions() -> [Action; 1] {
+        [
+            Action::new("simulation.run", "action-run-simulation", |ctx| {
+                let Some(signal) = ctx.resources.get::<Signal<CircuitMessage>>() else {
+                    return;
+                };
+                let _ = signal.send(CircuitMessage::SimulationStarted);
+            })
+            .category("category-simulation")
+            .menu(Menu::File)
+            .shortcut(egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F5)),
+        ]
+    }
+}
+
+impl Panel for ParameterPanel {
+    fn id(&self) -> &'static str {
+        "parameters"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-parameters")
+    }
+
+    fn icon(&self) -> &'static str {
+        "🎛"
+    }
+
+    fn ui(&mut self, ui: &mut Ui, ctx: &mut AppContext) {
+        let (Some(state), Some(signal)) = (
+            ctx.resources.get::<Arc<AppState>>(),
+            ctx.resources.get::<Signal<CircuitMessage>>(),
+        ) else {
+            return;
+        };
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| show_parameter_panel(ui, state, signal, ctx.recorder));
+    }
+}
+
+pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<CircuitMessage>, recorder: &Recorder) {
     let mut params = state.parameters.get();
     let mut changed = false;
 
     ui.vertical(|ui| {
-        ui.heading("Circuit Parameters");
+        ui.heading(tr!("parameters-heading"));
         ui.add_space(8.0);
 
         Grid::new("params_grid")
             .spacing([8.0, 8.0])
             .show(ui, |ui| {
                 // Resistance
-                ui.label("Resistance:");
+                ui.label(tr!("parameters-resistance"));
                 ui.horizontal(|ui| {
                     if ui.add(egui::DragValue::new(&mut params.resistance)
                         .speed(0.01)
@@ -34,7 +84,7 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
                 ui.end_row();
 
                 // Capacitance
-                ui.label("Capacitance:");
+                ui.label(tr!("parameters-capacitance"));
                 ui.horizontal(|ui| {
                     if ui.add(egui::DragValue::new(&mut params.capacitance)
                         .speed(0.1e-6)
@@ -49,7 +99,7 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
                 ui.end_row();
 
                 // Inductance
-                ui.label("Inductance:");
+                ui.label(tr!("parameters-inductance"));
                 ui.horizontal(|ui| {
                     if ui.add(egui::DragValue::new(&mut params.inductance)
                         .speed(0.1e-3)
@@ -64,7 +114,7 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
                 ui.end_row();
 
                 // Input Voltage
-                ui.label("Input Voltage:");
+                ui.label(tr!("parameters-voltage"));
                 ui.horizontal(|ui| {
                     if ui.add(egui::DragValue::new(&mut params.voltage)
                         .speed(0.1)
@@ -79,7 +129,7 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
                 ui.end_row();
 
                 // Simulation time
-                ui.label("Simulation Time:");
+                ui.label(tr!("parameters-time"));
                 ui.horizontal(|ui| {
                     if ui.add(egui::DragValue::new(&mut params.t_max)
                         .speed(0.1e-3)
@@ -100,24 +150,21 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
 
         // Integration method
         ui.horizontal(|ui| {
-            ui.label("Integration Method:");
+            ui.label(tr!("parameters-method"));
             egui::ComboBox::new("integration_method", "")
-                .selected_text(format!("{:?}", params.method))
+                .selected_text(tr!(method_label(&params.method)))
                 .show_ui(ui, |ui| {
-                    if ui.selectable_value(&mut params.method, IntegrationMethod::Euler, "Euler").changed() {
-                        changed = true;
-                    }
-                    if ui.selectable_value(&mut params.method, IntegrationMethod::RungeKutta4, "RK4").changed() {
-                        changed = true;
-                    }
-                    if ui.selectable_value(&mut params.method, IntegrationMethod::TrapezoidalDamping, "Trapezoidal").changed() {
-                        changed = true;
+                    for method in [IntegrationMethod::Euler, IntegrationMethod::RungeKutta4, IntegrationMethod::TrapezoidalDamping] {
+                        let label = tr!(method_label(&method));
+                        if ui.selectable_value(&mut params.method, method, label).changed() {
+                            changed = true;
+                        }
                     }
                 });
         });
 
         if changed {
-            state.update_parameters(params);
+            state.set_parameters(params);
         }
 
         // Simulation control
@@ -128,15 +175,27 @@ pub fn show_parameter_panel(ui: &mut Ui, state: &Arc<AppState>, signal: &Signal<
         ui.horizontal(|ui| {
             let sim_state = state.sim_state.get();
             let button_text = if sim_state == SimulationState::Running {
-                "RUNNING..."
+                tr!("parameters-running")
             } else {
-                "RUN"
+                tr!("parameters-run")
             };
             let button = egui::Button::new(button_text);
 
-            if ui.add_enabled(sim_state == SimulationState::Ready, button).clicked() {
+            // A replayed press is ignored too while a simulation runs
+            let ready = sim_state == SimulationState::Ready;
+            let clicked = ui.add_enabled(ready, button).clicked();
+            if recorder.press("parameters.run", clicked) && ready {
                 let _ = signal.send(CircuitMessage::SimulationStarted);
             }
         });
     });
 }
+
+/// Message id of the name of `method`
+fn method_label(method: &IntegrationMethod) -> &'static str {
+    match method {
+        IntegrationMethod::Euler => "parameters-method-euler",
+        IntegrationMethod::RungeKutta4 => "parameters-method-rk4",
+        IntegrationMethod::TrapezoidalDamping => "parameters-method-trapezoidal",
+    }
+}
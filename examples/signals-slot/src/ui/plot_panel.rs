@@ -1,11 +1,42 @@
 use egui::{Ui, Color32};
 use egui_plot::{Plot, Line, PlotPoints, Legend};
+use egui_mobius_template::{tr, AppContext, Panel};
 use std::sync::Arc;
 use crate::state::AppState;
 
+/// PlotPanel
+///
+/// Dock panel plotting the voltage and current of the last simulation.
+#[derive(Default)]
+pub struct PlotPanel;
+
+impl Panel for PlotPanel {
+    fn id(&self) -> &'static str {
+        "plot"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-plot")
+    }
+
+    fn icon(&self) -> &'static str {
+        "📈"
+    }
+
+    fn allows_duplicates(&self) -> bool {
+        true
+    }
+
+    fn ui(&mut self, ui: &mut Ui, ctx: &mut AppContext) {
+        if let Some(state) = ctx.resources.get::<Arc<AppState>>() {
+            show_plot_panel(ui, state);
+        }
+    }
+}
+
 pub fn show_plot_panel(ui: &mut Ui, state: &Arc<AppState>) {
     ui.vertical(|ui| {
-        ui.heading("Circuit Response");
+        ui.heading(tr!("panel-plot"));
         ui.add_space(8.0);
         
         let available_height = ui.available_height() - 20.0;
@@ -17,8 +48,8 @@ pub fn show_plot_panel(ui: &mut Ui, state: &Arc<AppState>) {
                 .height(available_height)
                 .width(available_width)
                 .legend(Legend::default())
-                .x_axis_label("Time (s)")
-                .y_axis_label("Voltage (V) / Current (A)")
+                .x_axis_label(tr!("plot-time-axis"))
+                .y_axis_label(tr!("plot-value-axis"))
                 .allow_zoom(true)
                 .allow_drag(true)
                 .allow_scroll(true)
@@ -41,18 +72,18 @@ pub fn show_plot_panel(ui: &mut Ui, state: &Arc<AppState>) {
                     .map(|(&t, &i)| [t, i])
                     .collect();
 
-                plot_ui.line(Line::new("Voltage", voltage_points)
+                plot_ui.line(Line::new(tr!("plot-voltage"), voltage_points)
                     .color(Color32::from_rgb(100, 200, 255))
                     .width(2.0));
                     
-                plot_ui.line(Line::new("Current", current_points)
+                plot_ui.line(Line::new(tr!("plot-current"), current_points)
                     .color(Color32::from_rgb(255, 150, 150))
                     .width(2.0));
             });
         } else {
             ui.allocate_space(egui::vec2(available_width, available_height));
             ui.centered_and_justified(|ui| {
-                ui.label("No simulation results yet. Click RUN to begin simulation.");
+                ui.label(tr!("plot-no-results"));
             });
         }
     });
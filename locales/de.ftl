@@ -0,0 +1,206 @@
+# Meldungen der Anwendungsoberfläche, siehe src/i18n.rs
+
+language-name = Deutsch
+
+## Formate von Zahlen, Datum und Uhrzeit (chrono-strftime-Muster)
+
+locale-decimal-separator = ,
+locale-group-separator = .
+locale-date = %d.%m.%Y
+locale-time = %H:%M:%S
+locale-time-12h = %-I:%M:%S %p
+locale-time-24h = %H:%M:%S
+locale-datetime = %d.%m.%Y %H:%M:%S
+
+## Menüleiste
+
+menu-file = Datei
+menu-edit = Bearbeiten
+menu-view = Ansicht
+menu-help = Hilfe
+menu-open-session = Sitzung öffnen
+menu-no-sessions = Keine gespeicherten Sitzungen
+menu-save-session = Sitzung speichern…
+menu-start-recording = Aufnahme starten
+menu-stop-recording = Aufnahme beenden
+menu-replay-recording = Aufnahme abspielen
+menu-no-recordings = Keine Aufnahmen
+menu-quit = Beenden
+menu-command-palette = Befehlspalette…
+menu-theme = Farbschema
+menu-no-themes = Keine gespeicherten Farbschemata
+menu-language = Sprache
+menu-reset-layout = Layout zurücksetzen
+menu-keyboard-shortcuts = Tastenkürzel
+menu-about = Über { $app }
+
+## Befehle der Palette und der Tastenkürzel
+
+command-open-panel = Öffnen: { $panel }
+command-palette = Ansicht: Befehlspalette
+command-reset-layout = Ansicht: Layout zurücksetzen
+command-save-session = Datei: Sitzung speichern…
+command-open-session = Datei: Sitzung „{ $name }“ öffnen
+command-start-recording = Datei: Aufnahme starten
+command-stop-recording = Datei: Aufnahme beenden
+command-toggle-recording = Datei: Aufnahme starten oder beenden
+command-replay-recording = Datei: Aufnahme „{ $name }“ abspielen
+command-quit = Datei: Beenden
+command-about = Hilfe: Über { $app }
+command-theme = Farbschema: { $name }
+command-language = Sprache: { $name }
+
+## Eingebaute Aktionen
+
+category-edit = Bearbeiten
+category-file = Datei
+action-undo = Rückgängig
+action-redo = Wiederholen
+action-copy-log = Protokoll kopieren
+action-export-log = Protokoll exportieren
+category-help = Hilfe
+action-export-diagnostics = Diagnosedaten exportieren
+
+## Reiter
+
+tab-duplicate = Duplizieren
+tab-move-to-split = In neue Teilung verschieben
+tab-split-right = Rechts
+tab-split-below = Unten
+tab-split-left = Links
+tab-split-above = Oben
+tab-already-open = Bereits geöffnet, klicken zum Anzeigen
+
+## Statusleiste
+
+status-logs-tooltip = { $errors ->
+    [one] 1 Fehler
+   *[other] { $errors } Fehler
+} in { $total ->
+    [one] 1 Protokolleintrag
+   *[other] { $total } Protokolleinträgen
+}
+status-theme-tooltip = Farbschema des Protokolls
+status-custom-colors = Eigene Farben
+status-unsaved = ● Nicht gespeichert
+status-unsaved-tooltip = Einstellungen noch nicht in { $path } geschrieben, klicken zum Speichern
+status-saved = ✔ Gespeichert
+status-saved-tooltip = Einstellungen in { $path } gespeichert
+status-recording-tooltip = Aufnahme läuft, bisher { $count ->
+    [one] 1 Eingabe
+   *[other] { $count } Eingaben
+}. Klicken zum Beenden und Speichern
+status-replaying-tooltip = Aufnahme wird abgespielt, klicken zum Beenden
+
+## Dialoge
+
+button-save = Speichern
+button-cancel = Abbrechen
+button-clear = Leeren
+button-reset = Zurücksetzen
+button-apply = Anwenden
+button-copy-report = Bericht kopieren
+button-close = Schließen
+session-dialog-title = Sitzung speichern
+session-name-hint = Name der Sitzung
+session-replaces = Ersetzt die gespeicherte Sitzung dieses Namens
+about-built-with = Erstellt mit egui_mobius_template { $version }
+about-settings = Einstellungen: { $path }
+crash-title = Absturzbericht
+crash-heading = { $app } wurde am { $time } unerwartet beendet
+crash-location = bei { $location } im Thread „{ $thread }“
+crash-backtrace = Backtrace
+crash-logs = { $count ->
+    [one] Letzter Protokolleintrag
+   *[other] Letzte { $count } Protokolleinträge
+}
+crash-report-file = Bericht: { $path }
+
+## Benachrichtigungen der Oberfläche
+
+notify-load-settings-failed = Einstellungen konnten nicht geladen werden
+notify-settings-error = Fehler in den Einstellungen
+notify-save-settings-failed = Einstellungen konnten nicht gespeichert werden
+notify-session-saved = Sitzung „{ $name }“ gespeichert
+notify-save-session-failed = Sitzung konnte nicht gespeichert werden
+notify-open-session-failed = Sitzung konnte nicht geöffnet werden
+notify-recording-saved = Aufnahme mit { $count ->
+    [one] 1 Eingabe
+   *[other] { $count } Eingaben
+} gespeichert
+notify-save-recording-failed = Aufnahme konnte nicht gespeichert werden
+notify-replay-failed = Aufnahme konnte nicht abgespielt werden
+notify-quit-failed = Einstellungen nicht gespeichert, Anwendung läuft weiter
+notify-quit-anyway = Trotzdem beenden
+notify-log-exported = Protokoll exportiert
+notify-export-log-failed = Protokoll konnte nicht exportiert werden
+notify-crashed = Eine Hintergrundaufgabe ist abgestürzt
+notify-diagnostics-exported = Diagnosedaten exportiert
+notify-export-diagnostics-failed = Diagnosedaten konnten nicht exportiert werden
+
+## Verlauf
+
+panel-history = Verlauf
+history-undo = ↶ Rückgängig
+history-redo = ↷ Wiederholen
+history-initial-state = Ausgangszustand
+history-log-colors = Protokollfarben
+
+## Benachrichtigungen
+
+panel-notifications = Benachrichtigungen
+notifications-count = { $count ->
+    [one] 1 Benachrichtigung
+   *[other] { $count } Benachrichtigungen
+}
+notifications-empty = Keine Benachrichtigungen
+notifications-dismiss = Schließen
+
+## Tastenkürzel
+
+panel-shortcuts = Tastenkürzel
+shortcuts-reset-all = Alle zurücksetzen
+shortcuts-conflicts = ⚠ { $count ->
+    [one] 1 Konflikt
+   *[other] { $count } Konflikte
+}
+shortcuts-press = Tastenkürzel drücken…
+shortcuts-needs-modifier = Strg oder Alt dazunehmen oder F1-F35 drücken…
+shortcuts-hint = Klicken und neues Tastenkürzel drücken, Rücktaste entfernt es, Escape bricht ab
+shortcuts-also-bound = Auch belegt durch { $commands }
+
+## Befehlspalette
+
+palette-title = Befehlspalette
+palette-hint = Befehl eingeben…
+palette-no-match = Keine passenden Befehle
+palette-recent = Zuletzt verwendet
+
+## Protokollfarben und Farbschemata
+
+log-color-slider = Schieberegler
+log-color-option-a = Option A
+log-color-option-b = Option B
+log-color-option-c = Option C
+log-color-custom-event = Eigenes Ereignis
+log-color-run-stop = Start/Stopp
+log-color-time-format = Zeitformat
+log-color-clock = Uhr
+color-palette-default = Standard
+color-palette-deuteranopia = Für Deuteranopie
+color-palette-protanopia = Für Protanopie
+color-palette-tritanopia = Für Tritanopie
+themes-export-as = Exportieren als:
+themes-name-hint = Name des Farbschemas
+themes-export = Exportieren
+themes-import-file = Datei importieren:
+themes-import = Importieren
+themes-installed = Farbschemata:
+themes-choose = Vorschau…
+themes-none = Keine Farbschemata in { $dir }
+themes-rescan = Verzeichnis neu einlesen
+themes-preview = Vorschau: { $name }
+themes-sample = Ereignis { $name }
+themes-exists = Ein Farbschema namens „{ $name }“ ist bereits installiert
+themes-replace = Ersetzen
+themes-keep-both = Beide behalten als „{ $name }“
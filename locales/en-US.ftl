@@ -0,0 +1,206 @@
+# Messages of the application shell, see src/i18n.rs
+
+language-name = English (US)
+
+## Formats of numbers, dates and times (chrono strftime patterns)
+
+locale-decimal-separator = .
+locale-group-separator = ,
+locale-date = %m/%d/%Y
+locale-time = %-I:%M:%S %p
+locale-time-12h = %-I:%M:%S %p
+locale-time-24h = %H:%M:%S
+locale-datetime = %m/%d/%Y %-I:%M:%S %p
+
+## Menu bar
+
+menu-file = File
+menu-edit = Edit
+menu-view = View
+menu-help = Help
+menu-open-session = Open session
+menu-no-sessions = No saved sessions
+menu-save-session = Save session…
+menu-start-recording = Start recording
+menu-stop-recording = Stop recording
+menu-replay-recording = Replay recording
+menu-no-recordings = No recordings
+menu-quit = Quit
+menu-command-palette = Command palette…
+menu-theme = Theme
+menu-no-themes = No saved themes
+menu-language = Language
+menu-reset-layout = Reset layout
+menu-keyboard-shortcuts = Keyboard shortcuts
+menu-about = About { $app }
+
+## Commands of the palette and the shortcuts panel
+
+command-open-panel = Open: { $panel }
+command-palette = View: Command palette
+command-reset-layout = View: Reset layout
+command-save-session = File: Save session…
+command-open-session = File: Open session “{ $name }”
+command-start-recording = File: Start recording
+command-stop-recording = File: Stop recording
+command-toggle-recording = File: Start or stop recording
+command-replay-recording = File: Replay recording “{ $name }”
+command-quit = File: Quit
+command-about = Help: About { $app }
+command-theme = Theme: { $name }
+command-language = Language: { $name }
+
+## Built-in actions
+
+category-edit = Edit
+category-file = File
+action-undo = Undo
+action-redo = Redo
+action-copy-log = Copy log
+action-export-log = Export log
+category-help = Help
+action-export-diagnostics = Export diagnostics
+
+## Dock tabs
+
+tab-duplicate = Duplicate
+tab-move-to-split = Move to new split
+tab-split-right = Right
+tab-split-below = Below
+tab-split-left = Left
+tab-split-above = Above
+tab-already-open = Already open, click to show it
+
+## Status bar
+
+status-logs-tooltip = { $errors ->
+    [one] 1 error
+   *[other] { $errors } errors
+} in { $total ->
+    [one] 1 log entry
+   *[other] { $total } log entries
+}
+status-theme-tooltip = Log color theme
+status-custom-colors = Custom colors
+status-unsaved = ● Unsaved
+status-unsaved-tooltip = Settings not yet written to { $path }, click to save now
+status-saved = ✔ Saved
+status-saved-tooltip = Settings saved to { $path }
+status-recording-tooltip = Recording, { $count ->
+    [one] 1 input
+   *[other] { $count } inputs
+} so far. Click to stop and save
+status-replaying-tooltip = Replaying a recording, click to stop
+
+## Dialogs
+
+button-save = Save
+button-cancel = Cancel
+button-clear = Clear
+button-reset = Reset
+button-apply = Apply
+button-copy-report = Copy report
+button-close = Close
+session-dialog-title = Save session
+session-name-hint = Session name
+session-replaces = Replaces the saved session of that name
+about-built-with = Built with egui_mobius_template { $version }
+about-settings = Settings: { $path }
+crash-title = Crash report
+crash-heading = { $app } stopped unexpectedly on { $time }
+crash-location = at { $location } in thread “{ $thread }”
+crash-backtrace = Backtrace
+crash-logs = Last { $count ->
+    [one] log entry
+   *[other] { $count } log entries
+}
+crash-report-file = Report: { $path }
+
+## Notifications of the shell
+
+notify-load-settings-failed = Failed to load settings
+notify-settings-error = Settings error
+notify-save-settings-failed = Failed to save settings
+notify-session-saved = Saved session “{ $name }”
+notify-save-session-failed = Failed to save session
+notify-open-session-failed = Failed to open session
+notify-recording-saved = Saved recording of { $count ->
+    [one] 1 input
+   *[other] { $count } inputs
+}
+notify-save-recording-failed = Failed to save recording
+notify-replay-failed = Failed to replay recording
+notify-quit-failed = Settings could not be saved, not quitting
+notify-quit-anyway = Quit anyway
+notify-log-exported = Exported log
+notify-export-log-failed = Failed to export log
+notify-crashed = A background task crashed
+notify-diagnostics-exported = Exported diagnostics
+notify-export-diagnostics-failed = Failed to export diagnostics
+
+## History
+
+panel-history = History
+history-undo = ↶ Undo
+history-redo = ↷ Redo
+history-initial-state = Initial state
+history-log-colors = Log colors
+
+## Notifications panel
+
+panel-notifications = Notifications
+notifications-count = { $count ->
+    [one] 1 notification
+   *[other] { $count } notifications
+}
+notifications-empty = No notifications
+notifications-dismiss = Dismiss
+
+## Keyboard shortcuts panel
+
+panel-shortcuts = Keyboard Shortcuts
+shortcuts-reset-all = Reset all
+shortcuts-conflicts = ⚠ { $count ->
+    [one] 1 conflict
+   *[other] { $count } conflicts
+}
+shortcuts-press = Press a shortcut…
+shortcuts-needs-modifier = Add Ctrl or Alt, or press F1-F35…
+shortcuts-hint = Click and press the new shortcut, Backspace unbinds, Escape cancels
+shortcuts-also-bound = Also bound to { $commands }
+
+## Command palette
+
+palette-title = Command palette
+palette-hint = Type a command…
+palette-no-match = No matching commands
+palette-recent = Recently used
+
+## Log colors and themes
+
+log-color-slider = Slider
+log-color-option-a = Option A
+log-color-option-b = Option B
+log-color-option-c = Option C
+log-color-custom-event = Custom Event
+log-color-run-stop = Run/Stop
+log-color-time-format = Time Format
+log-color-clock = Clock
+color-palette-default = Default
+color-palette-deuteranopia = Deuteranopia safe
+color-palette-protanopia = Protanopia safe
+color-palette-tritanopia = Tritanopia safe
+themes-export-as = Export as:
+themes-name-hint = Theme name
+themes-export = Export
+themes-import-file = Import file:
+themes-import = Import
+themes-installed = Themes:
+themes-choose = Preview…
+themes-none = No themes in { $dir }
+themes-rescan = Rescan theme directory
+themes-preview = Preview: { $name }
+themes-sample = { $name } event
+themes-exists = A theme named "{ $name }" is already installed
+themes-replace = Replace it
+themes-keep-both = Keep both as "{ $name }"
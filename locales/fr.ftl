@@ -0,0 +1,206 @@
+# Messages de l’interface de l’application, voir src/i18n.rs
+
+language-name = Français
+
+## Formats des nombres, dates et heures (motifs strftime de chrono)
+
+locale-decimal-separator = ,
+locale-group-separator = {"\u202F"}
+locale-date = %d/%m/%Y
+locale-time = %H:%M:%S
+locale-time-12h = %-I:%M:%S %p
+locale-time-24h = %H:%M:%S
+locale-datetime = %d/%m/%Y %H:%M:%S
+
+## Barre de menus
+
+menu-file = Fichier
+menu-edit = Édition
+menu-view = Affichage
+menu-help = Aide
+menu-open-session = Ouvrir une session
+menu-no-sessions = Aucune session enregistrée
+menu-save-session = Enregistrer la session…
+menu-start-recording = Démarrer l’enregistrement
+menu-stop-recording = Arrêter l’enregistrement
+menu-replay-recording = Rejouer un enregistrement
+menu-no-recordings = Aucun enregistrement
+menu-quit = Quitter
+menu-command-palette = Palette de commandes…
+menu-theme = Thème
+menu-no-themes = Aucun thème enregistré
+menu-language = Langue
+menu-reset-layout = Réinitialiser la disposition
+menu-keyboard-shortcuts = Raccourcis clavier
+menu-about = À propos de { $app }
+
+## Commandes de la palette et des raccourcis
+
+command-open-panel = Ouvrir : { $panel }
+command-palette = Affichage : Palette de commandes
+command-reset-layout = Affichage : Réinitialiser la disposition
+command-save-session = Fichier : Enregistrer la session…
+command-open-session = Fichier : Ouvrir la session « { $name } »
+command-start-recording = Fichier : Démarrer l’enregistrement
+command-stop-recording = Fichier : Arrêter l’enregistrement
+command-toggle-recording = Fichier : Démarrer ou arrêter l’enregistrement
+command-replay-recording = Fichier : Rejouer l’enregistrement « { $name } »
+command-quit = Fichier : Quitter
+command-about = Aide : À propos de { $app }
+command-theme = Thème : { $name }
+command-language = Langue : { $name }
+
+## Actions intégrées
+
+category-edit = Édition
+category-file = Fichier
+action-undo = Annuler
+action-redo = Rétablir
+action-copy-log = Copier le journal
+action-export-log = Exporter le journal
+category-help = Aide
+action-export-diagnostics = Exporter les diagnostics
+
+## Onglets
+
+tab-duplicate = Dupliquer
+tab-move-to-split = Déplacer dans une nouvelle division
+tab-split-right = À droite
+tab-split-below = En dessous
+tab-split-left = À gauche
+tab-split-above = Au-dessus
+tab-already-open = Déjà ouvert, cliquer pour l’afficher
+
+## Barre d’état
+
+status-logs-tooltip = { $errors ->
+    [one] 1 erreur
+   *[other] { $errors } erreurs
+} sur { $total ->
+    [one] 1 entrée du journal
+   *[other] { $total } entrées du journal
+}
+status-theme-tooltip = Thème des couleurs du journal
+status-custom-colors = Couleurs personnalisées
+status-unsaved = ● Non enregistré
+status-unsaved-tooltip = Paramètres pas encore écrits dans { $path }, cliquer pour enregistrer
+status-saved = ✔ Enregistré
+status-saved-tooltip = Paramètres enregistrés dans { $path }
+status-recording-tooltip = Enregistrement en cours, { $count ->
+    [one] 1 saisie
+   *[other] { $count } saisies
+} jusqu’ici. Cliquer pour arrêter et enregistrer
+status-replaying-tooltip = Lecture d’un enregistrement, cliquer pour arrêter
+
+## Dialogues
+
+button-save = Enregistrer
+button-cancel = Annuler
+button-clear = Effacer
+button-reset = Réinitialiser
+button-apply = Appliquer
+button-copy-report = Copier le rapport
+button-close = Fermer
+session-dialog-title = Enregistrer la session
+session-name-hint = Nom de la session
+session-replaces = Remplace la session enregistrée de ce nom
+about-built-with = Créé avec egui_mobius_template { $version }
+about-settings = Paramètres : { $path }
+crash-title = Rapport de plantage
+crash-heading = { $app } s’est arrêté de façon inattendue le { $time }
+crash-location = à { $location } dans le thread « { $thread } »
+crash-backtrace = Pile d’appels
+crash-logs = { $count ->
+    [one] Dernière entrée du journal
+   *[other] { $count } dernières entrées du journal
+}
+crash-report-file = Rapport : { $path }
+
+## Notifications de l’interface
+
+notify-load-settings-failed = Impossible de charger les paramètres
+notify-settings-error = Erreur dans les paramètres
+notify-save-settings-failed = Impossible d’enregistrer les paramètres
+notify-session-saved = Session « { $name } » enregistrée
+notify-save-session-failed = Impossible d’enregistrer la session
+notify-open-session-failed = Impossible d’ouvrir la session
+notify-recording-saved = Enregistrement de { $count ->
+    [one] 1 saisie
+   *[other] { $count } saisies
+} sauvegardé
+notify-save-recording-failed = Impossible de sauvegarder l’enregistrement
+notify-replay-failed = Impossible de rejouer l’enregistrement
+notify-quit-failed = Paramètres non enregistrés, l’application reste ouverte
+notify-quit-anyway = Quitter quand même
+notify-log-exported = Journal exporté
+notify-export-log-failed = Impossible d’exporter le journal
+notify-crashed = Une tâche d’arrière-plan a planté
+notify-diagnostics-exported = Diagnostics exportés
+notify-export-diagnostics-failed = Impossible d’exporter les diagnostics
+
+## Historique
+
+panel-history = Historique
+history-undo = ↶ Annuler
+history-redo = ↷ Rétablir
+history-initial-state = État initial
+history-log-colors = Couleurs du journal
+
+## Notifications
+
+panel-notifications = Notifications
+notifications-count = { $count ->
+    [one] { $count } notification
+   *[other] { $count } notifications
+}
+notifications-empty = Aucune notification
+notifications-dismiss = Fermer
+
+## Raccourcis clavier
+
+panel-shortcuts = Raccourcis clavier
+shortcuts-reset-all = Tout réinitialiser
+shortcuts-conflicts = ⚠ { $count ->
+    [one] { $count } conflit
+   *[other] { $count } conflits
+}
+shortcuts-press = Appuyez sur un raccourci…
+shortcuts-needs-modifier = Ajoutez Ctrl ou Alt, ou appuyez sur F1-F35…
+shortcuts-hint = Cliquer puis appuyer sur le nouveau raccourci, Retour arrière le supprime, Échap annule
+shortcuts-also-bound = Également attribué à { $commands }
+
+## Palette de commandes
+
+palette-title = Palette de commandes
+palette-hint = Saisir une commande…
+palette-no-match = Aucune commande correspondante
+palette-recent = Utilisées récemment
+
+## Couleurs du journal et thèmes
+
+log-color-slider = Curseur
+log-color-option-a = Option A
+log-color-option-b = Option B
+log-color-option-c = Option C
+log-color-custom-event = Événement personnalisé
+log-color-run-stop = Marche/Arrêt
+log-color-time-format = Format de l’heure
+log-color-clock = Horloge
+color-palette-default = Par défaut
+color-palette-deuteranopia = Adapté à la deutéranopie
+color-palette-protanopia = Adapté à la protanopie
+color-palette-tritanopia = Adapté à la tritanopie
+themes-export-as = Exporter sous :
+themes-name-hint = Nom du thème
+themes-export = Exporter
+themes-import-file = Importer un fichier :
+themes-import = Importer
+themes-installed = Thèmes :
+themes-choose = Aperçu…
+themes-none = Aucun thème dans { $dir }
+themes-rescan = Relire le dossier des thèmes
+themes-preview = Aperçu : { $name }
+themes-sample = Événement { $name }
+themes-exists = Un thème nommé « { $name } » est déjà installé
+themes-replace = Le remplacer
+themes-keep-both = Garder les deux sous « { $name } »
@@ -0,0 +1,317 @@
+//! Color accessibility helpers
+//!
+//! Contrast ratios follow WCAG 2.x: the ratio of the relative luminance
+//! of the lighter and the darker color, ranging from 1:1 to 21:1. Log
+//! text is normal-size text, so AA requires 4.5:1 and AAA 7:1.
+//!
+//! `Palette` provides `LogColors` presets whose entries stay
+//! distinguishable for the common forms of color blindness, with a
+//! variant for egui's dark and light theme. `Palette::simulate` shows a
+//! color as seen with the palette's color vision deficiency and
+//! `color_difference` tells how far apart two colors look.
+use crate::LogColors;
+use egui::Color32;
+
+/// Minimum contrast ratio for WCAG AA (normal text)
+pub const WCAG_AA: f32 = 4.5;
+
+/// Minimum contrast ratio for WCAG AAA (normal text)
+pub const WCAG_AAA: f32 = 7.0;
+
+/// Minimum contrast ratio for WCAG AA large text
+pub const WCAG_AA_LARGE: f32 = 3.0;
+
+/// Background the panels, and the log text in them, are drawn on in
+/// egui's dark or light theme
+pub fn background(dark_mode: bool) -> Color32 {
+    if dark_mode {
+        egui::Visuals::dark().panel_fill
+    } else {
+        egui::Visuals::light().panel_fill
+    }
+}
+
+/// Linear value of an sRGB channel
+fn linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// sRGB channel of a linear value
+fn gamma(v: f64) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let c = if v <= 0.003_130_8 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round() as u8
+}
+
+/// Relative luminance of an opaque sRGB color
+pub fn relative_luminance(color: Color32) -> f32 {
+    (0.2126 * linear(color.r()) + 0.7152 * linear(color.g()) + 0.0722 * linear(color.b())) as f32
+}
+
+/// Perceived difference of two opaque colors, the CIE76 distance of
+/// their CIELAB coordinates. Around 2 is just noticeable; log entries
+/// should differ by well over 10.
+pub fn color_difference(a: Color32, b: Color32) -> f32 {
+    let lab = |color: Color32| {
+        let [r, g, b] = [linear(color.r()), linear(color.g()), linear(color.b())];
+        // D65 white point
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.950_47;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.088_83;
+        let f = |t: f64| if t > 0.008_856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+        [116.0 * f(y) - 16.0, 500.0 * (f(x) - f(y)), 200.0 * (f(y) - f(z))]
+    };
+    let (a, b) = (lab(a), lab(b));
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt() as f32
+}
+
+/// Contrast ratio between two colors, from 1.0 to 21.0
+///
+/// Transparency is ignored: `foreground` is treated as drawn opaque.
+pub fn contrast_ratio(foreground: Color32, background: Color32) -> f32 {
+    let [r, g, b, _] = foreground.to_srgba_unmultiplied();
+    let a = relative_luminance(Color32::from_rgb(r, g, b));
+    let b = relative_luminance(background);
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    ((lighter as f64 + 0.05) / (darker as f64 + 0.05)) as f32
+}
+
+/// ContrastLevel
+///
+/// WCAG conformance level reached by a contrast ratio for normal text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContrastLevel {
+    /// Below 3:1, illegible for most readers
+    Fail,
+    /// At least 3:1, only sufficient for large text
+    AaLarge,
+    /// At least 4.5:1
+    Aa,
+    /// At least 7:1
+    Aaa,
+}
+
+impl ContrastLevel {
+    pub fn from_ratio(ratio: f32) -> Self {
+        if ratio >= WCAG_AAA {
+            ContrastLevel::Aaa
+        } else if ratio >= WCAG_AA {
+            ContrastLevel::Aa
+        } else if ratio >= WCAG_AA_LARGE {
+            ContrastLevel::AaLarge
+        } else {
+            ContrastLevel::Fail
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContrastLevel::Fail => "Fail",
+            ContrastLevel::AaLarge => "AA Large",
+            ContrastLevel::Aa => "AA",
+            ContrastLevel::Aaa => "AAA",
+        }
+    }
+}
+
+/// Lighten or darken `foreground` until it reaches `target` contrast
+/// against `background`, keeping its hue as far as possible.
+///
+/// The color is mixed towards white on dark backgrounds and towards
+/// black on light ones; if even that extreme is not enough it is used
+/// as-is. Colors that already reach the target are returned unchanged.
+pub fn fix_contrast(foreground: Color32, background: Color32, target: f32) -> Color32 {
+    if contrast_ratio(foreground, background) >= target {
+        return foreground;
+    }
+
+    let towards = if relative_luminance(background) < 0.18 {
+        Color32::WHITE
+    } else {
+        Color32::BLACK
+    };
+    let [r, g, b, a] = foreground.to_srgba_unmultiplied();
+    let mix = |t: f32| {
+        let lerp = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+        Color32::from_rgba_unmultiplied(lerp(r, towards.r()), lerp(g, towards.g()), lerp(b, towards.b()), a)
+    };
+
+    // Binary search for the smallest mix that reaches the target
+    let (mut low, mut high) = (0.0_f32, 1.0_f32);
+    for _ in 0..16 {
+        let mid = (low + high) / 2.0;
+        if contrast_ratio(mix(mid), background) >= target {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    mix(high)
+}
+
+/// Palette
+///
+/// Preset log color palettes, each reaching WCAG AA against the panel
+/// background of the theme they are made for. Apart from `Default`, the
+/// presets take their colors from the Okabe-Ito and Paul Tol palettes,
+/// which are designed for color blind readers, lightened or darkened
+/// for contrast; they are chosen so that no two entries look alike with
+/// the given type of color blindness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    Default,
+    /// Red-green, reduced sensitivity to green
+    Deuteranopia,
+    /// Red-green, reduced sensitivity to red
+    Protanopia,
+    /// Blue-yellow
+    Tritanopia,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Default,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+    ];
+
+    /// Name of the palette in the current language
+    pub fn label(&self) -> String {
+        crate::i18n::tr(match self {
+            Palette::Default => "color-palette-default",
+            Palette::Deuteranopia => "color-palette-deuteranopia",
+            Palette::Protanopia => "color-palette-protanopia",
+            Palette::Tritanopia => "color-palette-tritanopia",
+        })
+    }
+
+    /// The log colors of this palette for the dark or the light theme
+    pub fn colors(&self, dark_mode: bool) -> LogColors {
+        let hex = |rgb: u32| Color32::from_rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8);
+        match (self, dark_mode) {
+            (Palette::Default, true) => LogColors::default(),
+            (Palette::Default, false) => {
+                let mut colors = LogColors::default();
+                colors.fix_contrast(background(false), WCAG_AA);
+                colors
+            }
+            (Palette::Deuteranopia, true) => LogColors {
+                clock: hex(0x88CCEE),        // Cyan (Tol)
+                slider: hex(0xD66206),       // Vermillion (Okabe-Ito)
+                option_a: hex(0xF0E442),     // Yellow (Okabe-Ito)
+                option_b: hex(0x268CC5),     // Blue (Tol)
+                option_c: hex(0x3B964A),     // Green (Tol)
+                time_format: hex(0xDDDDDD),  // Light gray
+                custom_event: hex(0xDDCC77), // Sand (Tol)
+                run_stop_log: hex(0x009988), // Teal (Tol)
+            },
+            (Palette::Deuteranopia, false) => LogColors {
+                clock: hex(0x0076B9),        // Blue (Tol)
+                slider: hex(0xBA5200),       // Vermillion (Okabe-Ito)
+                option_a: hex(0x7C7229),     // Olive yellow (Tol)
+                option_b: hex(0x332288),     // Indigo (Tol)
+                option_c: hex(0x117733),     // Green (Tol)
+                time_format: hex(0x555555),  // Dark gray
+                custom_event: hex(0xAA4499), // Purple (Tol)
+                run_stop_log: hex(0xAD5665), // Rose (Tol)
+            },
+            (Palette::Protanopia, true) => LogColors {
+                clock: hex(0x33BBEE),        // Cyan (Tol)
+                slider: hex(0xD66206),       // Vermillion (Okabe-Ito)
+                option_a: hex(0xF0E442),     // Yellow (Okabe-Ito)
+                option_b: hex(0xBF6799),     // Purple (Tol)
+                option_c: hex(0x009988),     // Teal (Tol)
+                time_format: hex(0xDDDDDD),  // Light gray
+                custom_event: hex(0xDDCC77), // Sand (Tol)
+                run_stop_log: hex(0xD75F44), // Red (Tol)
+            },
+            (Palette::Protanopia, false) => LogColors {
+                clock: hex(0x0076B9),        // Blue (Tol)
+                slider: hex(0x976800),       // Orange (Okabe-Ito)
+                option_a: hex(0x882255),     // Wine (Tol)
+                option_b: hex(0x21799B),     // Cyan (Tol)
+                option_c: hex(0x117733),     // Green (Tol)
+                time_format: hex(0x707070),  // Gray
+                custom_event: hex(0x332288), // Indigo (Tol)
+                run_stop_log: hex(0x00805E), // Bluish green (Okabe-Ito)
+            },
+            (Palette::Tritanopia, true) => LogColors {
+                clock: hex(0x66CCEE),        // Cyan (Tol)
+                slider: hex(0xE69F00),       // Orange (Okabe-Ito)
+                option_a: hex(0xEF3C7D),     // Magenta (Tol)
+                option_b: hex(0x43945E),     // Green (Tol)
+                option_c: hex(0x877CB9),     // Indigo (Tol)
+                time_format: hex(0xDDDDDD),  // Light gray
+                custom_event: hex(0xCCBB44), // Yellow (Tol)
+                run_stop_log: hex(0xBA67AC), // Purple (Tol)
+            },
+            (Palette::Tritanopia, false) => LogColors {
+                clock: hex(0x332288),        // Indigo (Tol)
+                slider: hex(0x976800),       // Orange (Okabe-Ito)
+                option_a: hex(0xCC3311),     // Red (Tol)
+                option_b: hex(0x208131),     // Green (Tol)
+                option_c: hex(0xAA4499),     // Purple (Tol)
+                time_format: hex(0x707070),  // Gray
+                custom_event: hex(0xBA5200), // Vermillion (Okabe-Ito)
+                run_stop_log: hex(0x882255), // Wine (Tol)
+            },
+        }
+    }
+
+    /// `color` as seen with the color vision deficiency this palette is
+    /// made for, using the full-severity matrices of Machado et al.
+    /// (2009). `Default` returns it unchanged.
+    pub fn simulate(&self, color: Color32) -> Color32 {
+        let matrix = match self {
+            Palette::Default => return color,
+            Palette::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Palette::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Palette::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        };
+        let rgb = [linear(color.r()), linear(color.g()), linear(color.b())];
+        let channel = |row: [f64; 3]| gamma(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]);
+        Color32::from_rgba_unmultiplied(channel(matrix[0]), channel(matrix[1]), channel(matrix[2]), color.a())
+    }
+}
+
+impl LogColors {
+    /// Contrast ratio of every entry against `background`
+    pub fn contrast_report(&self, background: Color32) -> Vec<(&'static str, f32)> {
+        self.entries()
+            .into_iter()
+            .map(|(name, color)| (name, contrast_ratio(color, background)))
+            .collect()
+    }
+
+    /// Fix every entry that does not reach `target` contrast against
+    /// `background`. Returns the number of entries changed.
+    pub fn fix_contrast(&mut self, background: Color32, target: f32) -> usize {
+        let mut fixed = 0;
+        for (_, color) in self.entries_mut() {
+            let new_color = fix_contrast(*color, background, target);
+            if new_color != *color {
+                *color = new_color;
+                fixed += 1;
+            }
+        }
+        fixed
+    }
+}
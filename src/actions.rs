@@ -0,0 +1,162 @@
+//! Named application actions
+//!
+//! An action is something the user can trigger by name - clearing the
+//! logger, exporting the log, starting a simulation - independent of
+//! the button that usually triggers it. Actions are registered with the
+//! application shell, which offers them in the command palette and, if
+//! they name a `Menu`, in that menu of the menu bar:
+//!
+//! ```
+//! use egui_mobius_template::actions::{Action, ActionRegistry, Menu};
+//!
+//! let actions = ActionRegistry::new().with(
+//!     Action::new("logger.clear", "Clear logger", |ctx| ctx.terminal.logs.set(Default::default()))
+//!         .category("Logger")
+//!         .menu(Menu::Edit),
+//! );
+//! assert_eq!(actions.info("logger.clear").unwrap().title(), "Logger: Clear logger");
+//! ```
+//!
+//! Labels and categories may be message ids, e.g. `"action-undo"`,
+//! which are translated into the current language when shown.
+use crate::i18n;
+use crate::panel::AppContext;
+use eframe::egui;
+
+type Handler = Box<dyn FnMut(&mut AppContext)>;
+
+/// Menu
+///
+/// Menu of the application's menu bar an action is listed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Menu {
+    File,
+    Edit,
+    View,
+    Help,
+}
+
+/// ActionInfo
+///
+/// What the registry knows about an action besides its handler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionInfo {
+    /// Stable id, e.g. `"logger.clear"`
+    pub id       : &'static str,
+    pub label    : String,
+    /// Group shown in front of the label, e.g. `"Logger"`
+    pub category : &'static str,
+    pub shortcut : Option<egui::KeyboardShortcut>,
+    /// Menu listing the action, after the shell's own items
+    pub menu     : Option<Menu>,
+}
+
+impl ActionInfo {
+    /// Label in the current language, as listed in menus
+    pub fn display_label(&self) -> String {
+        i18n::tr(&self.label)
+    }
+
+    /// Category and label, as listed in the command palette
+    pub fn title(&self) -> String {
+        if self.category.is_empty() {
+            self.display_label()
+        } else {
+            format!("{}: {}", i18n::tr(self.category), self.display_label())
+        }
+    }
+}
+
+/// Action
+///
+/// A named handler run with the services of the app context.
+pub struct Action {
+    info    : ActionInfo,
+    handler : Handler,
+}
+
+impl Action {
+    pub fn new<F>(id: &'static str, label: impl Into<String>, handler: F) -> Self
+    where
+        F: FnMut(&mut AppContext) + 'static,
+    {
+        Self {
+            info: ActionInfo { id, label: label.into(), category: "", shortcut: None, menu: None },
+            handler: Box::new(handler),
+        }
+    }
+
+    pub fn category(mut self, category: &'static str) -> Self {
+        self.info.category = category;
+        self
+    }
+
+    /// Default shortcut running the action, the user may rebind it
+    pub fn shortcut(mut self, shortcut: egui::KeyboardShortcut) -> Self {
+        self.info.shortcut = Some(shortcut);
+        self
+    }
+
+    /// List the action in `menu` of the menu bar
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.info.menu = Some(menu);
+        self
+    }
+
+    pub fn info(&self) -> &ActionInfo {
+        &self.info
+    }
+}
+
+/// ActionRegistry
+///
+/// Every action of an application, in registration order.
+#[derive(Default)]
+pub struct ActionRegistry {
+    actions: Vec<Action>,
+}
+
+impl ActionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, action: Action) -> Self {
+        self.register(action);
+        self
+    }
+
+    /// Register `action`. An action with the id of an earlier one
+    /// replaces it.
+    pub fn register(&mut self, action: Action) {
+        match self.actions.iter_mut().find(|known| known.info.id == action.info.id) {
+            Some(known) => *known = action,
+            None => self.actions.push(action),
+        }
+    }
+
+    /// All registered actions
+    pub fn actions(&self) -> impl Iterator<Item = &ActionInfo> {
+        self.actions.iter().map(|action| &action.info)
+    }
+
+    /// Actions listed in `menu`, in registration order
+    pub fn in_menu(&self, menu: Menu) -> impl Iterator<Item = &ActionInfo> {
+        self.actions().filter(move |info| info.menu == Some(menu))
+    }
+
+    pub fn info(&self, id: &str) -> Option<&ActionInfo> {
+        self.actions().find(|info| info.id == id)
+    }
+
+    /// Run the action `id`. Returns false if it is not registered.
+    pub fn run(&mut self, id: &str, ctx: &mut AppContext) -> bool {
+        match self.actions.iter_mut().find(|action| action.info.id == id) {
+            Some(action) => {
+                (action.handler)(ctx);
+                true
+            }
+            None => false,
+        }
+    }
+}
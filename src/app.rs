@@ -0,0 +1,1316 @@
+//! Application shell shared by the templates
+//!
+//! `MobiusApp` owns everything a docked template application needs: the
+//! dock and its persisted layout, the terminal widget and its log
+//! colors, undo/redo history, theme files, the settings store with hot
+//! reload and saved sessions, the File/Edit/View/Help menu bar, the
+//! command palette, configurable keyboard shortcuts, toast notifications,
+//! the status bar, the language of the UI, recording and replaying of
+//! UI sessions, crash reports, optionally a single instance receiving
+//! the arguments of later launches and a tokio runtime. An application
+//! registers its panels and actions, describes its default layout and
+//! wires its own state in `setup`:
+//!
+//! ```no_run
+//! use egui_dock::DockState;
+//! use egui_mobius_template::app::MobiusApp;
+//! use egui_mobius_template::history::HistoryPanel;
+//!
+//! MobiusApp::builder()
+//!     .title("My App")
+//!     .panel::<HistoryPanel>()
+//!     .default_layout(DockState::new(vec!["history"]))
+//!     .setup(|_cc, app| {
+//!         app.resources.insert(42_u32);
+//!     })
+//!     .run()
+//!     .unwrap();
+//! ```
+use crate::actions::{Action, ActionRegistry, Menu};
+use crate::diagnostics::{self, CrashHandler, CrashReport, DiagnosticsBundle, DiagnosticsSettings};
+use crate::history::{History, MergeRule};
+use crate::i18n::{self, LanguageSettings};
+use crate::instance::{self, Instance, InstanceServer};
+use crate::layout::DockLayout;
+use crate::notifications::{Notification, Notifier, Severity};
+use crate::palette::{self, CommandPalette, PaletteEntry, RecentCommands};
+use crate::panel::{AppContext, Panel, PanelRegistry, PanelTab, Services};
+use crate::persistence;
+use crate::recorder::{self, Recorder, RecorderState, Recording, ReplaySpeed};
+use crate::session;
+use crate::settings::{self, SettingsStore, WindowSettings};
+use crate::shortcuts::ShortcutManager;
+use crate::status::{StatusBar, StatusItem};
+use crate::tabs::{self, TabAction, Tabs};
+use crate::themes::{self, ThemeManager};
+use crate::watcher::FileWatcher;
+use crate::{tr, LogColors, LogType, TerminalWidget, ERROR_COLOR};
+use eframe::egui;
+use egui_dock::{DockArea, DockState, NodeIndex, SurfaceIndex};
+use egui_mobius_reactive::Dynamic;
+use std::path::{Path, PathBuf};
+
+type SetupHook = Box<dyn FnOnce(&eframe::CreationContext<'_>, &mut AppContext)>;
+type UpdateHook = Box<dyn FnMut(&egui::Context, &mut AppContext)>;
+type OpenHook = Box<dyn FnMut(&Path, &mut AppContext)>;
+
+/// MobiusAppBuilder
+///
+/// Collects the panels, layout and hooks of an application. Created by
+/// `MobiusApp::builder`, consumed by `run`.
+pub struct MobiusAppBuilder {
+    title           : String,
+    panels          : PanelRegistry,
+    actions         : ActionRegistry,
+    default_layout  : Option<DockState<&'static str>>,
+    config_dir_name : Option<String>,
+    settings_path   : Option<PathBuf>,
+    min_size        : [f32; 2],
+    messages        : Vec<(String, String)>,
+    runtime         : Option<tokio::runtime::Runtime>,
+    single_instance : bool,
+    setup           : Option<SetupHook>,
+    on_update       : Option<UpdateHook>,
+    on_open         : Option<OpenHook>,
+}
+
+impl MobiusAppBuilder {
+    /// Window title and application name
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Register panel `P`, created with `P::default()`
+    pub fn panel<P: Panel + Default + 'static>(mut self) -> Self {
+        self.panels.register(P::default);
+        self
+    }
+
+    /// Register a panel created by `factory`
+    pub fn panel_with<P, F>(mut self, factory: F) -> Self
+    where
+        P: Panel + 'static,
+        F: Fn() -> P + 'static,
+    {
+        self.panels.register(factory);
+        self
+    }
+
+    /// Register an action, offered in the command palette and in the
+    /// menu it names
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.register(action);
+        self
+    }
+
+    pub fn actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        for action in actions {
+            self.actions.register(action);
+        }
+        self
+    }
+
+    /// Layout used on the first launch, when the saved layout cannot be
+    /// restored and by "Reset layout", with tabs given by panel id.
+    /// Without one, all panels share a single leaf.
+    pub fn default_layout(mut self, layout: DockState<&'static str>) -> Self {
+        self.default_layout = Some(layout);
+        self
+    }
+
+    /// Name of the per-user configuration directory holding the
+    /// settings, themes, sessions, exported logs and the single instance
+    /// lock. Give every application a name of its own: it defaults to
+    /// `persistence::CONFIG_DIR_NAME`, and applications sharing a
+    /// directory share all of these, including the window size and dock
+    /// layout.
+    pub fn config_dir_name(mut self, name: impl Into<String>) -> Self {
+        self.config_dir_name = Some(name.into());
+        self
+    }
+
+    /// Settings file, `settings::default_path()` by default
+    pub fn settings_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.settings_path = Some(path.into());
+        self
+    }
+
+    /// Minimum inner size of the window
+    pub fn min_size(mut self, min_size: [f32; 2]) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Add the Fluent messages `source` of the application to `language`,
+    /// see `i18n::add_messages`. Errors are logged when the application
+    /// starts.
+    pub fn messages(mut self, language: impl Into<String>, source: impl Into<String>) -> Self {
+        self.messages.push((language.into(), source.into()));
+        self
+    }
+
+    /// Run the application inside `runtime`, so `setup`, panels and
+    /// hooks can `tokio::spawn` background tasks
+    pub fn with_runtime(mut self, runtime: tokio::runtime::Runtime) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Run a single instance per settings file: a later launch forwards
+    /// its command-line arguments to the running instance and exits,
+    /// see the `instance` module
+    pub fn single_instance(mut self) -> Self {
+        self.single_instance = true;
+        self
+    }
+
+    /// Called once the window exists, with the shell's services. Insert
+    /// the state shared by the panels into `app.resources` here.
+    pub fn setup<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&eframe::CreationContext<'_>, &mut AppContext) + 'static,
+    {
+        self.setup = Some(Box::new(f));
+        self
+    }
+
+    /// Called every frame before the dock is shown, e.g. to hand
+    /// application state to the settings store
+    pub fn on_update<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&egui::Context, &mut AppContext) + 'static,
+    {
+        self.on_update = Some(Box::new(f));
+        self
+    }
+
+    /// Called for each file given on the command line, also when it was
+    /// forwarded by a later launch. Without it files are opened as saved
+    /// sessions.
+    pub fn on_open<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&Path, &mut AppContext) + 'static,
+    {
+        self.on_open = Some(Box::new(f));
+        self
+    }
+
+    /// Registered panels, in registration order
+    pub fn registered_panels(&self) -> &PanelRegistry {
+        &self.panels
+    }
+
+    /// The shell's actions followed by the registered ones
+    pub fn registered_actions(&self) -> &ActionRegistry {
+        &self.actions
+    }
+
+    /// The default layout, or all panels in registration order in a
+    /// single leaf if none was given
+    pub fn layout(&self) -> DockState<&'static str> {
+        self.default_layout.clone().unwrap_or_else(|| {
+            DockState::new(self.panels.panels().map(|info| info.id).collect())
+        })
+    }
+
+    /// Open the window and run the application until it is closed
+    pub fn run(self) -> eframe::Result {
+        let default_layout = self.layout();
+        let MobiusAppBuilder {
+            title,
+            panels,
+            actions,
+            default_layout: _,
+            config_dir_name,
+            settings_path,
+            min_size,
+            messages,
+            runtime,
+            single_instance,
+            setup,
+            on_update,
+            on_open,
+        } = self;
+
+        for (language, source) in messages {
+            if let Err(e) = i18n::add_messages(&language, &source) {
+                log::error!("{}", e);
+            }
+        }
+
+        if let Some(name) = config_dir_name {
+            if !persistence::set_config_dir_name(name) {
+                log::warn!("The configuration directory was already named, keeping {}", persistence::config_dir().display());
+            }
+        }
+        let settings_path = settings_path.unwrap_or_else(settings::default_path);
+
+        // Hand the arguments to a running instance instead of starting
+        // another one on the same settings
+        let args = instance::args();
+        let instance = if single_instance {
+            match instance::acquire(&settings_path) {
+                Ok(Instance::Primary(server)) => Some(server),
+                Ok(Instance::Secondary(client)) => {
+                    return match client.forward(&args) {
+                        Ok(()) => {
+                            log::info!("Forwarded {:?} to the running instance", args);
+                            Ok(())
+                        }
+                        Err(e) => Err(eframe::Error::AppCreation(Box::new(e))),
+                    };
+                }
+                Err(e) => {
+                    log::error!("Running without the single instance lock: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Report panics from here on, with the log and settings once they exist
+        let crash_handler = CrashHandler::install(diagnostics::crashes_dir(&settings_path));
+
+        // Load saved settings before the window is created so its size can be restored
+        let settings = SettingsStore::new(settings_path);
+        let load_result = settings.load();
+        crash_handler.attach_settings(&settings);
+        let window: WindowSettings = settings.get();
+
+        // The saved language, or else the system's if there are messages for it
+        let language = settings.get::<LanguageSettings>().language.or_else(i18n::system_language);
+        if let Some(language) = language {
+            i18n::set_language(&language);
+        }
+        let mut panels = panels;
+        panels.refresh_titles();
+
+        let _guard = runtime.as_ref().map(|runtime| runtime.enter());
+
+        let native_options = eframe::NativeOptions {
+            viewport: egui::ViewportBuilder::default()
+                .with_title(&title)
+                .with_titlebar_buttons_shown(true)
+                .with_inner_size(window.inner_size)
+                .with_min_inner_size(min_size)
+                .with_resizable(true),
+            ..Default::default()
+        };
+
+        let app_name = title.clone();
+        eframe::run_native(
+            &app_name,
+            native_options,
+            Box::new(move |cc| {
+                egui_extras::install_image_loaders(&cc.egui_ctx);
+
+                // Create the terminal and notifier first, so settings
+                // failures can be reported in them
+                let mut terminal = TerminalWidget::new(cc.egui_ctx.clone(), settings.get::<LogColors>());
+                let notifier = Notifier::new().with_repaint(&cc.egui_ctx);
+                if let Err(e) = load_result {
+                    terminal.add_log(format!("Failed to load settings: {}", e), LogType::Error);
+                    if settings.is_read_only() {
+                        terminal.add_log(format!("Settings will not be saved until {} is fixed", settings.path().display()), LogType::Error);
+                    } else {
+                        terminal.add_log(format!("Kept a copy of it as {}", settings::backup_path(settings.path()).display()), LogType::Error);
+                    }
+                    notifier.notify(Notification::error(tr!("notify-load-settings-failed")).body(e.to_string()));
+                }
+                let sink = terminal.clone();
+                let sink_notifier = notifier.clone();
+                settings.on_error(move |e| {
+                    sink.clone().add_log(format!("Settings: {}", e), LogType::Error);
+                    sink_notifier.notify(Notification::error(tr!("notify-settings-error")).body(e.to_string()));
+                });
+
+                crash_handler.attach_logs(&terminal.logs);
+                crash_handler.attach_notifier(&notifier);
+
+                // Show the report of a crash of an earlier run once
+                let crash_report = settings.get::<DiagnosticsSettings>()
+                    .unseen_crash(crash_handler.dir())
+                    .and_then(|path| match CrashReport::load(&path) {
+                        Ok(report) => Some((path, report)),
+                        Err(e) => {
+                            terminal.add_log(format!("Failed to read crash report: {}", e), LogType::Error);
+                            None
+                        }
+                    });
+
+                let history = History::new().with_repaint(&cc.egui_ctx);
+                history.track(&terminal.colors, "history-log-colors", MergeRule::continuous());
+                let recorder = Recorder::new().with_repaint(&cc.egui_ctx);
+                recorder.track(&terminal.colors, "shell.log_colors");
+
+                // Scan the theme directory, reporting files that could not be loaded
+                let (themes, theme_errors) = ThemeManager::new(themes::themes_dir());
+                for e in theme_errors {
+                    terminal.add_log(format!("Themes: {}", e), LogType::Error);
+                }
+
+                // Restore the saved dock layout or fall back to the default one
+                let mut tabs = Tabs::new(default_layout, &panels);
+                if let Some(dock) = settings.get::<DockLayout>().restore(|id| panels.create(id)) {
+                    tabs.dock = dock;
+                }
+
+                // Hot-reload the settings file when it is edited while the app runs
+                let reload_terminal = terminal.clone();
+                settings.on_reload(move |colors: LogColors| {
+                    let mut terminal = reload_terminal.clone();
+                    terminal.update_colors(colors);
+                    terminal.add_log("Reloaded log colors from settings file".to_string(), LogType::Default);
+                });
+                let repaint = cc.egui_ctx.clone();
+                settings.on_reload(move |window: WindowSettings| window.apply(&repaint));
+                settings.on_reload(|settings: LanguageSettings| {
+                    if let Some(language) = settings.language {
+                        i18n::set_language(&language);
+                    }
+                });
+
+                let palette = CommandPalette::new(settings.get::<RecentCommands>());
+
+                // Bind every command to its saved or default shortcut,
+                // reporting shortcuts claimed by more than one command
+                let mut shortcuts = ShortcutManager::new();
+                register_commands(&mut shortcuts, &title, &panels, &actions);
+                settings.bind(shortcuts.settings());
+                for (shortcut, keys) in shortcuts.conflicts() {
+                    let titles: Vec<_> = keys.iter()
+                        .filter_map(|key| shortcuts.commands().iter().find(|command| command.key == *key))
+                        .map(|command| command.title.as_str())
+                        .collect();
+                    terminal.add_log(
+                        format!("Shortcut {} is bound to {}", cc.egui_ctx.format_shortcut(&shortcut), titles.join(" and ")),
+                        LogType::Error,
+                    );
+                }
+
+                let mut app = MobiusApp {
+                    title,
+                    language: i18n::language(),
+                    sessions_dir: session::sessions_dir(settings.path()),
+                    recordings_dir: recorder::recordings_dir(settings.path()),
+                    session_name: None,
+                    about_open: false,
+                    crash_report,
+                    tabs,
+                    panels,
+                    actions,
+                    palette,
+                    terminal: Dynamic::new(terminal),
+                    services: Services {
+                        history,
+                        notifier,
+                        recorder,
+                        status: StatusBar::new().with_repaint(&cc.egui_ctx),
+                        shortcuts,
+                        ..Services::new(settings, themes)
+                    },
+                    stored: None,
+                    instance: instance.map(|server| server.with_repaint(&cc.egui_ctx)),
+                    startup_commands: instance::commands(&args),
+                    on_update,
+                    on_open,
+                    _watcher: FileWatcher::new(),
+                };
+                if let Some(setup) = setup {
+                    app.with_context(|ctx| setup(cc, ctx));
+                }
+                // Watch only after setup registered its reload callbacks
+                app.services.settings.watch(&app._watcher, &cc.egui_ctx);
+
+                Ok(Box::new(app))
+            }),
+        )
+    }
+}
+
+/// ShellSettings
+///
+/// The settings sections kept by the shell itself.
+struct ShellSettings {
+    colors : LogColors,
+    recent : RecentCommands,
+    window : Option<WindowSettings>,
+}
+
+/// Tab viewer for DockArea, dispatching to the panel of each tab with
+/// the shared services in an `AppContext`
+struct TabViewer<'a> {
+    terminal    : &'a mut Dynamic<TerminalWidget>,
+    services    : &'a mut Services,
+    panels      : &'a PanelRegistry,
+    open_panels : Vec<&'static str>,
+    lonely_tabs : Vec<usize>,
+    actions     : Vec<TabAction>,
+}
+
+impl egui_dock::TabViewer for TabViewer<'_> {
+    type Tab = PanelTab;
+
+    fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
+        tab.title().into()
+    }
+
+    fn id(&mut self, tab: &mut Self::Tab) -> egui::Id {
+        egui::Id::new(("tab", tab.instance()))
+    }
+
+    fn context_menu(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab,
+                    _surface: SurfaceIndex, _node: NodeIndex) {
+        let lonely = self.lonely_tabs.contains(&tab.instance());
+        tabs::context_menu(ui, tab, lonely, &mut self.actions);
+    }
+
+    fn add_popup(&mut self, ui: &mut egui::Ui, surface: SurfaceIndex, node: NodeIndex) {
+        tabs::add_popup(ui, self.panels, &self.open_panels, (surface, node), &mut self.actions);
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
+        let mut terminal = self.terminal.get();
+        tab.ui(ui, &mut AppContext::new(&mut terminal, self.services));
+        self.terminal.set(terminal);
+    }
+}
+
+/// MobiusApp
+///
+/// The running application shell, see the module documentation.
+pub struct MobiusApp {
+    title            : String,
+    /// Language the titles of panels and commands were last taken in
+    language         : String,
+    sessions_dir     : PathBuf,
+    recordings_dir   : PathBuf,
+    /// Name typed in the "Save session" dialog, `None` when it is closed
+    session_name     : Option<String>,
+    about_open       : bool,
+    /// Crash report of an earlier run shown until it is closed
+    crash_report     : Option<(PathBuf, CrashReport)>,
+    tabs             : Tabs,
+    panels           : PanelRegistry,
+    actions          : ActionRegistry,
+    palette          : CommandPalette,
+    terminal         : Dynamic<TerminalWidget>,
+    services         : Services,
+    /// What `store_settings` handed to the settings store last
+    stored           : Option<ShellSettings>,
+    /// Lock of a single instance, receiving the arguments of later launches
+    instance         : Option<InstanceServer>,
+    /// Commands given on the command line, run in the first frame
+    startup_commands : Vec<String>,
+    on_update        : Option<UpdateHook>,
+    on_open          : Option<OpenHook>,
+    _watcher         : FileWatcher,
+}
+
+impl MobiusApp {
+    pub fn builder() -> MobiusAppBuilder {
+        MobiusAppBuilder {
+            title: "egui_mobius".to_string(),
+            panels: PanelRegistry::new(),
+            // An application action with the same id replaces them
+            actions: ActionRegistry::new()
+                .with(undo_action())
+                .with(redo_action())
+                .with(copy_log_action())
+                .with(export_log_action())
+                .with(export_diagnostics_action()),
+            default_layout: None,
+            config_dir_name: None,
+            settings_path: None,
+            min_size: [600.0, 400.0],
+            messages: Vec::new(),
+            runtime: None,
+            single_instance: false,
+            setup: None,
+            on_update: None,
+            on_open: None,
+        }
+    }
+
+    /// Run `f` with the shell's services, writing the terminal back
+    fn with_context<R>(&mut self, f: impl FnOnce(&mut AppContext) -> R) -> R {
+        let mut terminal = self.terminal.get();
+        let result = f(&mut AppContext::new(&mut terminal, &mut self.services));
+        self.terminal.set(terminal);
+        result
+    }
+
+    /// Every command of the palette: opening each panel, the commands
+    /// of the shell's menus, the registered actions, opening each saved
+    /// session, applying each theme and switching to each language
+    fn palette_entries(&self, ctx: &egui::Context) -> Vec<PaletteEntry> {
+        let mut entries: Vec<_> = self.panels.panels()
+            .map(|info| PaletteEntry::new(format!("panel:{}", info.id), tr!("command-open-panel", panel = info.label())))
+            .collect();
+        entries.push(PaletteEntry::new("layout:reset", tr!("command-reset-layout")));
+        entries.push(PaletteEntry::new("session:save", tr!("command-save-session")));
+        entries.push(PaletteEntry::new("record:toggle", self.record_title()));
+        entries.push(PaletteEntry::new("app:quit", tr!("command-quit")));
+        entries.push(PaletteEntry::new("app:about", tr!("command-about", app = self.title.as_str())));
+        entries.extend(self.actions.actions()
+            .map(|info| PaletteEntry::new(format!("action:{}", info.id), info.title())));
+        for entry in &mut entries {
+            entry.shortcut = self.services.shortcuts.binding(&entry.key).map(|shortcut| ctx.format_shortcut(&shortcut));
+        }
+        for (name, path) in session::list(&self.sessions_dir) {
+            entries.push(PaletteEntry::new(
+                format!("session:open:{}", path.display()),
+                tr!("command-open-session", name = name),
+            ));
+        }
+        for (name, path) in session::list(&self.recordings_dir) {
+            entries.push(PaletteEntry::new(
+                format!("record:replay:{}", path.display()),
+                tr!("command-replay-recording", name = name),
+            ));
+        }
+        for (path, theme) in self.services.themes.themes() {
+            entries.push(PaletteEntry::new(
+                format!("theme:{}", path.display()),
+                tr!("command-theme", name = theme.name.as_str()),
+            ));
+        }
+        for (id, name) in i18n::languages() {
+            entries.push(PaletteEntry::new(format!("language:{}", id), tr!("command-language", name = name)));
+        }
+        entries
+    }
+
+    /// Run the command `key` chosen in the palette, a menu or by its
+    /// shortcut, see `palette_entries`. Changes of the dock are queued
+    /// in `actions`.
+    fn run_command(&mut self, ctx: &egui::Context, key: &str, actions: &mut Vec<TabAction>) {
+        let Some((kind, id)) = key.split_once(':') else {
+            return;
+        };
+        // Controlling the recorder and quitting are not part of a session
+        if kind != "record" && kind != "app" {
+            self.services.recorder.command(key);
+        }
+        match kind {
+            "panel" => {
+                if let Some(info) = self.panels.info(id) {
+                    actions.push(TabAction::Show(info.id));
+                }
+            }
+            "layout" => actions.push(TabAction::ResetLayout),
+            "palette" => self.palette.toggle(),
+            "session" => match id.strip_prefix("open:") {
+                Some(path) => self.open_session(Path::new(path)),
+                None => self.session_name = Some(String::new()),
+            },
+            "settings" => {
+                if let Err(e) = self.services.settings.flush() {
+                    self.services.notifier.notify(Notification::error(tr!("notify-save-settings-failed")).body(e.to_string()));
+                }
+            }
+            "language" if i18n::set_language(id) => ctx.request_repaint(),
+            "open" => self.open_file(Path::new(id)),
+            "record" => match id {
+                "toggle" if self.services.recorder.is_recording() => self.stop_recording(),
+                "toggle" => self.services.recorder.start(&self.title),
+                "stop" => self.stop_recording(),
+                _ => {
+                    if let Some(path) = id.strip_prefix("replay:") {
+                        self.replay(Path::new(path));
+                    }
+                }
+            },
+            "app" => match id {
+                "quit" => self.quit(ctx),
+                "force_quit" => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                "about" => self.about_open = true,
+                _ => {}
+            },
+            "action" => {
+                let mut registry = std::mem::take(&mut self.actions);
+                self.with_context(|ctx| registry.run(id, ctx));
+                self.actions = registry;
+            }
+            "theme" => {
+                let theme = self.services.themes.themes().iter()
+                    .find(|(path, _)| path.display().to_string() == id)
+                    .map(|(_, theme)| theme.clone());
+                if let Some(theme) = theme {
+                    let mut terminal = self.terminal.get();
+                    terminal.update_colors(theme.log_colors);
+                    terminal.add_log(format!("Applied theme \"{}\"", theme.name), LogType::Default);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Save the current settings, layout included, as session `name`
+    fn save_session(&mut self, ctx: &egui::Context, name: &str) {
+        self.capture_layout();
+        self.store_settings(ctx);
+        let path = session::session_path(&self.sessions_dir, name);
+        let mut terminal = self.terminal.get();
+        match self.services.settings.export(&path) {
+            Ok(()) => {
+                terminal.add_log(format!("Saved session \"{}\"", name.trim()), LogType::Default);
+                self.services.notifier.success(tr!("notify-session-saved", name = name.trim()));
+            }
+            Err(e) => {
+                terminal.add_log(format!("Failed to save session: {}", e), LogType::Error);
+                self.services.notifier.notify(Notification::error(tr!("notify-save-session-failed")).body(e.to_string()));
+            }
+        }
+    }
+
+    /// Open a file given on the command line with the application's
+    /// `on_open` hook, or else as a saved session
+    fn open_file(&mut self, path: &Path) {
+        match self.on_open.take() {
+            Some(mut on_open) => {
+                self.with_context(|app| on_open(path, app));
+                self.on_open = Some(on_open);
+            }
+            None => self.open_session(path),
+        }
+    }
+
+    /// Replace the settings and the layout with those of a saved session
+    fn open_session(&mut self, path: &Path) {
+        let mut terminal = self.terminal.get();
+        if let Err(e) = self.services.settings.import(path) {
+            terminal.add_log(format!("Failed to open session: {}", e), LogType::Error);
+            self.services.notifier.notify(Notification::error(tr!("notify-open-session-failed")).body(e.to_string()));
+            return;
+        }
+        let panels = &self.panels;
+        if let Some(dock) = self.services.settings.get::<DockLayout>().restore(|id| panels.create(id)) {
+            self.tabs.dock = dock;
+        }
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        terminal.add_log(format!("Opened session \"{}\"", name), LogType::Default);
+    }
+
+    /// Stop recording or replaying, saving a recording in the
+    /// recordings directory
+    fn stop_recording(&mut self) {
+        let Some(recording) = self.services.recorder.stop() else {
+            return;
+        };
+        let path = self.recordings_dir.join(Recording::file_name(&recording.started));
+        let mut terminal = self.terminal.get();
+        match recording.save(&path) {
+            Ok(()) => {
+                terminal.add_log(format!("Saved recording to {}", path.display()), LogType::Default);
+                self.services.notifier.notify(Notification::success(tr!("notify-recording-saved", count = recording.events.len()))
+                    .body(path.display().to_string()));
+            }
+            Err(e) => {
+                terminal.add_log(format!("Failed to save recording: {}", e), LogType::Error);
+                self.services.notifier.notify(Notification::error(tr!("notify-save-recording-failed")).body(e.to_string()));
+            }
+        }
+    }
+
+    /// Replay a saved recording at its recorded pace
+    fn replay(&mut self, path: &Path) {
+        let mut terminal = self.terminal.get();
+        let result = Recording::load(path)
+            .and_then(|recording| self.services.recorder.replay(recording, ReplaySpeed::Recorded(1.0)));
+        match result {
+            Ok(()) => terminal.add_log(format!("Replaying {}", path.display()), LogType::Default),
+            Err(e) => {
+                terminal.add_log(format!("Failed to replay recording: {}", e), LogType::Error);
+                self.services.notifier.notify(Notification::error(tr!("notify-replay-failed")).body(e.to_string()));
+            }
+        }
+    }
+
+    fn record_title(&self) -> String {
+        if self.services.recorder.is_recording() {
+            tr!("command-stop-recording")
+        } else {
+            tr!("command-start-recording")
+        }
+    }
+
+    /// Close the window once every setting is saved. If saving fails the
+    /// application keeps running, so the failure can be seen.
+    fn quit(&mut self, ctx: &egui::Context) {
+        self.capture_layout();
+        self.store_settings(ctx);
+        match self.services.settings.flush() {
+            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            Err(e) => {
+                self.terminal.get().add_log(format!("Failed to save settings: {}", e), LogType::Error);
+                self.services.notifier.notify(Notification::error(tr!("notify-quit-failed"))
+                    .body(e.to_string())
+                    .action(tr!("notify-quit-anyway"), "app:force_quit"));
+            }
+        }
+    }
+
+    /// Take the titles of panels and commands in the new language once
+    /// it was switched, by a command, a panel or the settings file, and
+    /// remember the user's choice
+    fn follow_language(&mut self) {
+        let language = i18n::language();
+        if language == self.language {
+            return;
+        }
+        self.panels.refresh_titles();
+        register_commands(&mut self.services.shortcuts, &self.title, &self.panels, &self.actions);
+        self.services.settings.set(&LanguageSettings { language: Some(language.clone()) });
+        self.language = language;
+    }
+
+    fn capture_layout(&self) {
+        self.services.settings.set(&DockLayout::capture(&self.tabs.dock, |tab| tab.id().to_string()));
+    }
+
+    /// Hand the parts of the shell's state that changed since the last
+    /// call to the settings store. Unchanged parts are left alone, so a
+    /// reload of the settings file is not overwritten by the values it
+    /// replaces before they are updated.
+    fn store_settings(&mut self, ctx: &egui::Context) {
+        let current = ShellSettings {
+            colors: self.terminal.get().colors.get(),
+            recent: self.palette.recent().clone(),
+            window: WindowSettings::from_context(ctx),
+        };
+        let stored = self.stored.as_ref();
+        if stored.is_none_or(|stored| stored.colors != current.colors) {
+            self.services.settings.set(&current.colors);
+        }
+        if stored.is_none_or(|stored| stored.recent != current.recent) {
+            self.services.settings.set(&current.recent);
+        }
+        if let Some(window) = current.window.filter(|window| stored.is_none_or(|stored| stored.window != Some(*window))) {
+            self.services.settings.set(&window);
+        }
+        self.stored = Some(current);
+    }
+}
+
+/// The layout and all pending settings are saved when the application
+/// is closed. The save is flushed synchronously so it completes before
+/// the process exits.
+impl Drop for MobiusApp {
+    fn drop(&mut self) {
+        self.capture_layout();
+        if let Err(e) = self.services.settings.flush() {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+}
+
+impl eframe::App for MobiusApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Apply an edit of the settings file before anything is stored
+        self.services.settings.apply_reload();
+        self.follow_language();
+
+        // Record the changes of the last frame, or replay the due inputs
+        let mut actions = Vec::new();
+        for key in self.services.recorder.poll() {
+            self.run_command(ctx, &key, &mut actions);
+        }
+
+        // Run the commands of the command line, and of later launches
+        // once they forwarded theirs
+        let mut commands = std::mem::take(&mut self.startup_commands);
+        for args in self.instance.as_ref().map(InstanceServer::take).unwrap_or_default() {
+            self.terminal.get().add_log(format!("Received from another launch: {}", args.join(" ")), LogType::Default);
+            commands.extend(instance::commands(&args));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+        for key in commands {
+            self.run_command(ctx, &key, &mut actions);
+        }
+
+        // Dispatch shortcuts before any widget sees the keys
+        for key in self.services.shortcuts.dispatch(ctx) {
+            self.run_command(ctx, &key, &mut actions);
+        }
+
+        // Update terminal widget's repaint context
+        let mut terminal = self.terminal.get();
+        terminal.repaint = ctx.clone();
+        self.terminal.set(terminal);
+
+        if let Some(mut on_update) = self.on_update.take() {
+            self.with_context(|app| on_update(ctx, app));
+            self.on_update = Some(on_update);
+        }
+
+        let (open_panels, lonely_tabs) = (self.tabs.open_panels(), self.tabs.lonely_tabs());
+        let mut chosen = Vec::new();
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            chosen = self.menu_bar(ui, &open_panels);
+        });
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            chosen.extend(self.status_bar(ui));
+        });
+        for key in chosen {
+            self.run_command(ctx, &key, &mut actions);
+        }
+
+        if self.palette.is_open() {
+            let entries = self.palette_entries(ctx);
+            if let Some(key) = self.palette.show(ctx, &entries) {
+                self.run_command(ctx, &key, &mut actions);
+            }
+        }
+        if let Some(name) = self.session_dialog(ctx) {
+            self.save_session(ctx, &name);
+        }
+        self.about_window(ctx);
+        if let Some(key) = self.crash_window(ctx) {
+            self.run_command(ctx, &key, &mut actions);
+        }
+        self.services.notifier.show(ctx);
+        for key in self.services.notifier.take_commands() {
+            self.run_command(ctx, &key, &mut actions);
+        }
+
+        let mut viewer = TabViewer {
+            terminal: &mut self.terminal,
+            services: &mut self.services,
+            panels: &self.panels,
+            open_panels,
+            lonely_tabs,
+            actions,
+        };
+        DockArea::new(&mut self.tabs.dock)
+            .show_add_buttons(true)
+            .show_add_popup(true)
+            .show(ctx, &mut viewer);
+        for action in viewer.actions {
+            self.tabs.apply(&self.panels, action);
+        }
+
+        self.store_settings(ctx);
+    }
+}
+
+/// MenuItems
+///
+/// Items of the menu bar, each running a command of the shell and
+/// showing its current shortcut. The chosen commands are collected and
+/// run once the menu bar is shown.
+struct MenuItems<'a> {
+    ctx       : egui::Context,
+    shortcuts : &'a ShortcutManager,
+    chosen    : Vec<String>,
+}
+
+impl MenuItems<'_> {
+    fn item(&mut self, ui: &mut egui::Ui, label: impl Into<String>, key: impl Into<String>) {
+        self.add(ui, egui::Button::new(label.into()), key.into());
+    }
+
+    fn add(&mut self, ui: &mut egui::Ui, button: egui::Button<'_>, key: String) {
+        let shortcut = self.shortcuts.binding(&key)
+            .map(|shortcut| self.ctx.format_shortcut(&shortcut))
+            .unwrap_or_default();
+        if ui.add(button.shortcut_text(shortcut)).clicked() {
+            self.chosen.push(key);
+            ui.close();
+        }
+    }
+
+    /// The actions registered for `menu`, after a separator
+    fn actions(&mut self, ui: &mut egui::Ui, actions: &ActionRegistry, menu: Menu) {
+        for (index, info) in actions.in_menu(menu).enumerate() {
+            if index == 0 {
+                ui.separator();
+            }
+            self.item(ui, info.display_label(), format!("action:{}", info.id));
+        }
+    }
+}
+
+impl MobiusApp {
+    /// Show the File, Edit, View and Help menus. Returns the keys of the
+    /// chosen commands, see `run_command`.
+    fn menu_bar(&self, ui: &mut egui::Ui, open_panels: &[&'static str]) -> Vec<String> {
+        let mut items = MenuItems { ctx: ui.ctx().clone(), shortcuts: &self.services.shortcuts, chosen: Vec::new() };
+        egui::MenuBar::new().ui(ui, |ui| {
+            ui.menu_button(tr!("menu-file"), |ui| {
+                ui.menu_button(tr!("menu-open-session"), |ui| {
+                    let sessions = session::list(&self.sessions_dir);
+                    if sessions.is_empty() {
+                        ui.weak(tr!("menu-no-sessions"));
+                    }
+                    for (name, path) in sessions {
+                        items.item(ui, name, format!("session:open:{}", path.display()));
+                    }
+                });
+                items.item(ui, tr!("menu-save-session"), "session:save");
+                ui.separator();
+                let record = if self.services.recorder.is_recording() { "menu-stop-recording" } else { "menu-start-recording" };
+                items.item(ui, tr!(record), "record:toggle");
+                ui.menu_button(tr!("menu-replay-recording"), |ui| {
+                    let recordings = session::list(&self.recordings_dir);
+                    if recordings.is_empty() {
+                        ui.weak(tr!("menu-no-recordings"));
+                    }
+                    // Newest first
+                    for (name, path) in recordings.into_iter().rev() {
+                        items.item(ui, name, format!("record:replay:{}", path.display()));
+                    }
+                });
+                items.actions(ui, &self.actions, Menu::File);
+                ui.separator();
+                items.item(ui, tr!("menu-quit"), "app:quit");
+            });
+
+            ui.menu_button(tr!("menu-edit"), |ui| {
+                for info in self.actions.in_menu(Menu::Edit) {
+                    items.item(ui, info.display_label(), format!("action:{}", info.id));
+                }
+            });
+
+            ui.menu_button(tr!("menu-view"), |ui| {
+                items.item(ui, tr!("menu-command-palette"), "palette:toggle");
+                ui.separator();
+                // Open panels are highlighted; clicking one shows it,
+                // reopening it if it was closed
+                for panel in self.panels.panels() {
+                    let button = egui::Button::selectable(open_panels.contains(&panel.id), panel.label());
+                    items.add(ui, button, format!("panel:{}", panel.id));
+                }
+                ui.separator();
+                ui.menu_button(tr!("menu-theme"), |ui| {
+                    if self.services.themes.themes().is_empty() {
+                        ui.weak(tr!("menu-no-themes"));
+                    }
+                    for (path, theme) in self.services.themes.themes() {
+                        items.item(ui, theme.name.as_str(), format!("theme:{}", path.display()));
+                    }
+                });
+                ui.menu_button(tr!("menu-language"), |ui| {
+                    for (id, name) in i18n::languages() {
+                        let button = egui::Button::selectable(id == self.language, name);
+                        items.add(ui, button, format!("language:{}", id));
+                    }
+                });
+                egui::gui_zoom::zoom_menu_buttons(ui);
+                ui.separator();
+                items.item(ui, tr!("menu-reset-layout"), "layout:reset");
+                items.actions(ui, &self.actions, Menu::View);
+            });
+
+            ui.menu_button(tr!("menu-help"), |ui| {
+                let shortcuts_panel = self.panels.info(SHORTCUTS_PANEL).is_some();
+                if shortcuts_panel {
+                    items.item(ui, tr!("menu-keyboard-shortcuts"), format!("panel:{}", SHORTCUTS_PANEL));
+                }
+                for info in self.actions.in_menu(Menu::Help) {
+                    items.item(ui, info.display_label(), format!("action:{}", info.id));
+                }
+                if shortcuts_panel || self.actions.in_menu(Menu::Help).next().is_some() {
+                    ui.separator();
+                }
+                // An application's own About panel replaces the shell's window
+                let about = if self.panels.info(ABOUT_PANEL).is_some() {
+                    format!("panel:{}", ABOUT_PANEL)
+                } else {
+                    "app:about".to_string()
+                };
+                items.item(ui, tr!("menu-about", app = self.title.as_str()), about);
+            });
+        });
+        items.chosen
+    }
+
+    /// Show the application's status items on the left and the shell's
+    /// on the right. Returns the commands of the clicked items.
+    fn status_bar(&self, ui: &mut egui::Ui) -> Vec<String> {
+        let mut chosen = Vec::new();
+        let app_items = self.services.status.items();
+        let shell_items = self.shell_status_items();
+        ui.horizontal(|ui| {
+            for (index, item) in app_items.iter().enumerate() {
+                if index > 0 {
+                    ui.separator();
+                }
+                chosen.extend(item.ui(ui));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                for (index, item) in shell_items.iter().rev().enumerate() {
+                    if index > 0 {
+                        ui.separator();
+                    }
+                    chosen.extend(item.ui(ui));
+                }
+            });
+        });
+        chosen
+    }
+
+    /// Log counts, notifications, the theme in use and whether the
+    /// settings are saved. Items open the panel with the details if the
+    /// application registered it.
+    fn shell_status_items(&self) -> Vec<StatusItem> {
+        let panel = |id: &str| self.panels.info(id).map(|info| format!("panel:{}", info.id));
+        let terminal = self.terminal.get();
+        let logs = terminal.logs.get();
+        let errors = logs.iter().filter(|(_, log_type)| *log_type == LogType::Error).count();
+
+        let mut log_counts = StatusItem::new("logs", format!("❌ {}  📜 {}", errors, logs.len()))
+            .tooltip(tr!("status-logs-tooltip", errors = errors, total = logs.len()));
+        if errors > 0 {
+            log_counts = log_counts.color(ERROR_COLOR);
+        }
+        log_counts.command = panel(LOGGER_PANEL);
+        let mut items = vec![log_counts];
+
+        match self.services.recorder.state() {
+            RecorderState::Idle => {}
+            RecorderState::Recording { events, elapsed } => {
+                let seconds = elapsed.as_secs();
+                items.push(StatusItem::new("recorder", format!("⏺ {}:{:02}", seconds / 60, seconds % 60))
+                    .color(ERROR_COLOR)
+                    .tooltip(tr!("status-recording-tooltip", count = events))
+                    .command("record:stop"));
+            }
+            RecorderState::Replaying { done, total } => {
+                items.push(StatusItem::new("recorder", format!("▶ {}/{}", done, total))
+                    .tooltip(tr!("status-replaying-tooltip"))
+                    .command("record:stop"));
+            }
+        }
+
+        if let Some(command) = panel(NOTIFICATIONS_PANEL) {
+            let count = self.services.notifier.history().len();
+            items.push(StatusItem::new("notifications", format!("🔔 {}", count))
+                .tooltip(tr!("notifications-count", count = count))
+                .command(command));
+        }
+
+        let colors = terminal.colors.get();
+        let theme = self.services.themes.matching(&colors).map_or_else(|| tr!("status-custom-colors"), |theme| theme.name.clone());
+        let mut theme_item = StatusItem::new("theme", format!("🎨 {}", theme)).tooltip(tr!("status-theme-tooltip"));
+        theme_item.command = panel(SETTINGS_PANEL);
+        items.push(theme_item);
+
+        let path = self.services.settings.path().display().to_string();
+        items.push(if self.services.settings.has_unsaved_changes() {
+            StatusItem::new("settings", tr!("status-unsaved"))
+                .color(Severity::Warning.color())
+                .tooltip(tr!("status-unsaved-tooltip", path = path))
+                .command("settings:save")
+        } else {
+            StatusItem::new("settings", tr!("status-saved")).tooltip(tr!("status-saved-tooltip", path = path))
+        });
+        items
+    }
+
+    /// Ask for the name of the session to save. Returns the name once the
+    /// user confirms it.
+    fn session_dialog(&mut self, ctx: &egui::Context) -> Option<String> {
+        let name = self.session_name.as_mut()?;
+        let (enter, escape) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        ));
+        let valid = !name.trim().is_empty();
+        let mut save = enter && valid;
+        let mut cancel = escape;
+
+        egui::Window::new(tr!("session-dialog-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 48.0])
+            .show(ctx, |ui| {
+                ui.add(egui::TextEdit::singleline(name).hint_text(tr!("session-name-hint"))).request_focus();
+                if valid && session::session_path(&self.sessions_dir, name).exists() {
+                    ui.weak(tr!("session-replaces"));
+                }
+                ui.horizontal(|ui| {
+                    save |= ui.add_enabled(valid, egui::Button::new(tr!("button-save"))).clicked();
+                    cancel |= ui.button(tr!("button-cancel")).clicked();
+                });
+            });
+
+        if save {
+            return self.session_name.take();
+        }
+        if cancel {
+            self.session_name = None;
+        }
+        None
+    }
+
+    /// The shell's About window, unless the application has its own
+    /// About panel
+    fn about_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr!("menu-about", app = self.title.as_str()))
+            .open(&mut self.about_open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(&self.title);
+                ui.label(tr!("about-built-with", version = env!("CARGO_PKG_VERSION")));
+                ui.weak(tr!("about-settings", path = self.services.settings.path().display().to_string()));
+            });
+    }
+}
+
+impl MobiusApp {
+    /// The crash report of an earlier run. Returns the command of the
+    /// clicked export button. Closing the window marks the report seen.
+    fn crash_window(&mut self, ctx: &egui::Context) -> Option<String> {
+        let (path, report) = self.crash_report.as_ref()?;
+        let mut open = true;
+        let mut close = false;
+        let mut command = None;
+        egui::Window::new(tr!("crash-title"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.heading(tr!("crash-heading", app = self.title.as_str(), time = i18n::format_datetime(&report.time)));
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new(&report.message).monospace().color(ERROR_COLOR));
+                if let Some(location) = &report.location {
+                    ui.weak(tr!("crash-location", location = location.as_str(), thread = report.thread.as_str()));
+                }
+                ui.collapsing(tr!("crash-backtrace"), |ui| {
+                    egui::ScrollArea::vertical().id_salt("backtrace").max_height(200.0).show(ui, |ui| {
+                        ui.monospace(&report.backtrace);
+                    });
+                });
+                ui.collapsing(tr!("crash-logs", count = report.logs.len()), |ui| {
+                    egui::ScrollArea::vertical().id_salt("crash_logs").max_height(200.0).show(ui, |ui| {
+                        for entry in &report.logs {
+                            ui.monospace(entry);
+                        }
+                    });
+                });
+                ui.weak(tr!("crash-report-file", path = path.display().to_string()));
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("action-export-diagnostics")).clicked() {
+                        command = Some("action:help.export_diagnostics".to_string());
+                    }
+                    if ui.button(tr!("button-copy-report")).clicked() {
+                        ui.ctx().copy_text(report.to_text());
+                    }
+                    close = ui.button(tr!("button-close")).clicked();
+                });
+            });
+
+        if close || !open {
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+            self.services.settings.set(&DiagnosticsSettings { last_seen_crash: name });
+            self.crash_report = None;
+        }
+        command
+    }
+}
+
+/// Panel opened by Help > Keyboard shortcuts, if registered
+const SHORTCUTS_PANEL: &str = "shortcuts";
+/// Panel opened by Help > About instead of the shell's window, if registered
+const ABOUT_PANEL: &str = "about";
+/// Panels opened by the shell's status items, if registered
+const LOGGER_PANEL: &str = "logger";
+const NOTIFICATIONS_PANEL: &str = "notifications";
+const SETTINGS_PANEL: &str = "settings";
+
+/// Register every command that can be bound to a shortcut, with its
+/// default and its title in the current language: toggling the palette,
+/// showing each panel, the commands of the shell's menus and the
+/// registered actions. The keys are those of the palette entries.
+fn register_commands(shortcuts: &mut ShortcutManager, title: &str, panels: &PanelRegistry, actions: &ActionRegistry) {
+    shortcuts.register("palette:toggle", tr!("command-palette"), Some(palette::SHORTCUT));
+    for info in panels.panels() {
+        shortcuts.register(format!("panel:{}", info.id), tr!("command-open-panel", panel = info.label()), info.shortcut);
+    }
+    shortcuts.register("layout:reset", tr!("command-reset-layout"), None);
+    shortcuts.register("session:save", tr!("command-save-session"), Some(SAVE_SESSION));
+    shortcuts.register("record:toggle", tr!("command-toggle-recording"), Some(TOGGLE_RECORDING));
+    shortcuts.register("app:quit", tr!("command-quit"), Some(QUIT));
+    shortcuts.register("app:about", tr!("command-about", app = title), None);
+    for info in actions.actions() {
+        shortcuts.register(format!("action:{}", info.id), info.title(), info.shortcut);
+    }
+}
+
+const SAVE_SESSION: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::S);
+const TOGGLE_RECORDING: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT), egui::Key::R);
+const QUIT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Q);
+
+fn export_diagnostics_action() -> Action {
+    Action::new("help.export_diagnostics", "action-export-diagnostics", |ctx| {
+        let name = DiagnosticsBundle::file_name(&chrono::Local::now());
+        let path = diagnostics::diagnostics_dir(ctx.settings.path()).join(name);
+        match DiagnosticsBundle::collect(&ctx.terminal.logs.get(), ctx.settings).write(&path) {
+            Ok(()) => {
+                ctx.terminal.add_log(format!("Exported diagnostics to {}", path.display()), LogType::Default);
+                ctx.notifier.notify(Notification::success(tr!("notify-diagnostics-exported")).body(path.display().to_string()));
+            }
+            Err(e) => {
+                ctx.terminal.add_log(format!("Failed to export diagnostics: {}", e), LogType::Error);
+                ctx.notifier.notify(Notification::error(tr!("notify-export-diagnostics-failed")).body(e.to_string()));
+            }
+        }
+    })
+    .category("category-help")
+    .menu(Menu::Help)
+}
+
+fn undo_action() -> Action {
+    Action::new("edit.undo", "action-undo", |ctx| {
+        ctx.history.undo();
+    })
+    .category("category-edit")
+    .menu(Menu::Edit)
+    .shortcut(egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z))
+}
+
+fn redo_action() -> Action {
+    Action::new("edit.redo", "action-redo", |ctx| {
+        ctx.history.redo();
+    })
+    .category("category-edit")
+    .menu(Menu::Edit)
+    .shortcut(egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z))
+}
+
+fn copy_log_action() -> Action {
+    Action::new("edit.copy_log", "action-copy-log", |ctx| {
+        ctx.terminal.repaint.copy_text(ctx.terminal.logs_text());
+    })
+    .category("category-edit")
+    .menu(Menu::Edit)
+}
+
+/// Writes the log to a time-stamped file in the `logs` directory next to
+/// the settings file
+fn export_log_action() -> Action {
+    Action::new("file.export_log", "action-export-log", |ctx| {
+        let name = format!("log-{}.txt", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let path = ctx.settings.path().with_file_name("logs").join(name);
+        match ctx.terminal.export_logs(&path) {
+            Ok(()) => {
+                ctx.terminal.add_log(format!("Exported log to {}", path.display()), LogType::Default);
+                ctx.notifier.notify(Notification::success(tr!("notify-log-exported")).body(path.display().to_string()));
+            }
+            Err(e) => {
+                ctx.terminal.add_log(format!("Failed to export log: {}", e), LogType::Error);
+                ctx.notifier.notify(Notification::error(tr!("notify-export-log-failed")).body(e.to_string()));
+            }
+        }
+    })
+    .category("category-file")
+    .menu(Menu::File)
+    .shortcut(egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::E))
+}
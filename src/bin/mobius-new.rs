@@ -0,0 +1,117 @@
+//! mobius-new
+//!
+//! Generates a new application from one of the templates of this crate,
+//! see `egui_mobius_template::scaffold`.
+use egui_mobius_template::scaffold::{self, ProjectOptions, Template};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: mobius-new <name> [options]
+       mobius-new --list
+
+Options:
+  -t, --template <name>     template to start from (default: reactive)
+      --title <title>       window title (default: the name in title case)
+      --config-dir <name>   configuration directory name (default: the name)
+      --panels <a,b,...>    panels to include (default: all of the template)
+      --path <dir>          depend on a local checkout of egui_mobius_template
+  -o, --out <dir>           directory to create (default: ./<name>)
+      --list                list the templates and their panels
+  -h, --help                show this help";
+
+/// Parsed command line
+struct Args {
+    options : ProjectOptions,
+    out     : Option<PathBuf>,
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut name = None;
+    let mut template = Template::Reactive;
+    let mut title = None;
+    let mut config_dir_name = None;
+    let mut panels = None;
+    let mut path = None;
+    let mut out = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--list" => {
+                list();
+                std::process::exit(0);
+            }
+            "-t" | "--template" => template = value(&arg)?.parse().map_err(|err| format!("{}", err))?,
+            "--title" => title = Some(value(&arg)?),
+            "--config-dir" => config_dir_name = Some(value(&arg)?),
+            "--panels" => {
+                panels = Some(value(&arg)?.split(',').map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect())
+            }
+            "--path" => path = Some(PathBuf::from(value(&arg)?)),
+            "-o" | "--out" => out = Some(PathBuf::from(value(&arg)?)),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            _ if name.is_none() => name = Some(arg),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    let name = name.ok_or("missing project name")?;
+    let mut options = ProjectOptions::new(name, template);
+    options.title = title;
+    options.config_dir_name = config_dir_name;
+    options.panels = panels;
+    if let Some(path) = path {
+        let path = path.canonicalize().map_err(|err| format!("{}: {}", path.display(), err))?;
+        options = options.local_dependency(&path);
+    }
+    Ok(Some(Args { options, out }))
+}
+
+fn list() {
+    for template in Template::ALL {
+        println!("{:<16}{}", template.name(), template.description());
+        let panels: Vec<_> = template
+            .panels()
+            .iter()
+            .map(|panel| if panel.required { format!("{} (required)", panel.id) } else { panel.id.to_string() })
+            .collect();
+        println!("{:<16}panels: {}", "", panels.join(", "));
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let out = args.out.unwrap_or_else(|| PathBuf::from(&args.options.name));
+    if args.options.uses_release_tag() {
+        eprintln!(
+            "warning: {} depends on the v{} tag of egui_mobius_template, which only exists once that \
+             version is released; use --path <dir> to depend on a local checkout instead",
+            out.display(),
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    match scaffold::write(&out, &args.options) {
+        Ok(files) => {
+            println!("Created {} from the {} template ({} files)", out.display(), args.options.template, files.len());
+            println!("\n    cd {}\n    cargo run", out.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
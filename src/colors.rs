@@ -1,29 +1,181 @@
 use serde::{Deserialize, Serialize};
 use egui::Color32;
-use std::path::PathBuf;
-use std::fs;
+use crate::settings::SettingsSection;
 
+/// Serde helpers for `Color32`
+///
+/// Colors are serialized as unmultiplied `[r, g, b, a]` arrays, which
+/// round-trip losslessly through `Color32::from_rgba_unmultiplied`.
+///
+/// When deserializing, any of the formats understood by `parse_color`
+/// is accepted in addition to `[r, g, b]` / `[r, g, b, a]` arrays, so
+/// theme files can be edited by hand.
 pub mod color32_serde {
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::de::{self, SeqAccess, Visitor};
+    use serde::{Deserializer, Serialize, Serializer};
     use eframe::egui::Color32;
+    use std::fmt;
 
     pub fn serialize<S>(color: &Color32, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let rgba = [color.r(), color.g(), color.b(), color.a()];
-        rgba.serialize(serializer)
+        color.to_srgba_unmultiplied().serialize(serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<Color32, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let rgba = <[u8; 4]>::deserialize(deserializer)?;
-        Ok(Color32::from_rgba_unmultiplied(rgba[0], rgba[1], rgba[2], rgba[3]))
+        deserializer.deserialize_any(ColorVisitor)
+    }
+
+    struct ColorVisitor;
+
+    impl<'de> Visitor<'de> for ColorVisitor {
+        type Value = Color32;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an [r, g, b(, a)] array, a hex string, a CSS color name or rgb()/hsl() notation")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Color32, E> {
+            super::parse_color(value).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color32, A::Error> {
+            let mut rgba = [0u8, 0, 0, 255];
+            let mut len = 0;
+            while let Some(component) = seq.next_element::<u8>()? {
+                if len == 4 {
+                    return Err(de::Error::invalid_length(5, &self));
+                }
+                rgba[len] = component;
+                len += 1;
+            }
+            if len < 3 {
+                return Err(de::Error::invalid_length(len, &self));
+            }
+            Ok(Color32::from_rgba_unmultiplied(rgba[0], rgba[1], rgba[2], rgba[3]))
+        }
     }
 }
 
+/// ColorParseError
+///
+/// Returned by `parse_color` for strings that are not a known color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorParseError(String);
+
+impl std::fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid color \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// Parse a human-friendly color string
+///
+/// Accepted formats:
+/// - hex: `#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`
+/// - CSS color names, e.g. `"orange"`, `"rebeccapurple"`, `"transparent"`
+/// - `rgb(255, 128, 0)`, `rgba(255, 128, 0, 0.5)`, `rgb(100% 50% 0% / 50%)`
+/// - `hsl(30, 100%, 50%)`, `hsla(30deg, 100%, 50%, 0.5)`
+pub fn parse_color(input: &str) -> Result<Color32, ColorParseError> {
+    let err = || ColorParseError(input.to_string());
+    let s = input.trim();
+
+    if s.starts_with('#') {
+        return Color32::from_hex(s).map_err(|_| err());
+    }
+
+    let lower = s.to_ascii_lowercase();
+    if let Some(open) = lower.find('(') {
+        let name = lower[..open].trim();
+        let args = lower[open + 1..].strip_suffix(')').ok_or_else(err)?;
+        let args = split_color_args(args);
+        return match name {
+            "rgb" | "rgba" => parse_rgb_args(&args).ok_or_else(err),
+            "hsl" | "hsla" => parse_hsl_args(&args).ok_or_else(err),
+            _ => Err(err()),
+        };
+    }
+
+    if lower == "transparent" {
+        return Ok(Color32::TRANSPARENT);
+    }
+    CSS_COLORS
+        .iter()
+        .find(|(name, _)| *name == lower)
+        .map(|(_, [r, g, b])| Color32::from_rgb(*r, *g, *b))
+        .ok_or_else(err)
+}
+
+/// Split `rgb()`/`hsl()` arguments written either comma separated or
+/// in the CSS Color 4 space separated form with an optional `/ alpha`.
+fn split_color_args(args: &str) -> Vec<&str> {
+    args.split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Alpha given as a fraction (`0.5`) or percentage (`50%`)
+fn parse_alpha(arg: Option<&&str>) -> Option<u8> {
+    match arg {
+        None => Some(255),
+        Some(a) => {
+            let value = match a.strip_suffix('%') {
+                Some(pct) => pct.parse::<f32>().ok()? / 100.0,
+                None => a.parse::<f32>().ok()?,
+            };
+            Some((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+        }
+    }
+}
+
+fn parse_rgb_args(args: &[&str]) -> Option<Color32> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let channel = |arg: &str| -> Option<u8> {
+        let value = match arg.strip_suffix('%') {
+            Some(pct) => pct.parse::<f32>().ok()? * 2.55,
+            None => arg.parse::<f32>().ok()?,
+        };
+        Some(value.clamp(0.0, 255.0).round() as u8)
+    };
+    let alpha = parse_alpha(args.get(3))?;
+    Some(Color32::from_rgba_unmultiplied(
+        channel(args[0])?,
+        channel(args[1])?,
+        channel(args[2])?,
+        alpha,
+    ))
+}
+
+fn parse_hsl_args(args: &[&str]) -> Option<Color32> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let hue = args[0].trim_end_matches("deg").parse::<f32>().ok()?.rem_euclid(360.0);
+    let percent = |arg: &str| -> Option<f32> {
+        Some((arg.strip_suffix('%')?.parse::<f32>().ok()? / 100.0).clamp(0.0, 1.0))
+    };
+    let saturation = percent(args[1])?;
+    let lightness = percent(args[2])?;
+    let alpha = parse_alpha(args.get(3))?;
+
+    // https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+    let a = saturation * lightness.min(1.0 - lightness);
+    let f = |n: f32| {
+        let k = (n + hue / 30.0) % 12.0;
+        let value = lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        (value * 255.0).round() as u8
+    };
+    Some(Color32::from_rgba_unmultiplied(f(0.0), f(8.0), f(4.0), alpha))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LogColors {
     #[serde(with = "color32_serde")]
@@ -61,65 +213,190 @@ impl Default for LogColors {
 
 
 impl LogColors {
-    #[allow(dead_code)]
-    pub fn load() -> Self {
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("egui_mobius_template");
-        let config_path = config_dir.join("log_colors.json");
-        
-        println!("Loading colors from: {}", config_path.display());
-        
-        match fs::read_to_string(&config_path) {
-            Ok(file_content) => {
-                match serde_json::from_str(&file_content) {
-                    Ok(colors) => {
-                        println!("Successfully loaded colors from file");
-                        colors
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse colors JSON: {}", e);
-                        Self::default()
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to read colors file: {}", e);
-                Self::default()
-            }
-        }
+    /// Message ids of the names and values of all entries, in settings
+    /// panel order
+    pub fn entries(&self) -> [(&'static str, Color32); 8] {
+        [
+            ("log-color-slider", self.slider),
+            ("log-color-option-a", self.option_a),
+            ("log-color-option-b", self.option_b),
+            ("log-color-option-c", self.option_c),
+            ("log-color-custom-event", self.custom_event),
+            ("log-color-run-stop", self.run_stop_log),
+            ("log-color-time-format", self.time_format),
+            ("log-color-clock", self.clock),
+        ]
     }
 
-    #[allow(dead_code)]
-    pub fn save(&self) {
-        let colors = self.clone();
-        std::thread::spawn(move || {
-            // Get config directory path
-            let config_dir = dirs::config_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join("egui_mobius_template");
-            
-            // Create config directory if it doesn't exist
-            if let Err(e) = fs::create_dir_all(&config_dir) {
-                eprintln!("Failed to create config directory: {}", e);
-                return;
-            }
-            
-            // Create config file path
-            let config_path = config_dir.join("log_colors.json");
-            
-            // Serialize colors to JSON
-            match serde_json::to_string_pretty(&colors) {
-                Ok(json) => {
-                    // Write JSON to file
-                    if let Err(e) = fs::write(&config_path, json) {
-                        eprintln!("Failed to write colors to {}: {}", config_path.display(), e);
-                    } else {
-                        println!("Successfully saved colors to {}", config_path.display());
-                    }
-                },
-                Err(e) => eprintln!("Failed to serialize colors: {}", e),
-            }
-        });
+    /// Message ids of the names and mutable references to all entries,
+    /// in settings panel order
+    pub fn entries_mut(&mut self) -> [(&'static str, &mut Color32); 8] {
+        [
+            ("log-color-slider", &mut self.slider),
+            ("log-color-option-a", &mut self.option_a),
+            ("log-color-option-b", &mut self.option_b),
+            ("log-color-option-c", &mut self.option_c),
+            ("log-color-custom-event", &mut self.custom_event),
+            ("log-color-run-stop", &mut self.run_stop_log),
+            ("log-color-time-format", &mut self.time_format),
+            ("log-color-clock", &mut self.clock),
+        ]
     }
 }
+
+impl SettingsSection for LogColors {
+    const KEY: &'static str = "log_colors";
+    const LEGACY_FILE: Option<&'static str> = Some("log_colors.json");
+}
+
+/// The CSS Color Module Level 4 named colors
+const CSS_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
@@ -0,0 +1,416 @@
+//! Crash reports and diagnostics bundles
+//!
+//! `CrashHandler::install` sets a panic hook that writes a `CrashReport`
+//! to `crashes/` next to the settings file before the default hook runs:
+//! the panic message and location, a backtrace, the last entries of the
+//! terminal log, the current settings and `SystemInfo`. `MobiusApp`
+//! installs it on startup and shows the newest report it has not shown
+//! yet on the next launch; a panic in a background thread is notified
+//! right away, as the application keeps running.
+//!
+//! A `DiagnosticsBundle` collects the log, the settings, the system
+//! information and the crash reports into a single zip archive to attach
+//! to a bug report, see the shell's "Export diagnostics" action.
+use crate::notifications::{Notification, Notifier};
+use crate::persistence::{read_json, write_atomic, write_json, PersistError};
+use crate::settings::{SettingsSection, SettingsStore};
+use crate::{tr, LogType};
+use chrono::{DateTime, Local};
+use egui_mobius_reactive::Dynamic;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Cursor, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use zip::write::SimpleFileOptions;
+
+/// Number of log entries kept in a crash report
+pub const CRASH_LOG_ENTRIES: usize = 200;
+
+/// How long the panic hook waits for the log and settings, which may be
+/// locked by the panicking thread
+const COLLECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Name of the thread collecting a crash report's log and settings;
+/// its own panics are not reported again
+const COLLECTOR_THREAD: &str = "crash-report";
+
+/// Directory holding the crash reports next to the settings file
+/// `settings_path`
+pub fn crashes_dir(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("crashes")
+}
+
+/// Directory the diagnostics bundles are exported to
+pub fn diagnostics_dir(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("diagnostics")
+}
+
+/// DiagnosticsError
+///
+/// Errors raised while writing a diagnostics bundle.
+#[derive(Debug)]
+pub enum DiagnosticsError {
+    /// A file could not be read or written
+    Persist(PersistError),
+    /// The archive could not be built
+    Zip(zip::result::ZipError),
+}
+
+impl fmt::Display for DiagnosticsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticsError::Persist(e) => write!(f, "{}", e),
+            DiagnosticsError::Zip(e) => write!(f, "failed to build the archive: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DiagnosticsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DiagnosticsError::Persist(e) => Some(e),
+            DiagnosticsError::Zip(e) => Some(e),
+        }
+    }
+}
+
+impl From<PersistError> for DiagnosticsError {
+    fn from(e: PersistError) -> Self {
+        DiagnosticsError::Persist(e)
+    }
+}
+
+impl From<zip::result::ZipError> for DiagnosticsError {
+    fn from(e: zip::result::ZipError) -> Self {
+        DiagnosticsError::Zip(e)
+    }
+}
+
+impl From<io::Error> for DiagnosticsError {
+    fn from(e: io::Error) -> Self {
+        DiagnosticsError::Zip(e.into())
+    }
+}
+
+/// SystemInfo
+///
+/// Versions and platform of the running application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemInfo {
+    /// Name of the executable
+    pub app              : String,
+    pub template_version : String,
+    pub os               : String,
+    pub arch             : String,
+    pub cpus             : usize,
+    pub language         : String,
+}
+
+impl SystemInfo {
+    pub fn current() -> Self {
+        let app = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Self {
+            app,
+            template_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: format!("{} ({})", std::env::consts::OS, std::env::consts::FAMILY),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: std::thread::available_parallelism().map_or(1, |count| count.get()),
+            language: crate::i18n::language(),
+        }
+    }
+}
+
+impl fmt::Display for SystemInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Application: {}", self.app)?;
+        writeln!(f, "egui_mobius_template: {}", self.template_version)?;
+        writeln!(f, "OS: {}", self.os)?;
+        writeln!(f, "Architecture: {}", self.arch)?;
+        writeln!(f, "CPUs: {}", self.cpus)?;
+        writeln!(f, "Language: {}", self.language)
+    }
+}
+
+/// CrashReport
+///
+/// What was known when the application panicked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub time      : DateTime<Local>,
+    pub thread    : String,
+    pub message   : String,
+    /// `file:line:column` of the panic
+    pub location  : Option<String>,
+    pub backtrace : String,
+    /// The last `CRASH_LOG_ENTRIES` log entries, oldest first
+    pub logs      : Vec<String>,
+    /// The settings document, `null` if it could not be read
+    pub settings  : Value,
+    pub system    : SystemInfo,
+}
+
+impl CrashReport {
+    pub fn load(path: &Path) -> Result<Self, PersistError> {
+        read_json(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PersistError> {
+        write_json(path, self)
+    }
+
+    /// File name of a report written at `time`; names sort by time
+    pub fn file_name(time: &DateTime<Local>) -> String {
+        format!("crash-{}.json", time.format("%Y%m%d-%H%M%S%.3f"))
+    }
+
+    /// The reports in `dir`, oldest first
+    pub fn list(dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().is_some_and(|extension| extension == "json")
+                    && path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("crash-"))
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// The report as plain text, e.g. to paste into an issue
+    pub fn to_text(&self) -> String {
+        let mut text = format!("Panic at {}", self.time.format("%Y-%m-%d %H:%M:%S%.3f %:z"));
+        text.push_str(&format!(" in thread '{}': {}\n", self.thread, self.message));
+        if let Some(location) = &self.location {
+            text.push_str(&format!("Location: {}\n", location));
+        }
+        text.push_str(&format!("\n{}\nBacktrace:\n{}\n", self.system, self.backtrace));
+        if !self.logs.is_empty() {
+            text.push_str("\nLast log entries:\n");
+            for entry in &self.logs {
+                text.push_str(entry);
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// DiagnosticsSettings
+///
+/// The newest crash report already shown, by file name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticsSettings {
+    pub last_seen_crash: Option<String>,
+}
+
+impl SettingsSection for DiagnosticsSettings {
+    const KEY: &'static str = "diagnostics";
+}
+
+impl DiagnosticsSettings {
+    /// The newest report in `dir` if it was not shown yet
+    pub fn unseen_crash(&self, dir: &Path) -> Option<PathBuf> {
+        let newest = CrashReport::list(dir).pop()?;
+        let name = newest.file_name()?.to_string_lossy().into_owned();
+        match &self.last_seen_crash {
+            Some(seen) if *seen >= name => None,
+            _ => Some(newest),
+        }
+    }
+}
+
+/// Log entries as they appear in reports and exported logs
+fn log_lines(logs: &VecDeque<(String, LogType)>, count: usize) -> Vec<String> {
+    logs.iter()
+        .skip(logs.len().saturating_sub(count))
+        .map(|(message, log_type)| format!("[{:?}] {}", log_type, message))
+        .collect()
+}
+
+#[derive(Default)]
+struct Attached {
+    logs     : Option<Dynamic<VecDeque<(String, LogType)>>>,
+    settings : Option<SettingsStore>,
+    notifier : Option<Notifier>,
+}
+
+/// CrashHandler
+///
+/// The installed panic hook, see the module documentation. The log,
+/// settings and notifier are attached once they exist; a panic before
+/// that is reported without them.
+#[derive(Clone)]
+pub struct CrashHandler {
+    dir      : PathBuf,
+    attached : Arc<Mutex<Attached>>,
+}
+
+impl CrashHandler {
+    /// Write crash reports to `dir` from now on. The previous hook, by
+    /// default the one printing the panic, still runs afterwards.
+    pub fn install(dir: impl Into<PathBuf>) -> Self {
+        let handler = Self { dir: dir.into(), attached: Arc::new(Mutex::new(Attached::default())) };
+        let hook = handler.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if std::thread::current().name() != Some(COLLECTOR_THREAD) {
+                hook.report(info);
+            }
+            previous(info);
+        }));
+        handler
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Include the last entries of `logs` in reports
+    pub fn attach_logs(&self, logs: &Dynamic<VecDeque<(String, LogType)>>) {
+        self.attached.lock().unwrap().logs = Some(logs.clone());
+    }
+
+    /// Include the settings document in reports
+    pub fn attach_settings(&self, settings: &SettingsStore) {
+        self.attached.lock().unwrap().settings = Some(settings.clone());
+    }
+
+    /// Notify panics, which only matters for background threads as a
+    /// panic of the UI thread ends the application
+    pub fn attach_notifier(&self, notifier: &Notifier) {
+        self.attached.lock().unwrap().notifier = Some(notifier.clone());
+    }
+
+    /// Write the report of the panic `info`. Returns where it was written.
+    fn report(&self, info: &PanicHookInfo<'_>) -> Option<PathBuf> {
+        let payload = info.payload();
+        let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        let location = info.location().map(|location| location.to_string());
+        let thread = std::thread::current().name().unwrap_or("<unnamed>").to_string();
+        let backtrace = Backtrace::force_capture().to_string();
+
+        // The panicking thread may hold the locks of the log or the
+        // settings, so they are read by another thread
+        let Attached { logs, settings, notifier } = match self.attached.try_lock() {
+            Ok(attached) => Attached {
+                logs: attached.logs.clone(),
+                settings: attached.settings.clone(),
+                notifier: attached.notifier.clone(),
+            },
+            Err(_) => Attached::default(),
+        };
+        let (sender, receiver) = mpsc::channel();
+        let notice = message.clone();
+        let collector = std::thread::Builder::new().name(COLLECTOR_THREAD.to_string()).spawn(move || {
+            let logs = logs.map(|logs| log_lines(&logs.get(), CRASH_LOG_ENTRIES)).unwrap_or_default();
+            let settings = settings.map_or(Value::Null, |settings| Value::Object(settings.document()));
+            let _ = sender.send((logs, settings));
+            if let Some(notifier) = notifier {
+                notifier.notify(Notification::error(tr!("notify-crashed"))
+                    .body(notice)
+                    .action(tr!("action-export-diagnostics"), "action:help.export_diagnostics"));
+            }
+        });
+        let (logs, settings) = match collector {
+            Ok(_) => receiver.recv_timeout(COLLECT_TIMEOUT).unwrap_or((Vec::new(), Value::Null)),
+            Err(_) => (Vec::new(), Value::Null),
+        };
+
+        let report = CrashReport {
+            time: Local::now(),
+            thread,
+            message,
+            location,
+            backtrace,
+            logs,
+            settings,
+            system: SystemInfo::current(),
+        };
+        let path = self.dir.join(CrashReport::file_name(&report.time));
+        match report.save(&path) {
+            Ok(()) => {
+                eprintln!("Crash report written to {}", path.display());
+                Some(path)
+            }
+            Err(e) => {
+                eprintln!("Failed to write crash report: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// DiagnosticsBundle
+///
+/// Files collected for a bug report, written as one zip archive.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsBundle {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl DiagnosticsBundle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The system information, the whole log, the settings document and
+    /// every crash report next to the settings file
+    pub fn collect(logs: &VecDeque<(String, LogType)>, settings: &SettingsStore) -> Self {
+        let mut log = log_lines(logs, logs.len()).join("\n");
+        log.push('\n');
+        let document = serde_json::to_vec_pretty(&settings.document()).unwrap_or_default();
+        let mut bundle = Self::new()
+            .file("system.txt", SystemInfo::current().to_string())
+            .file("log.txt", log)
+            .file("settings.json", document);
+        for path in CrashReport::list(&crashes_dir(settings.path())) {
+            if let (Some(name), Ok(contents)) = (path.file_name(), std::fs::read(&path)) {
+                bundle = bundle.file(format!("crashes/{}", name.to_string_lossy()), contents);
+            }
+        }
+        bundle
+    }
+
+    /// Add the file `name`, a path inside the archive
+    pub fn file(mut self, name: impl Into<String>, contents: impl Into<Vec<u8>>) -> Self {
+        self.files.push((name.into(), contents.into()));
+        self
+    }
+
+    /// Names of the files in the bundle, in the order they were added
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Write the archive to `path`, atomically
+    pub fn write(&self, path: &Path) -> Result<(), DiagnosticsError> {
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, contents) in &self.files {
+            archive.start_file(name.as_str(), options)?;
+            archive.write_all(contents)?;
+        }
+        let bytes = archive.finish()?.into_inner();
+        write_atomic(path, &bytes)?;
+        Ok(())
+    }
+
+    /// File name of a bundle exported at `time`
+    pub fn file_name(time: &DateTime<Local>) -> String {
+        format!("diagnostics-{}.zip", time.format("%Y%m%d-%H%M%S"))
+    }
+}
@@ -0,0 +1,397 @@
+//! Undo/redo history for reactive application state
+//!
+//! Every change is recorded as a `Command` that knows how to undo and
+//! redo itself. `History::track` records changes of a `Dynamic` value
+//! automatically, so panels keep writing to their state as before. A
+//! `MergeRule` decides whether consecutive changes of the same value
+//! form one step, e.g. all values passed while dragging a slider.
+//! Changes reach the history on the thread that delivers `Dynamic`
+//! notifications; `History::is_settled` tells when all have arrived.
+//!
+//! ```
+//! use egui_mobius_reactive::Dynamic;
+//! use egui_mobius_template::history::{History, MergeRule};
+//!
+//! let volume = Dynamic::new(1.0_f32);
+//! let history = History::new();
+//! history.track(&volume, "Volume", MergeRule::Never);
+//!
+//! volume.set(2.0);
+//! while !history.is_settled() {
+//!     std::thread::yield_now();
+//! }
+//! assert!(history.undo());
+//! assert_eq!(volume.get(), 1.0);
+//! assert!(history.redo());
+//! assert_eq!(volume.get(), 2.0);
+//! ```
+use crate::panel::{AppContext, Panel};
+use crate::shortcuts::ShortcutManager;
+use crate::tr;
+use crate::transaction::dynamic_id;
+use eframe::egui;
+use egui_mobius_reactive::{Dynamic, ValueExt};
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default number of steps kept in the history
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Pause after which a drag that merges changes starts a new step
+pub const DEFAULT_MERGE_WINDOW: Duration = Duration::from_millis(750);
+
+/// MergeRule
+///
+/// How consecutive changes of the same tracked value are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeRule {
+    /// Every change is its own step, e.g. selecting an option
+    Never,
+    /// Changes less than the given time apart form one step, e.g. the
+    /// values passed while dragging a slider or color picker
+    Within(Duration),
+}
+
+impl MergeRule {
+    /// Merge changes less than `DEFAULT_MERGE_WINDOW` apart
+    pub fn continuous() -> Self {
+        MergeRule::Within(DEFAULT_MERGE_WINDOW)
+    }
+}
+
+/// Command
+///
+/// One reversible step of the history.
+pub trait Command: Send {
+    /// Short description shown in the history list
+    fn label(&self) -> String;
+    /// Revert the change
+    fn undo(&mut self);
+    /// Apply the change again
+    fn redo(&mut self);
+    /// Absorb `next`, recorded right after this command, into this one.
+    /// Returns false if the two must stay separate steps.
+    fn merge(&mut self, _next: &mut dyn Command) -> bool {
+        false
+    }
+    /// True if the command no longer changes anything, e.g. after a
+    /// drag returned to its starting value
+    fn is_noop(&self) -> bool {
+        false
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// ValueChange
+///
+/// A change of a tracked `Dynamic` from one value to another.
+pub struct ValueChange<T> {
+    label   : String,
+    target  : Dynamic<T>,
+    /// Last value seen by the tracker, updated before undo/redo write so
+    /// the resulting notification is not recorded as a new change
+    seen    : Arc<Mutex<T>>,
+    before  : T,
+    after   : T,
+    rule    : MergeRule,
+    at      : Instant,
+}
+
+impl<T: Clone + Send + Sync + PartialEq + 'static> ValueChange<T> {
+    fn apply(&self, value: &T) {
+        *self.seen.lock().unwrap() = value.clone();
+        self.target.set(value.clone());
+    }
+}
+
+impl<T: Clone + Send + Sync + PartialEq + 'static> Command for ValueChange<T> {
+    fn label(&self) -> String {
+        self.label.clone()
+    }
+
+    fn undo(&mut self) {
+        self.apply(&self.before);
+    }
+
+    fn redo(&mut self) {
+        self.apply(&self.after);
+    }
+
+    fn merge(&mut self, next: &mut dyn Command) -> bool {
+        let MergeRule::Within(window) = self.rule else {
+            return false;
+        };
+        let Some(next) = next.as_any_mut().downcast_mut::<ValueChange<T>>() else {
+            return false;
+        };
+        if dynamic_id(&self.target) != dynamic_id(&next.target)
+            || next.at.duration_since(self.at) > window
+        {
+            return false;
+        }
+        self.after = next.after.clone();
+        self.at = next.at;
+        true
+    }
+
+    fn is_noop(&self) -> bool {
+        self.before == self.after
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Whether a tracker has recorded the current value of its `Dynamic`
+type Settled = Box<dyn Fn() -> bool + Send>;
+
+struct HistoryInner {
+    undo     : Vec<Box<dyn Command>>,
+    redo     : Vec<Box<dyn Command>>,
+    limit    : usize,
+    repaint  : Option<egui::Context>,
+    trackers : Vec<Settled>,
+}
+
+/// History
+///
+/// Undo and redo stacks shared by all clones. Recording a new step
+/// clears the redo stack.
+#[derive(Clone)]
+pub struct History {
+    inner: Arc<Mutex<HistoryInner>>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    /// Create a history keeping `DEFAULT_LIMIT` steps
+    pub fn new() -> Self {
+        Self::with_limit(DEFAULT_LIMIT)
+    }
+
+    /// Create a history keeping at most `limit` steps
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HistoryInner {
+                undo: Vec::new(),
+                redo: Vec::new(),
+                limit,
+                repaint: None,
+                trackers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Request a repaint of `ctx` whenever a step is recorded, so a
+    /// visible history list stays current
+    pub fn with_repaint(self, ctx: &egui::Context) -> Self {
+        self.inner.lock().unwrap().repaint = Some(ctx.clone());
+        self
+    }
+
+    /// Record every change of `dynamic` as a step labelled `label`, which
+    /// may be a message id
+    pub fn track<T>(&self, dynamic: &Dynamic<T>, label: &str, rule: MergeRule)
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        let seen = Arc::new(Mutex::new(dynamic.get()));
+        let (tracked, last) = (dynamic.clone(), seen.clone());
+        self.inner.lock().unwrap().trackers.push(Box::new(move || *last.lock().unwrap() == tracked.get()));
+        let history = self.clone();
+        let target = dynamic.clone();
+        let label = label.to_string();
+        dynamic.on_change(move || {
+            let after = target.get();
+            let before = {
+                let mut seen = seen.lock().unwrap();
+                if *seen == after {
+                    return;
+                }
+                std::mem::replace(&mut *seen, after.clone())
+            };
+            history.push(Box::new(ValueChange {
+                label: label.clone(),
+                target: target.clone(),
+                seen: seen.clone(),
+                before,
+                after,
+                rule,
+                at: Instant::now(),
+            }));
+        });
+    }
+
+    /// True once every change of the tracked values has been recorded.
+    /// Changes are delivered on another thread, so one made just now may
+    /// not be in the history yet.
+    pub fn is_settled(&self) -> bool {
+        self.inner.lock().unwrap().trackers.iter().all(|settled| settled())
+    }
+
+    /// Record a step that has already been applied
+    pub fn push(&self, mut command: Box<dyn Command>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.redo.clear();
+
+        let merged = match inner.undo.last_mut() {
+            Some(last) => last.merge(command.as_mut()),
+            None => false,
+        };
+        if merged {
+            if inner.undo.last().is_some_and(|last| last.is_noop()) {
+                inner.undo.pop();
+            }
+        } else {
+            inner.undo.push(command);
+            if inner.undo.len() > inner.limit {
+                inner.undo.remove(0);
+            }
+        }
+
+        if let Some(ctx) = &inner.repaint {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Revert the most recent step. Returns false if there is none.
+    pub fn undo(&self) -> bool {
+        let command = self.inner.lock().unwrap().undo.pop();
+        let Some(mut command) = command else {
+            return false;
+        };
+        command.undo();
+        self.inner.lock().unwrap().redo.push(command);
+        true
+    }
+
+    /// Apply the most recently undone step again. Returns false if there
+    /// is none.
+    pub fn redo(&self) -> bool {
+        let command = self.inner.lock().unwrap().redo.pop();
+        let Some(mut command) = command else {
+            return false;
+        };
+        command.redo();
+        self.inner.lock().unwrap().undo.push(command);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.inner.lock().unwrap().undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.inner.lock().unwrap().redo.is_empty()
+    }
+
+    /// Labels of all steps, oldest first, and the number of them that
+    /// are currently applied
+    pub fn steps(&self) -> (Vec<String>, usize) {
+        let inner = self.inner.lock().unwrap();
+        let applied = inner.undo.len();
+        let labels = inner.undo.iter()
+            .chain(inner.redo.iter().rev())
+            .map(|command| command.label())
+            .collect();
+        (labels, applied)
+    }
+
+    /// Undo or redo until exactly `applied` steps are applied
+    pub fn go_to(&self, applied: usize) {
+        while self.steps().1 > applied && self.undo() {}
+        while self.steps().1 < applied && self.redo() {}
+    }
+
+    /// Forget all steps
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.undo.clear();
+        inner.redo.clear();
+    }
+
+    /// Undo/redo buttons and the list of steps. Clicking a step undoes
+    /// or redoes everything after or up to it. The buttons show the
+    /// shortcuts bound to undo and redo in `shortcuts` on hover.
+    pub fn ui(&self, ui: &mut egui::Ui, shortcuts: &ShortcutManager) {
+        let bound = |key: &str| shortcuts.binding(key).map(|shortcut| ui.ctx().format_shortcut(&shortcut));
+        let (undo_shortcut, redo_shortcut) = (bound("action:edit.undo"), bound("action:edit.redo"));
+        ui.horizontal(|ui| {
+            let mut undo = ui.add_enabled(self.can_undo(), egui::Button::new(tr!("history-undo")));
+            if let Some(text) = &undo_shortcut {
+                undo = undo.on_hover_text(text);
+            }
+            if undo.clicked() {
+                self.undo();
+            }
+            let mut redo = ui.add_enabled(self.can_redo(), egui::Button::new(tr!("history-redo")));
+            if let Some(text) = &redo_shortcut {
+                redo = redo.on_hover_text(text);
+            }
+            if redo.clicked() {
+                self.redo();
+            }
+            if ui.button(tr!("button-clear")).clicked() {
+                self.clear();
+            }
+        });
+        ui.separator();
+
+        let (labels, applied) = self.steps();
+        let mut target = None;
+        egui::ScrollArea::vertical()
+            .id_salt("history_steps")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if ui.selectable_label(applied == 0, tr!("history-initial-state")).clicked() {
+                    target = Some(0);
+                }
+                for (index, label) in labels.iter().enumerate() {
+                    let step = index + 1;
+                    let label = tr!(label);
+                    let text = if step <= applied {
+                        egui::RichText::new(label)
+                    } else {
+                        egui::RichText::new(label).weak()
+                    };
+                    if ui.selectable_label(step == applied, text).clicked() {
+                        target = Some(step);
+                    }
+                }
+            });
+        if let Some(step) = target {
+            self.go_to(step);
+        }
+    }
+}
+
+/// HistoryPanel
+///
+/// Dock panel showing `History::ui` for the history of the app context.
+#[derive(Default)]
+pub struct HistoryPanel;
+
+impl Panel for HistoryPanel {
+    fn id(&self) -> &'static str {
+        "history"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-history")
+    }
+
+    fn icon(&self) -> &'static str {
+        "🕘"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        ctx.history.ui(ui, ctx.shortcuts);
+    }
+}
@@ -0,0 +1,327 @@
+//! Localization
+//!
+//! UI strings are Fluent messages, looked up by id in the current
+//! language and formatted with arguments. The shell's messages for
+//! English, German and French are embedded in the crate; an application
+//! embeds its own `.ftl` files and adds them with `add_messages`, or
+//! `MobiusAppBuilder::messages`, adding languages or overriding the
+//! shell's messages:
+//!
+//! ```
+//! use egui_mobius_template::{i18n, tr};
+//!
+//! i18n::add_messages("en-US", "greeting = Hello, { $name }!").unwrap();
+//! i18n::add_messages("de", "greeting = Hallo, { $name }!").unwrap();
+//!
+//! assert_eq!(tr!("greeting", name = "Ada"), "Hello, Ada!");
+//! i18n::set_language("de-AT");
+//! assert_eq!(i18n::language(), "de");
+//! assert_eq!(tr!("greeting", name = "Ada"), "Hallo, Ada!");
+//! ```
+//!
+//! The language is process-wide and can be switched at any time; panels
+//! look their strings up every frame, so they follow immediately. Labels
+//! that are registered once - panel titles, action labels and categories,
+//! history steps - may be message ids, which the shell translates when it
+//! shows them. A label that is not the id of a message is shown as it is,
+//! so applications that are not localized need not change.
+//!
+//! Numbers, dates and times are formatted by the `locale-*` messages of
+//! the current language, see `format_number` and `format_datetime`.
+use crate::settings::SettingsSection;
+use eframe::egui;
+use chrono::{DateTime, TimeZone};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+use std::sync::{LazyLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentValue;
+
+/// Language used when no other one is chosen, and for messages missing
+/// from the current language
+pub const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// Messages of the shell, embedded in the crate
+const SHELL_MESSAGES: [(&str, &str); 3] = [
+    ("en-US", include_str!("../locales/en-US.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("fr", include_str!("../locales/fr.ftl")),
+];
+
+/// I18nError
+///
+/// Errors from adding messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum I18nError {
+    /// Not a valid language identifier such as `"de"` or `"en-US"`
+    InvalidLanguage(String),
+    /// Entries of a message file that could not be parsed. The other
+    /// entries of the file were added.
+    Parse { language: String, errors: Vec<String> },
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I18nError::InvalidLanguage(language) => write!(f, "invalid language identifier {:?}", language),
+            I18nError::Parse { language, errors } => {
+                write!(f, "invalid messages for {}: {}", language, errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for I18nError {}
+
+struct Catalog {
+    /// One bundle per language, the default language first
+    bundles : Vec<FluentBundle<FluentResource>>,
+    current : usize,
+}
+
+impl Catalog {
+    fn add(&mut self, language: &str, source: &str) -> Result<(), I18nError> {
+        let id: LanguageIdentifier = language
+            .parse()
+            .map_err(|_| I18nError::InvalidLanguage(language.to_string()))?;
+        let (resource, errors) = match FluentResource::try_new(source.to_string()) {
+            Ok(resource) => (resource, Vec::new()),
+            Err((resource, errors)) => (resource, errors.iter().map(|e| e.to_string()).collect()),
+        };
+        let index = match self.bundles.iter().position(|bundle| bundle.locales[0] == id) {
+            Some(index) => index,
+            None => {
+                let mut bundle = FluentBundle::new_concurrent(vec![id]);
+                // Unicode isolation marks show up as boxes in egui
+                bundle.set_use_isolating(false);
+                self.bundles.push(bundle);
+                self.bundles.len() - 1
+            }
+        };
+        self.bundles[index].add_resource_overriding(resource);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(I18nError::Parse { language: language.to_string(), errors })
+        }
+    }
+
+    /// Index of the bundle for `language`: the exact language, or else
+    /// the first one of the same primary language
+    fn find(&self, language: &str) -> Option<usize> {
+        let id: LanguageIdentifier = language.parse().ok()?;
+        self.bundles
+            .iter()
+            .position(|bundle| bundle.locales[0] == id)
+            .or_else(|| self.bundles.iter().position(|bundle| bundle.locales[0].language == id.language))
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        [self.current, 0].into_iter().find_map(|index| {
+            let bundle = &self.bundles[index];
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+        })
+    }
+}
+
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| {
+    let mut catalog = Catalog { bundles: Vec::new(), current: 0 };
+    for (language, source) in SHELL_MESSAGES {
+        catalog.add(language, source).expect("the shell's messages parse");
+    }
+    RwLock::new(catalog)
+});
+
+/// Add the messages of the Fluent file `source` to `language`, replacing
+/// messages with the same id. A new language becomes selectable.
+pub fn add_messages(language: &str, source: &str) -> Result<(), I18nError> {
+    CATALOG.write().unwrap().add(language, source)
+}
+
+/// Switch to `language`, or the closest language with messages, e.g.
+/// `"de"` for `"de-CH"`. Returns false, keeping the current language, if
+/// there is none.
+pub fn set_language(language: &str) -> bool {
+    let mut catalog = CATALOG.write().unwrap();
+    match catalog.find(language) {
+        Some(index) => {
+            catalog.current = index;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Identifier of the current language
+pub fn language() -> String {
+    let catalog = CATALOG.read().unwrap();
+    catalog.bundles[catalog.current].locales[0].to_string()
+}
+
+/// Identifier and native name of every language with messages
+pub fn languages() -> Vec<(String, String)> {
+    let catalog = CATALOG.read().unwrap();
+    catalog
+        .bundles
+        .iter()
+        .map(|bundle| {
+            let id = bundle.locales[0].to_string();
+            let name = bundle
+                .get_message("language-name")
+                .and_then(|message| message.value())
+                .map(|pattern| bundle.format_pattern(pattern, None, &mut Vec::new()).into_owned())
+                .unwrap_or_else(|| id.clone());
+            (id, name)
+        })
+        .collect()
+}
+
+/// Language of the operating system's locale, if it is known
+pub fn system_language() -> Option<String> {
+    sys_locale::get_locale()
+}
+
+/// Whether `id` is a message of the current or the default language
+pub fn has_message(id: &str) -> bool {
+    CATALOG.read().unwrap().format(id, None).is_some()
+}
+
+/// The message `id` in the current language, or `id` itself if there is
+/// no such message
+pub fn tr(id: &str) -> String {
+    CATALOG.read().unwrap().format(id, None).unwrap_or_else(|| id.to_string())
+}
+
+/// The message `id` formatted with `args`, see the `tr!` macro
+pub fn tr_args(id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    CATALOG.read().unwrap().format(id, Some(&fluent_args)).unwrap_or_else(|| id.to_string())
+}
+
+/// Look up a message in the current language, with optional named
+/// arguments. Strings and numbers can be passed as arguments; numbers
+/// select plural variants.
+///
+/// ```
+/// use egui_mobius_template::tr;
+///
+/// assert_eq!(tr!("menu-quit"), "Quit");
+/// assert_eq!(tr!("notifications-count", count = 1), "1 notification");
+/// assert_eq!(tr!("notifications-count", count = 3), "3 notifications");
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::tr($id)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr_args($id, &[$((stringify!($name), $crate::i18n::FluentValue::from($value))),+])
+    };
+}
+
+/// `value` with `decimals` decimals and the decimal and digit group
+/// separators of the current language
+pub fn format_number(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let group = tr("locale-group-separator");
+
+    let mut out = String::new();
+    if value.is_sign_negative() && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        out.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            out.push_str(&group);
+        }
+        out.push(digit);
+    }
+    if !fraction.is_empty() {
+        out.push_str(&tr("locale-decimal-separator"));
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// `time` formatted with the chrono pattern of the message `pattern`,
+/// falling back to ISO 8601 if a translation has an invalid pattern
+fn format_with<Tz: TimeZone>(time: &DateTime<Tz>, pattern: &str) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    let mut out = String::new();
+    if write!(out, "{}", time.format(&tr(pattern))).is_err() {
+        out = time.format("%Y-%m-%d %H:%M:%S").to_string();
+    }
+    out
+}
+
+/// Date and time, as in log timestamps
+pub fn format_datetime<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    format_with(time, "locale-datetime")
+}
+
+pub fn format_date<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    format_with(time, "locale-date")
+}
+
+/// Time of day in the usual clock of the current language
+pub fn format_time<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    format_with(time, "locale-time")
+}
+
+/// Time of day on a 24-hour or 12-hour clock, as chosen by the user
+pub fn format_clock<Tz: TimeZone>(time: &DateTime<Tz>, use_24h: bool) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    format_with(time, if use_24h { "locale-time-24h" } else { "locale-time-12h" })
+}
+
+/// LanguageSettings
+///
+/// The language chosen by the user, restored on the next launch.
+/// Without one the application follows the system language.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LanguageSettings {
+    pub language: Option<String>,
+}
+
+impl SettingsSection for LanguageSettings {
+    const KEY: &'static str = "language";
+}
+
+/// Combo box choosing the language. Returns true if it was switched.
+pub fn language_selector(ui: &mut egui::Ui) -> bool {
+    let current = language();
+    let languages = languages();
+    let selected = languages.iter().find(|(id, _)| *id == current).map_or(current.clone(), |(_, name)| name.clone());
+    let mut chosen = None;
+    egui::ComboBox::from_id_salt("language_selector")
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (id, name) in &languages {
+                if ui.selectable_label(*id == current, name).clicked() {
+                    chosen = Some(id.clone());
+                }
+            }
+        });
+    chosen.is_some_and(|id| id != current && set_language(&id))
+}
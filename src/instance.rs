@@ -0,0 +1,308 @@
+//! Single application instance with command forwarding
+//!
+//! With `MobiusAppBuilder::single_instance`, the first launch takes an
+//! exclusive lock on `instance.lock` next to the settings file and listens
+//! on the Unix-domain socket `instance.sock` beside it. A second launch
+//! with the same settings file cannot take the lock, so it sends its
+//! command-line arguments over the socket and exits instead of racing
+//! the first one on the settings. The lock is held by the open file, so
+//! it is released even if the application crashes.
+//!
+//! The running instance turns forwarded arguments into shell commands
+//! with `commands`: `--command KEY` (or `-c KEY`) runs the command `KEY`,
+//! e.g. `panel:logger`, and any other argument opens that file.
+use eframe::egui;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+#[cfg(unix)]
+use std::io::{BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::thread::JoinHandle;
+#[cfg(unix)]
+use std::time::Duration;
+
+/// How long a second launch waits for the running instance to listen,
+/// e.g. while it is still starting up
+#[cfg(unix)]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Lock file of the instance using the settings file `settings_path`
+pub fn lock_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("instance.lock")
+}
+
+/// Socket the instance using `settings_path` listens on
+pub fn socket_path(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("instance.sock")
+}
+
+/// InstanceError
+///
+/// Errors raised while locking, listening or forwarding arguments.
+#[derive(Debug)]
+pub enum InstanceError {
+    /// Creating, locking or connecting to a file failed
+    Io { path: PathBuf, source: io::Error },
+    /// The running instance did not accept the arguments
+    Rejected(String),
+    /// Forwarding needs Unix-domain sockets, which this platform lacks
+    Unsupported,
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            InstanceError::Rejected(reason) => write!(f, "the running instance rejected the arguments: {}", reason),
+            InstanceError::Unsupported => write!(f, "forwarding to the running instance is not supported on this platform"),
+        }
+    }
+}
+
+impl std::error::Error for InstanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InstanceError::Io { source, .. } => Some(source),
+            InstanceError::Rejected(_) | InstanceError::Unsupported => None,
+        }
+    }
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> InstanceError + '_ {
+    move |source| InstanceError::Io { path: path.to_path_buf(), source }
+}
+
+/// Instance
+///
+/// Outcome of `acquire`: this process is the only instance, or another
+/// one is running.
+pub enum Instance {
+    Primary(InstanceServer),
+    Secondary(InstanceClient),
+}
+
+/// Lock the instance using the settings file `settings_path` and listen
+/// for forwarded arguments, or connect to the instance holding the lock
+pub fn acquire(settings_path: &Path) -> Result<Instance, InstanceError> {
+    let lock_path = lock_path(settings_path);
+    if let Some(dir) = lock_path.parent() {
+        fs::create_dir_all(dir).map_err(io_error(dir))?;
+    }
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(io_error(&lock_path))?;
+    let socket = socket_path(settings_path);
+    match lock.try_lock() {
+        Ok(()) => InstanceServer::listen(lock, socket).map(Instance::Primary),
+        Err(fs::TryLockError::WouldBlock) => Ok(Instance::Secondary(InstanceClient { socket })),
+        Err(fs::TryLockError::Error(source)) => Err(InstanceError::Io { path: lock_path, source }),
+    }
+}
+
+/// The command-line arguments of this process, with file paths made
+/// absolute so the running instance finds the same files
+pub fn args() -> Vec<String> {
+    let mut args = Vec::new();
+    let mut command_follows = false;
+    for arg in std::env::args().skip(1) {
+        let is_path = !command_follows && !arg.starts_with('-');
+        command_follows = arg == "--command" || arg == "-c";
+        args.push(match std::path::absolute(&arg) {
+            Ok(path) if is_path => path.display().to_string(),
+            _ => arg,
+        });
+    }
+    args
+}
+
+/// Shell commands given by the arguments `args`, see the module
+/// documentation. Unknown options are logged and skipped.
+pub fn commands(args: &[String]) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(key) = arg.strip_prefix("--command=") {
+            commands.push(key.to_string());
+        } else if arg == "--command" || arg == "-c" {
+            match args.next() {
+                Some(key) => commands.push(key.clone()),
+                None => log::warn!("{} needs a command", arg),
+            }
+        } else if arg.starts_with('-') {
+            log::warn!("Unknown option {}", arg);
+        } else {
+            commands.push(format!("open:{}", arg));
+        }
+    }
+    commands
+}
+
+struct ServerInner {
+    received : Vec<Vec<String>>,
+    repaint  : Option<egui::Context>,
+}
+
+/// InstanceServer
+///
+/// Held by the running instance: keeps the lock and collects the
+/// arguments forwarded by later launches. The thread accepting them
+/// is stopped and the socket removed on drop.
+pub struct InstanceServer {
+    _lock  : File,
+    socket : PathBuf,
+    inner  : Arc<Mutex<ServerInner>>,
+    #[cfg(unix)]
+    stop   : Arc<AtomicBool>,
+    #[cfg(unix)]
+    accept : Option<JoinHandle<()>>,
+}
+
+impl InstanceServer {
+    #[cfg(unix)]
+    fn listen(lock: File, socket: PathBuf) -> Result<Self, InstanceError> {
+        // A socket left behind by a crashed instance; the lock is ours
+        let _ = fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket).map_err(io_error(&socket))?;
+        let inner = Arc::new(Mutex::new(ServerInner { received: Vec::new(), repaint: None }));
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (shared, stopped) = (inner.clone(), stop.clone());
+        let accept = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Err(e) = receive(stream, &shared) {
+                    log::warn!("Failed to receive forwarded arguments: {}", e);
+                }
+            }
+        });
+        Ok(Self { _lock: lock, socket, inner, stop, accept: Some(accept) })
+    }
+
+    #[cfg(not(unix))]
+    fn listen(lock: File, socket: PathBuf) -> Result<Self, InstanceError> {
+        let inner = Arc::new(Mutex::new(ServerInner { received: Vec::new(), repaint: None }));
+        Ok(Self { _lock: lock, socket, inner })
+    }
+
+    /// Repaint `ctx` when arguments arrive, so they are handled while
+    /// the window is idle
+    pub fn with_repaint(self, ctx: &egui::Context) -> Self {
+        self.inner.lock().unwrap().repaint = Some(ctx.clone());
+        self
+    }
+
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    /// The argument lists forwarded since the last call, oldest first
+    pub fn take(&self) -> Vec<Vec<String>> {
+        std::mem::take(&mut self.inner.lock().unwrap().received)
+    }
+}
+
+impl Drop for InstanceServer {
+    fn drop(&mut self) {
+        // Wake the accept loop with a connection of our own so it sees
+        // the flag; without one it would block on `accept` for good
+        #[cfg(unix)]
+        if let Some(accept) = self.accept.take() {
+            self.stop.store(true, Ordering::SeqCst);
+            if UnixStream::connect(&self.socket).is_ok() {
+                let _ = accept.join();
+            }
+        }
+        let _ = fs::remove_file(&self.socket);
+    }
+}
+
+/// Read one JSON line of arguments from `stream` and acknowledge it
+/// once they are queued
+#[cfg(unix)]
+fn receive(stream: UnixStream, shared: &Mutex<ServerInner>) -> io::Result<()> {
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let mut writer = &stream;
+    match serde_json::from_str::<Vec<String>>(&line) {
+        Ok(args) => {
+            let mut inner = shared.lock().unwrap();
+            inner.received.push(args);
+            if let Some(repaint) = &inner.repaint {
+                repaint.request_repaint();
+            }
+            drop(inner);
+            writer.write_all(b"ok\n")
+        }
+        Err(e) => {
+            writeln!(writer, "{}", e)?;
+            Err(io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+    }
+}
+
+/// InstanceClient
+///
+/// Handed to a launch that found another instance running.
+pub struct InstanceClient {
+    socket: PathBuf,
+}
+
+impl InstanceClient {
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    /// Send `args` to the running instance and wait until it queued
+    /// them
+    #[cfg(unix)]
+    pub fn forward(&self, args: &[String]) -> Result<(), InstanceError> {
+        // The running instance may have taken the lock but not yet listen
+        let started = std::time::Instant::now();
+        let stream = loop {
+            match UnixStream::connect(&self.socket) {
+                Ok(stream) => break stream,
+                Err(_) if started.elapsed() < CONNECT_TIMEOUT => {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                Err(e) => return Err(InstanceError::Io { path: self.socket.clone(), source: e }),
+            }
+        };
+        let error = io_error(&self.socket);
+        let mut message = serde_json::to_string(args).expect("strings serialize");
+        message.push('\n');
+        let exchange = || -> io::Result<String> {
+            stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+            (&stream).write_all(message.as_bytes())?;
+            let mut reply = String::new();
+            BufReader::new(&stream).read_line(&mut reply)?;
+            Ok(reply)
+        };
+        let reply = exchange().map_err(error)?;
+        match reply.trim_end() {
+            "ok" => Ok(()),
+            reason => Err(InstanceError::Rejected(reason.to_string())),
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn forward(&self, _args: &[String]) -> Result<(), InstanceError> {
+        Err(InstanceError::Unsupported)
+    }
+}
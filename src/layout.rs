@@ -0,0 +1,80 @@
+//! Persisted egui_dock layout
+//!
+//! A `DockState` cannot be stored as is: its tabs usually hold state
+//! that is rebuilt on every launch. `DockLayout` stores the layout with
+//! each tab replaced by a stable string id and maps the ids back to
+//! tabs on restore. Ids that are no longer known are dropped, and if
+//! no tab is left the application falls back to its default layout.
+//!
+//! ```
+//! use egui_dock::DockState;
+//! use egui_mobius_template::layout::DockLayout;
+//!
+//! let dock = DockState::new(vec!["Control", "About"]);
+//! let layout = DockLayout::capture(&dock, |tab| tab.to_string());
+//!
+//! // "About" no longer exists
+//! let restored = layout
+//!     .restore(|id| (id == "Control").then_some("Control"))
+//!     .unwrap_or_else(|| DockState::new(vec!["Control"]));
+//! assert_eq!(restored.iter_all_tabs().count(), 1);
+//! ```
+use crate::settings::SettingsSection;
+use egui_dock::DockState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// DockLayout
+///
+/// Settings section holding the last dock layout, `None` until one has
+/// been captured.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DockLayout {
+    layout: Option<Value>,
+}
+
+impl SettingsSection for DockLayout {
+    const KEY: &'static str = "dock_layout";
+}
+
+impl DockLayout {
+    /// Capture the layout of `dock`, identifying every tab by `id`
+    pub fn capture<Tab>(dock: &DockState<Tab>, id: impl FnMut(&Tab) -> String) -> Self {
+        Self {
+            layout: serde_json::to_value(dock.map_tabs(id)).ok().map(|mut layout| {
+                zero_unset_coordinates(&mut layout);
+                layout
+            }),
+        }
+    }
+
+    /// Rebuild the captured layout, creating every tab from its id.
+    /// Tabs for which `tab` returns `None` are dropped. Returns `None`
+    /// if nothing was captured, the layout cannot be read or no tab is
+    /// left.
+    pub fn restore<Tab>(&self, tab: impl FnMut(&String) -> Option<Tab>) -> Option<DockState<Tab>> {
+        let ids: DockState<String> = serde_json::from_value(self.layout.clone()?).ok()?;
+        let dock = ids.filter_map_tabs(tab);
+        let empty = dock.iter_all_tabs().next().is_none();
+        (!empty).then_some(dock)
+    }
+}
+
+/// Leaf rects are infinite until the dock has been shown and serialize
+/// as `null`, which cannot be read back. They are recomputed on every
+/// frame, so any finite value will do.
+fn zero_unset_coordinates(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if value.is_null() && (key == "x" || key == "y") {
+                    *value = Value::from(0.0);
+                } else {
+                    zero_unset_coordinates(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(zero_unset_coordinates),
+        _ => {}
+    }
+}
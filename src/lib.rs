@@ -3,9 +3,53 @@ use egui::{Color32, RichText};
 use std::sync::Arc;
 use std::collections::VecDeque;
 use egui_mobius_reactive::{Dynamic, Derived, ReactiveValue};
+use transaction::{ReactiveScope, Transaction};
 
+pub mod accessibility;
+pub mod actions;
+pub mod app;
 pub mod colors;
+pub mod diagnostics;
+pub mod history;
+pub mod i18n;
+pub mod instance;
+pub mod layout;
+pub mod logging;
+pub mod notifications;
+pub mod palette;
+pub mod panel;
+pub mod persistence;
+pub mod recorder;
+pub mod scaffold;
+pub mod session;
+pub mod settings;
+pub mod shortcuts;
+pub mod status;
+pub mod tabs;
+pub mod testing;
+pub mod themes;
+pub mod transaction;
+pub mod watcher;
+pub use actions::{Action, ActionRegistry, Menu};
+pub use app::MobiusApp;
 pub use colors::LogColors;
+pub use history::History;
+pub use layout::DockLayout;
+pub use logging::Latch;
+pub use notifications::{Notification, Notifier};
+pub use palette::CommandPalette;
+pub use panel::{AppContext, Panel, PanelRegistry, PanelTab, Resources, Services};
+pub use persistence::{PersistError, PersistenceService};
+pub use recorder::{Recorder, Recording};
+pub use settings::{SettingsSection, SettingsStore, WindowSettings};
+pub use shortcuts::ShortcutManager;
+pub use status::{StatusBar, StatusItem};
+pub use themes::{Theme, ThemeManager};
+pub use watcher::FileWatcher;
+pub use egui_mobius_template_derive::ReactiveState;
+
+/// Re-export of `egui_mobius_reactive`, used by `#[derive(ReactiveState)]`
+pub use egui_mobius_reactive as reactive;
 
 /// LogType
 ///
@@ -33,7 +77,10 @@ pub use colors::LogColors;
 /// custom events.
 ///
 /// The RunStop variant is used for logs generated by the
-/// run/stop widget.    
+/// run/stop widget.
+///
+/// The Error variant is used for failures reported by the
+/// application itself, e.g. when saving the configuration fails.
 /// 
 #[derive(Clone, Copy, PartialEq)]
 pub enum LogType {
@@ -48,6 +95,7 @@ pub enum LogType {
     Timestamp,
     Primary,
     Secondary,
+    Error,
 }
 /// Debug for LogType
 /// 
@@ -67,6 +115,7 @@ impl std::fmt::Debug for LogType {
             LogType::RunStop => write!(f, "Run/Stop"),
             LogType::Default => write!(f, "Default"),
             LogType::Timestamp => write!(f, "Timestamp"),
+            LogType::Error => write!(f, "Error"),
         }
     }
 }
@@ -74,11 +123,15 @@ impl std::fmt::Debug for LogType {
 
 pub const MAX_LOGS: usize = 1000; // Maximum number of logs to keep in memory
 
+/// Color used for `LogType::Error` entries, independent of the theme
+pub const ERROR_COLOR: Color32 = Color32::from_rgb(255, 90, 90);
+
 #[derive(Clone)]
 pub struct TerminalWidget {
     pub logs          : Dynamic<VecDeque<(String, LogType)>>,
     pub colors        : Dynamic<LogColors>,
     pub rich_text     : Derived<Vec<RichText>>,
+    pub scope         : ReactiveScope,
     pub repaint       : egui::Context,
 }
 
@@ -101,13 +154,12 @@ impl TerminalWidget {
         let logs = Dynamic::new(VecDeque::with_capacity(MAX_LOGS));
         let colors = Dynamic::new(colors);
 
-        // Create derived rich text from logs and colors
+        // Create derived rich text from logs and colors. It depends on
+        // their scope, so a transaction changing both rebuilds it once
+        let scope = ReactiveScope::new().with(&logs).with(&colors);
         let logs_clone = logs.clone();
         let colors_clone = colors.clone();
-        let deps = [
-            Arc::new(logs.clone()) as Arc<dyn ReactiveValue>,
-            Arc::new(colors.clone()) as Arc<dyn ReactiveValue>
-        ];
+        let deps = [Arc::new(scope.clone()) as Arc<dyn ReactiveValue>];
         let rich_text = Derived::new(
             &deps,
             move || {
@@ -128,6 +180,7 @@ impl TerminalWidget {
                             LogType::Default => Color32::WHITE,
                             LogType::Primary => colors.clock,  // Use clock color for primary events
                             LogType::Secondary => colors.custom_event,  // Use custom_event color for secondary events
+                            LogType::Error => ERROR_COLOR,
                         };
                         RichText::new(msg).color(color)
                     })  
@@ -138,6 +191,7 @@ impl TerminalWidget {
             logs,
             colors,
             rich_text,
+            scope,
             repaint,
 
         }
@@ -152,4 +206,30 @@ impl TerminalWidget {
         // Force a repaint to show the changes
         self.repaint.request_repaint();
     }
+
+    /// Write the logs, oldest first, as text to `path`, one
+    /// `[type] message` line per entry
+    pub fn export_logs(&self, path: &std::path::Path) -> Result<(), PersistError> {
+        persistence::write_atomic(path, self.logs_text().as_bytes())
+    }
+
+    /// The logs, oldest first, as `[type] message` lines
+    pub fn logs_text(&self) -> String {
+        self.logs.get()
+            .iter()
+            .map(|(msg, log_type)| format!("[{:?}] {}\n", log_type, msg))
+            .collect()
+    }
+
+    /// Change logs and colors together: the staged writes are applied
+    /// if `f` returns `Ok`, rebuilding the rich text and repainting once
+    pub fn transaction<R, E, F>(&self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Transaction) -> Result<R, E>,
+    {
+        self.scope.transaction(|tx| {
+            tx.request_repaint(&self.repaint);
+            f(tx)
+        })
+    }
 }
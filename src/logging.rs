@@ -0,0 +1,232 @@
+//! Logging macros for the terminal widget
+//!
+//! These macros simplify common logging patterns using the `Latch`
+//! pattern: the logs of a `TerminalWidget` are taken out of their
+//! `Dynamic`, modified, and written back (notifying subscribers once)
+//! when the latch goes out of scope.
+//!
+//! All macros are exported at the crate root and only use `$crate`
+//! paths, so applications need nothing but the macro itself in scope:
+//!
+//! ```
+//! use egui_mobius_template::{terminal_log, LogColors, LogType, TerminalWidget};
+//!
+//! let mut terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+//! terminal_log!(terminal, "Started");
+//! terminal_log!(terminal, "Slider at {:.1}", 42.0);
+//! terminal_log!(terminal, type: LogType::Error, "Failed after {} retries", 3);
+//! assert_eq!(terminal.logs.get().len(), 3);
+//! ```
+use crate::MAX_LOGS;
+use egui_mobius_reactive::Dynamic;
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+
+/// Current local date and time in the format of the current language,
+/// as appended by `set_timestamp_log!`
+pub fn timestamp() -> String {
+    crate::i18n::format_datetime(&chrono::Local::now())
+}
+
+/// Latch
+///
+/// A guard that takes a copy of a `Dynamic` value, derefs to it, and
+/// sets it back when dropped, so a batch of changes notifies
+/// subscribers only once.
+///
+/// ```
+/// use egui_mobius_reactive::Dynamic;
+/// use egui_mobius_template::Latch;
+///
+/// let counter = Dynamic::new(1);
+/// {
+///     let mut latch = Latch::new(&counter);
+///     *latch += 1;
+/// }
+/// assert_eq!(counter.get(), 2);
+/// ```
+pub struct Latch<'a, T: Send + Clone + 'static> {
+    dynamic : &'a Dynamic<T>,
+    value   : T,
+}
+
+impl<'a, T: Clone + Send + 'static> Latch<'a, T> {
+    /// Create a new Latch for the given Dynamic value
+    pub fn new(dynamic: &'a Dynamic<T>) -> Self {
+        Self {
+            dynamic,
+            value: dynamic.get(),
+        }
+    }
+
+    /// Push to back of VecDeque with circular buffer behavior, keeping
+    /// at most `MAX_LOGS` entries
+    pub fn push_back<U>(&mut self, item: U)
+    where
+        Self: DerefMut<Target = VecDeque<U>>,
+        U: Clone,
+    {
+        if self.len() >= MAX_LOGS {
+            self.pop_front();
+        }
+        self.deref_mut().push_back(item);
+    }
+}
+
+impl<T: Clone + Send + 'static> Drop for Latch<'_, T> {
+    fn drop(&mut self) {
+        self.dynamic.set(self.value.clone());
+    }
+}
+
+impl<T: Clone + Send> Deref for Latch<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: Clone + Send> DerefMut for Latch<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+/// Core macro that pushes a `(String, LogType)` entry to the widget's logs
+///
+/// ```
+/// # use egui_mobius_template::{log_to_terminal, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// log_to_terminal!(terminal, ("Hello".to_string(), LogType::Default));
+/// ```
+#[macro_export]
+macro_rules! log_to_terminal {
+    ($widget:expr, $log_entry:expr) => {{
+        let mut latch = $crate::Latch::new(&$widget.logs);
+        latch.push_back($log_entry);
+    }};
+}
+
+/// Macro for logging a message followed by the current timestamp
+///
+/// The log type defaults to `LogType::Default`.
+///
+/// ```
+/// # use egui_mobius_template::{set_timestamp_log, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// set_timestamp_log!(terminal, "System info");
+/// set_timestamp_log!(terminal, "System info", LogType::Timestamp);
+/// ```
+#[macro_export]
+macro_rules! set_timestamp_log {
+    ($widget:expr, $message:expr) => {
+        $crate::set_timestamp_log!($widget, $message, $crate::LogType::Default)
+    };
+
+    ($widget:expr, $message:expr, $log_type:expr) => {{
+        let log_string = format!("{} : {}", $message, $crate::logging::timestamp());
+        $crate::log_to_terminal!($widget, (log_string, $log_type));
+    }};
+}
+
+/// Macro for logging slider value changes
+///
+/// # Arguments
+///
+/// * `$widget` - The terminal widget to log to
+/// * `$message` - The message, usually containing the slider value
+/// * `$log_type` - (Optional) The type of log entry to create. Defaults to `LogType::Slider`
+///
+/// ```
+/// # use egui_mobius_template::{set_slider_log, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// let slider_value = 32.0; // would normally come from the ui code
+/// set_slider_log!(terminal, format!("Slider {:.1}", slider_value));
+/// set_slider_log!(terminal, format!("Slider {:.1}", slider_value), LogType::CustomEvent);
+/// ```
+#[macro_export]
+macro_rules! set_slider_log {
+    ($widget:expr, $message:expr) => {
+        $crate::set_slider_log!($widget, $message, $crate::LogType::Slider)
+    };
+
+    ($widget:expr, $message:expr, $log_type:expr) => {
+        $crate::log_to_terminal!($widget, (($message).to_string(), $log_type))
+    };
+}
+
+/// Macro for logging combo/dropdown selections as `Selected: <label>`
+///
+/// ```
+/// # use egui_mobius_template::{set_combo_log, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// set_combo_log!(terminal, "Option A", LogType::OptionA);
+/// ```
+#[macro_export]
+macro_rules! set_combo_log {
+    ($widget:expr, $label:expr, $log_type:expr) => {{
+        let log_string = format!("Selected: {}", $label);
+        $crate::log_to_terminal!($widget, (log_string, $log_type));
+    }};
+}
+
+/// Generic macro for any kind of log entry
+///
+/// A string literal is treated as a format string, like `format!`; any
+/// other expression is logged through `ToString`. The log type defaults
+/// to `LogType::Default` and is chosen with a leading `type:`.
+///
+/// ```
+/// # use egui_mobius_template::{terminal_log, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// let name = String::from("sensor");
+/// terminal_log!(terminal, "Plain message");
+/// terminal_log!(terminal, "Read {} values from {name}", 3);
+/// terminal_log!(terminal, name.clone());
+/// terminal_log!(terminal, type: LogType::Error, "Lost {}", name);
+/// terminal_log!(terminal, type: LogType::Error, name);
+/// ```
+#[macro_export]
+macro_rules! terminal_log {
+    // Format string with custom log type
+    ($widget:expr, type: $log_type:expr, $format:literal $(, $arg:expr)* $(,)?) => {
+        $crate::log_to_terminal!($widget, (format!($format $(, $arg)*), $log_type))
+    };
+
+    // Message with custom log type
+    ($widget:expr, type: $log_type:expr, $message:expr $(,)?) => {
+        $crate::log_to_terminal!($widget, (($message).to_string(), $log_type))
+    };
+
+    // Format string with default log type
+    ($widget:expr, $format:literal $(, $arg:expr)* $(,)?) => {
+        $crate::terminal_log!($widget, type: $crate::LogType::Default, $format $(, $arg)*)
+    };
+
+    // Message with default log type
+    ($widget:expr, $message:expr $(,)?) => {
+        $crate::terminal_log!($widget, type: $crate::LogType::Default, $message)
+    };
+}
+
+/// With-style macro that provides a block for manipulating logs using a Latch
+///
+/// The logs are written back once, when the block ends.
+///
+/// ```
+/// # use egui_mobius_template::{with_terminal_logs, LogColors, LogType, TerminalWidget};
+/// # let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+/// with_terminal_logs!(terminal, |logs| {
+///     logs.clear();
+///     logs.push_back(("Cleared".to_string(), LogType::Default));
+/// });
+/// ```
+#[macro_export]
+macro_rules! with_terminal_logs {
+    ($widget:expr, |$logs:ident| $block:block) => {{
+        #[allow(unused_mut)]
+        let mut $logs = $crate::Latch::new(&$widget.logs);
+        $block
+    }};
+}
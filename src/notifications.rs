@@ -0,0 +1,439 @@
+//! Toast notifications
+//!
+//! Events the user should not miss - a failed save, a simulation error,
+//! a background task that finished - are shown as toasts in the bottom
+//! right corner of the window, whichever panels are open. A toast has a
+//! severity, disappears after a timeout that is paused while the pointer
+//! is over it, and may offer buttons running commands of the shell such
+//! as `"panel:plot"`. Every notification is also kept in a history shown
+//! by the `NotificationsPanel`.
+//!
+//! `Notifier` is a cheap to clone, thread-safe handle, so background
+//! tasks and slot threads notify directly:
+//!
+//! ```
+//! use egui_mobius_template::notifications::{Notification, Notifier, Severity};
+//!
+//! let notifier = Notifier::new();
+//! let background = notifier.clone();
+//! std::thread::spawn(move || {
+//!     background.notify(Notification::error("Simulation failed")
+//!         .body("Time step must be positive")
+//!         .action("Show parameters", "panel:parameters"));
+//! })
+//! .join()
+//! .unwrap();
+//!
+//! assert_eq!(notifier.toasts()[0].severity, Severity::Error);
+//! assert_eq!(notifier.history().len(), 1);
+//! ```
+use crate::i18n;
+use crate::panel::{AppContext, Panel};
+use crate::tr;
+use eframe::egui;
+use egui::Color32;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Number of notifications kept in the history
+pub const MAX_HISTORY: usize = 100;
+
+/// Number of toasts shown at once, the newest ones
+pub const MAX_TOASTS: usize = 5;
+
+/// Severity
+///
+/// How important a notification is, which decides its icon, color and
+/// default timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn icon(self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ",
+            Severity::Success => "✔",
+            Severity::Warning => "⚠",
+            Severity::Error => "❌",
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Severity::Info => Color32::from_rgb(100, 170, 255),
+            Severity::Success => Color32::from_rgb(90, 200, 120),
+            Severity::Warning => Color32::from_rgb(240, 190, 60),
+            Severity::Error => crate::ERROR_COLOR,
+        }
+    }
+
+    /// How long a toast of this severity stays; errors stay until
+    /// they are dismissed
+    pub fn default_timeout(self) -> Option<Duration> {
+        match self {
+            Severity::Info | Severity::Success => Some(Duration::from_secs(4)),
+            Severity::Warning => Some(Duration::from_secs(8)),
+            Severity::Error => None,
+        }
+    }
+}
+
+/// NotificationAction
+///
+/// Button of a notification running a command of the shell, e.g.
+/// `"panel:logger"` or `"action:simulation.run"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationAction {
+    pub label   : String,
+    pub command : String,
+}
+
+/// Notification
+///
+/// One message, shown as a toast and kept in the history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub severity : Severity,
+    pub title    : String,
+    pub body     : Option<String>,
+    pub actions  : Vec<NotificationAction>,
+    /// How long the toast stays, `None` until it is dismissed
+    pub timeout  : Option<Duration>,
+    pub time     : chrono::DateTime<chrono::Local>,
+}
+
+impl Notification {
+    pub fn new(severity: Severity, title: impl Into<String>) -> Self {
+        Self {
+            severity,
+            title: title.into(),
+            body: None,
+            actions: Vec::new(),
+            timeout: severity.default_timeout(),
+            time: chrono::Local::now(),
+        }
+    }
+
+    pub fn info(title: impl Into<String>) -> Self {
+        Self::new(Severity::Info, title)
+    }
+
+    pub fn success(title: impl Into<String>) -> Self {
+        Self::new(Severity::Success, title)
+    }
+
+    pub fn warning(title: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, title)
+    }
+
+    pub fn error(title: impl Into<String>) -> Self {
+        Self::new(Severity::Error, title)
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Add a button running `command` and dismissing the toast
+    pub fn action(mut self, label: impl Into<String>, command: impl Into<String>) -> Self {
+        self.actions.push(NotificationAction { label: label.into(), command: command.into() });
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Keep the toast until it is dismissed
+    pub fn sticky(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+}
+
+struct Toast {
+    id           : u64,
+    notification : Notification,
+    /// Set when the toast is first shown, pushed back while hovered
+    deadline     : Option<Instant>,
+}
+
+struct NotifierInner {
+    toasts   : Vec<Toast>,
+    history  : VecDeque<Notification>,
+    commands : Vec<String>,
+    next_id  : u64,
+    repaint  : Option<egui::Context>,
+}
+
+/// Notifier
+///
+/// Toasts, history and requested commands shared by all clones.
+#[derive(Clone)]
+pub struct Notifier {
+    inner: Arc<Mutex<NotifierInner>>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(NotifierInner {
+                toasts: Vec::new(),
+                history: VecDeque::new(),
+                commands: Vec::new(),
+                next_id: 0,
+                repaint: None,
+            })),
+        }
+    }
+
+    /// Request a repaint of `ctx` whenever a notification arrives, so
+    /// toasts sent from other threads show up immediately
+    pub fn with_repaint(self, ctx: &egui::Context) -> Self {
+        self.inner.lock().unwrap().repaint = Some(ctx.clone());
+        self
+    }
+
+    /// Show `notification` as a toast and add it to the history.
+    /// Returns the id of the toast, for `dismiss`. A notification equal
+    /// to a toast still shown, e.g. a save that keeps failing, restarts
+    /// the timer of that toast instead.
+    pub fn notify(&self, notification: Notification) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        let same = |toast: &&mut Toast| {
+            toast.notification.severity == notification.severity
+                && toast.notification.title == notification.title
+                && toast.notification.body == notification.body
+        };
+        if let Some(toast) = inner.toasts.iter_mut().find(same) {
+            toast.deadline = None;
+            return toast.id;
+        }
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.history.push_front(notification.clone());
+        inner.history.truncate(MAX_HISTORY);
+        inner.toasts.push(Toast { id, notification, deadline: None });
+        if let Some(ctx) = &inner.repaint {
+            ctx.request_repaint();
+        }
+        id
+    }
+
+    pub fn info(&self, title: impl Into<String>) -> u64 {
+        self.notify(Notification::info(title))
+    }
+
+    pub fn success(&self, title: impl Into<String>) -> u64 {
+        self.notify(Notification::success(title))
+    }
+
+    pub fn warning(&self, title: impl Into<String>) -> u64 {
+        self.notify(Notification::warning(title))
+    }
+
+    pub fn error(&self, title: impl Into<String>) -> u64 {
+        self.notify(Notification::error(title))
+    }
+
+    /// Remove the toast `id`; it stays in the history
+    pub fn dismiss(&self, id: u64) {
+        self.inner.lock().unwrap().toasts.retain(|toast| toast.id != id);
+    }
+
+    pub fn dismiss_all(&self) {
+        self.inner.lock().unwrap().toasts.clear();
+    }
+
+    /// Notifications currently shown as toasts, oldest first
+    pub fn toasts(&self) -> Vec<Notification> {
+        self.inner.lock().unwrap().toasts.iter().map(|toast| toast.notification.clone()).collect()
+    }
+
+    /// Notifications received so far, newest first
+    pub fn history(&self) -> Vec<Notification> {
+        self.inner.lock().unwrap().history.iter().cloned().collect()
+    }
+
+    pub fn clear_history(&self) {
+        self.inner.lock().unwrap().history.clear();
+    }
+
+    /// Ask the shell to run `command`, as the buttons of notifications do
+    pub fn request_command(&self, command: impl Into<String>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.commands.push(command.into());
+        if let Some(ctx) = &inner.repaint {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Commands requested since the last call, in request order
+    pub fn take_commands(&self) -> Vec<String> {
+        std::mem::take(&mut self.inner.lock().unwrap().commands)
+    }
+
+    /// Show the newest toasts in the bottom right corner and remove
+    /// those whose timeout has passed
+    pub fn show(&self, ctx: &egui::Context) {
+        let now = Instant::now();
+        let toasts: Vec<(u64, Notification, Option<Instant>)> = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.toasts.retain(|toast| toast.deadline.is_none_or(|deadline| deadline > now));
+            for toast in &mut inner.toasts {
+                if toast.deadline.is_none() {
+                    toast.deadline = toast.notification.timeout.map(|timeout| now + timeout);
+                }
+            }
+            let skip = inner.toasts.len().saturating_sub(MAX_TOASTS);
+            inner.toasts.iter()
+                .skip(skip)
+                .map(|toast| (toast.id, toast.notification.clone(), toast.deadline))
+                .collect()
+        };
+        if toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = Vec::new();
+        let mut hovered = Vec::new();
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_width(320.0);
+                for (id, notification, _) in &toasts {
+                    let frame = egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, notification.severity.color()))
+                        .show(ui, |ui| {
+                            if self.toast_ui(ui, notification) {
+                                dismissed.push(*id);
+                            }
+                        });
+                    if frame.response.contains_pointer() {
+                        hovered.push(*id);
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.toasts.retain(|toast| !dismissed.contains(&toast.id));
+        for toast in &mut inner.toasts {
+            if hovered.contains(&toast.id) {
+                toast.deadline = toast.notification.timeout.map(|timeout| now + timeout);
+            }
+        }
+        // Wake up for the next toast to expire
+        if let Some(next) = inner.toasts.iter().filter_map(|toast| toast.deadline).min() {
+            ctx.request_repaint_after(next.saturating_duration_since(now));
+        }
+    }
+
+    /// Content of one toast. Returns true if it was closed or one of its
+    /// actions was chosen.
+    fn toast_ui(&self, ui: &mut egui::Ui, notification: &Notification) -> bool {
+        let mut close = false;
+        ui.horizontal(|ui| {
+            ui.colored_label(notification.severity.color(), notification.severity.icon());
+            ui.strong(&notification.title);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                close = ui.small_button("✕").on_hover_text(tr!("notifications-dismiss")).clicked();
+            });
+        });
+        if let Some(body) = &notification.body {
+            ui.label(body);
+        }
+        if !notification.actions.is_empty() {
+            ui.horizontal(|ui| {
+                for action in &notification.actions {
+                    if ui.button(&action.label).clicked() {
+                        self.request_command(action.command.clone());
+                        close = true;
+                    }
+                }
+            });
+        }
+        close
+    }
+
+    /// The history, newest first, with the time and actions of each
+    /// notification
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        let history = self.history();
+        ui.horizontal(|ui| {
+            ui.label(tr!("notifications-count", count = history.len()));
+            if ui.add_enabled(!history.is_empty(), egui::Button::new(tr!("button-clear"))).clicked() {
+                self.clear_history();
+            }
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("notification_history")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if history.is_empty() {
+                    ui.weak(tr!("notifications-empty"));
+                }
+                for notification in &history {
+                    ui.horizontal(|ui| {
+                        ui.weak(i18n::format_time(&notification.time));
+                        ui.colored_label(notification.severity.color(), notification.severity.icon());
+                        ui.strong(&notification.title);
+                    });
+                    if let Some(body) = &notification.body {
+                        ui.label(body);
+                    }
+                    if !notification.actions.is_empty() {
+                        ui.horizontal(|ui| {
+                            for action in &notification.actions {
+                                if ui.small_button(&action.label).clicked() {
+                                    self.request_command(action.command.clone());
+                                }
+                            }
+                        });
+                    }
+                    ui.separator();
+                }
+            });
+    }
+}
+
+/// NotificationsPanel
+///
+/// Dock panel showing `Notifier::ui` for the notifier of the app context.
+#[derive(Default)]
+pub struct NotificationsPanel;
+
+impl Panel for NotificationsPanel {
+    fn id(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-notifications")
+    }
+
+    fn icon(&self) -> &'static str {
+        "🔔"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        ctx.notifier.ui(ui);
+    }
+}
@@ -0,0 +1,260 @@
+//! Command palette
+//!
+//! Ctrl+Shift+P (Cmd+Shift+P on macOS) opens a searchable list of every
+//! command of the application: opening panels, registered actions and
+//! switching themes; the shortcut can be rebound like any other. Typing
+//! filters the list with a fuzzy match, so `clog` finds "Logger: Clear
+//! logger"; the arrow keys select, Enter runs and Escape closes.
+//! Commands run recently are listed first and remembered across
+//! sessions.
+//!
+//! ```
+//! use egui_mobius_template::palette::{fuzzy_score, search, PaletteEntry, RecentCommands};
+//!
+//! assert!(fuzzy_score("clog", "Logger: Clear logger").is_some());
+//! assert!(fuzzy_score("xyz", "Logger: Clear logger").is_none());
+//!
+//! let entries = [
+//!     PaletteEntry::new("panel:logger", "Open: Logger"),
+//!     PaletteEntry::new("action:logger.clear", "Logger: Clear logger"),
+//! ];
+//! let found = search("clear", &RecentCommands::default(), &entries);
+//! assert_eq!(found[0].key, "action:logger.clear");
+//! ```
+use crate::i18n;
+use crate::settings::SettingsSection;
+use eframe::egui;
+use egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+/// Default shortcut opening and closing the palette
+pub const SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::P);
+
+/// Number of recently run commands remembered
+pub const MAX_RECENT: usize = 5;
+
+/// Score of `text` for the fuzzy `query`, higher is better. `None` if
+/// the characters of the query do not all appear in `text` in order.
+/// Matches at the start of a word and runs of consecutive characters
+/// score higher; case and whitespace in the query are ignored.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous = None;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let index = (next..text.len()).find(|&i| same_letter(text[i], wanted))?;
+        score += 1;
+        if index > 0 && previous == Some(index - 1) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        previous = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
+
+fn same_letter(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// PaletteEntry
+///
+/// One command as listed in the palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    /// Identifies the command to whoever shows the palette
+    pub key      : String,
+    pub title    : String,
+    /// Shortcut shown next to the title, already formatted
+    pub shortcut : Option<String>,
+}
+
+impl PaletteEntry {
+    pub fn new(key: impl Into<String>, title: impl Into<String>) -> Self {
+        Self { key: key.into(), title: title.into(), shortcut: None }
+    }
+
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+}
+
+/// RecentCommands
+///
+/// Keys of the commands run last, most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentCommands {
+    pub keys: Vec<String>,
+}
+
+impl RecentCommands {
+    /// Move `key` to the front, forgetting the oldest beyond `MAX_RECENT`
+    pub fn push(&mut self, key: &str) {
+        self.keys.retain(|known| known != key);
+        self.keys.insert(0, key.to_string());
+        self.keys.truncate(MAX_RECENT);
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.iter().any(|known| known == key)
+    }
+}
+
+impl SettingsSection for RecentCommands {
+    const KEY: &'static str = "recent_commands";
+}
+
+/// Entries matching `query`, best match first. Without a query the
+/// recent entries come first, followed by all others in their order.
+pub fn search<'a>(query: &str, recent: &RecentCommands,
+                  entries: &'a [PaletteEntry]) -> Vec<&'a PaletteEntry> {
+    if query.trim().is_empty() {
+        let mut found: Vec<_> = recent.keys.iter()
+            .filter_map(|key| entries.iter().find(|entry| &entry.key == key))
+            .collect();
+        found.extend(entries.iter().filter(|entry| !recent.contains(&entry.key)));
+        return found;
+    }
+    let mut scored: Vec<_> = entries.iter()
+        .filter_map(|entry| Some((fuzzy_score(query, &entry.title)?, entry)))
+        .collect();
+    // Stable sort keeps the given order among equal scores
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// CommandPalette
+///
+/// State of the palette window. The owner lists the commands each frame
+/// and runs the one `show` returns.
+#[derive(Default)]
+pub struct CommandPalette {
+    open     : bool,
+    query    : String,
+    selected : usize,
+    recent   : RecentCommands,
+}
+
+impl CommandPalette {
+    pub fn new(recent: RecentCommands) -> Self {
+        Self { recent, ..Self::default() }
+    }
+
+    pub fn recent(&self) -> &RecentCommands {
+        &self.recent
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Open with an empty query
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn toggle(&mut self) {
+        if self.open {
+            self.close();
+        } else {
+            self.open();
+        }
+    }
+
+    /// Show the palette if it is open. Returns the key of the command
+    /// the user chose, which is then remembered as recent.
+    pub fn show(&mut self, ctx: &egui::Context, entries: &[PaletteEntry]) -> Option<String> {
+        if !self.open {
+            return None;
+        }
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+            i.consume_key(Modifiers::NONE, Key::Enter),
+            i.consume_key(Modifiers::NONE, Key::Escape),
+        ));
+        if escape {
+            self.close();
+            return None;
+        }
+
+        let results = search(&self.query, &self.recent, entries);
+        if down {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(results.len().saturating_sub(1));
+
+        let mut chosen = if enter { results.get(self.selected).map(|entry| entry.key.clone()) } else { None };
+        let recent_count = if self.query.trim().is_empty() {
+            results.iter().take_while(|entry| self.recent.contains(&entry.key)).count()
+        } else {
+            0
+        };
+
+        egui::Window::new(i18n::tr("palette-title"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 48.0])
+            .show(ctx, |ui| {
+                ui.set_width(420.0);
+                let edit = ui.add(egui::TextEdit::singleline(&mut self.query)
+                    .hint_text(i18n::tr("palette-hint"))
+                    .desired_width(f32::INFINITY));
+                edit.request_focus();
+                if edit.changed() {
+                    self.selected = 0;
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_salt("palette_entries")
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        if results.is_empty() {
+                            ui.weak(i18n::tr("palette-no-match"));
+                        }
+                        for (index, entry) in results.iter().enumerate() {
+                            if recent_count > 0 && index == 0 {
+                                ui.weak(i18n::tr("palette-recent"));
+                            }
+                            if recent_count > 0 && index == recent_count {
+                                ui.separator();
+                            }
+                            let button = egui::Button::selectable(index == self.selected, entry.title.as_str())
+                                .shortcut_text(entry.shortcut.as_deref().unwrap_or(""))
+                                .min_size(egui::vec2(ui.available_width(), 0.0));
+                            let response = ui.add(button);
+                            if index == self.selected && (up || down) {
+                                response.scroll_to_me(None);
+                            }
+                            if response.clicked() {
+                                chosen = Some(entry.key.clone());
+                            }
+                        }
+                    });
+            });
+
+        if let Some(key) = &chosen {
+            self.recent.push(key);
+            self.close();
+        }
+        chosen
+    }
+}
@@ -0,0 +1,325 @@
+//! Dock panels and the registry they are created from
+//!
+//! A panel is a self-contained module implementing `Panel`. It reaches
+//! the shell's `Services` and the application's own state through an
+//! `AppContext`, so adding a panel means writing the module and
+//! registering it:
+//!
+//! ```
+//! use eframe::egui;
+//! use egui_mobius_template::panel::{AppContext, Panel, PanelRegistry};
+//!
+//! #[derive(Default)]
+//! struct Counter {
+//!     clicks: usize,
+//! }
+//!
+//! impl Panel for Counter {
+//!     fn id(&self) -> &'static str {
+//!         "counter"
+//!     }
+//!
+//!     fn title(&self) -> String {
+//!         format!("Counter ({})", self.clicks)
+//!     }
+//!
+//!     fn ui(&mut self, ui: &mut egui::Ui, _ctx: &mut AppContext) {
+//!         if ui.button("Click").clicked() {
+//!             self.clicks += 1;
+//!         }
+//!     }
+//! }
+//!
+//! let panels = PanelRegistry::new().with::<Counter>();
+//! let tab = panels.create("counter").unwrap();
+//! assert_eq!(tab.title(), "Counter (0)");
+//! ```
+//!
+//! Every tab holds its own panel instance, created by the registry.
+//! Panels identify themselves by a stable id, which is what
+//! `DockLayout` persists.
+use crate::shortcuts::ShortcutManager;
+use crate::{History, Notifier, Recorder, SettingsStore, StatusBar, TerminalWidget, ThemeManager};
+use eframe::egui;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Panel
+///
+/// Content of a dock tab.
+pub trait Panel {
+    /// Stable id, used to persist the dock layout and to open the panel
+    fn id(&self) -> &'static str;
+
+    /// Title shown on the tab and in menus
+    fn title(&self) -> String;
+
+    /// Icon shown in front of the title, e.g. an emoji
+    fn icon(&self) -> &'static str {
+        ""
+    }
+
+    /// Whether more than one tab of this panel may be open. Panels that
+    /// edit application state should exist once.
+    fn allows_duplicates(&self) -> bool {
+        false
+    }
+
+    /// Default shortcut showing the panel, the user may rebind it
+    fn shortcut(&self) -> Option<egui::KeyboardShortcut> {
+        None
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext);
+}
+
+/// Resources
+///
+/// Application-specific state shared by panels, one value per type.
+/// Wrap values in a newtype or struct when a plain type such as
+/// `Dynamic<f32>` would be ambiguous.
+#[derive(Default)]
+pub struct Resources {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `value`, replacing an earlier value of the same type
+    pub fn with<T: 'static>(mut self, value: T) -> Self {
+        self.insert(value);
+        self
+    }
+
+    /// Add `value`, returning the earlier value of the same type
+    pub fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+}
+
+/// Services
+///
+/// The services the shell owns and lends to panels through an
+/// `AppContext`. Only the settings and themes need a place on disk;
+/// `new` gives every other service its default, and struct update
+/// syntax replaces the ones that need more setup:
+///
+/// ```ignore
+/// let services = Services { recorder, ..Services::new(settings, themes) };
+/// ```
+pub struct Services {
+    pub history   : History,
+    pub notifier  : Notifier,
+    pub recorder  : Recorder,
+    pub status    : StatusBar,
+    pub settings  : SettingsStore,
+    pub themes    : ThemeManager,
+    pub shortcuts : ShortcutManager,
+    pub resources : Resources,
+}
+
+impl Services {
+    pub fn new(settings: SettingsStore, themes: ThemeManager) -> Self {
+        Self {
+            history: History::new(),
+            notifier: Notifier::new(),
+            recorder: Recorder::new(),
+            status: StatusBar::new(),
+            settings,
+            themes,
+            shortcuts: ShortcutManager::new(),
+            resources: Resources::new(),
+        }
+    }
+}
+
+/// AppContext
+///
+/// Services handed to a panel while it draws.
+pub struct AppContext<'a> {
+    pub terminal  : &'a mut TerminalWidget,
+    pub history   : &'a History,
+    pub notifier  : &'a Notifier,
+    pub recorder  : &'a Recorder,
+    pub status    : &'a StatusBar,
+    pub settings  : &'a SettingsStore,
+    pub themes    : &'a mut ThemeManager,
+    pub shortcuts : &'a mut ShortcutManager,
+    pub resources : &'a mut Resources,
+}
+
+impl<'a> AppContext<'a> {
+    /// Lend `services` and the `terminal`, which the shell shares with
+    /// its runtime and keeps apart from them
+    pub fn new(terminal: &'a mut TerminalWidget, services: &'a mut Services) -> Self {
+        Self {
+            terminal,
+            history: &services.history,
+            notifier: &services.notifier,
+            recorder: &services.recorder,
+            status: &services.status,
+            settings: &services.settings,
+            themes: &mut services.themes,
+            shortcuts: &mut services.shortcuts,
+            resources: &mut services.resources,
+        }
+    }
+}
+
+/// PanelInfo
+///
+/// What the registry knows about a panel without opening it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelInfo {
+    pub id                : &'static str,
+    pub title             : String,
+    pub icon              : &'static str,
+    pub allows_duplicates : bool,
+    pub shortcut          : Option<egui::KeyboardShortcut>,
+}
+
+impl PanelInfo {
+    /// Icon and title, as shown in menus
+    pub fn label(&self) -> String {
+        label(self.icon, &self.title)
+    }
+}
+
+fn label(icon: &str, title: &str) -> String {
+    if icon.is_empty() {
+        title.to_string()
+    } else {
+        format!("{} {}", icon, title)
+    }
+}
+
+type Factory = Box<dyn Fn() -> Box<dyn Panel>>;
+
+/// A registered panel: its info, the panel the info was read from, kept
+/// to read the title again, and the factory of new instances
+struct Entry {
+    info    : PanelInfo,
+    sample  : Box<dyn Panel>,
+    factory : Factory,
+}
+
+/// PanelRegistry
+///
+/// Every panel an application can show, in registration order.
+#[derive(Default)]
+pub struct PanelRegistry {
+    entries: Vec<Entry>,
+}
+
+impl PanelRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `P`, created with `P::default()`
+    pub fn with<P: Panel + Default + 'static>(mut self) -> Self {
+        self.register(P::default);
+        self
+    }
+
+    /// Register a panel created by `factory`. A panel with the id of an
+    /// earlier one replaces it.
+    pub fn register<P, F>(&mut self, factory: F)
+    where
+        P: Panel + 'static,
+        F: Fn() -> P + 'static,
+    {
+        let sample = factory();
+        let info = PanelInfo {
+            id: sample.id(),
+            title: sample.title(),
+            icon: sample.icon(),
+            allows_duplicates: sample.allows_duplicates(),
+            shortcut: sample.shortcut(),
+        };
+        let entry = Entry { info, sample: Box::new(sample), factory: Box::new(move || Box::new(factory())) };
+        match self.entries.iter_mut().find(|known| known.info.id == entry.info.id) {
+            Some(known) => *known = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// All registered panels
+    pub fn panels(&self) -> impl Iterator<Item = &PanelInfo> {
+        self.entries.iter().map(|entry| &entry.info)
+    }
+
+    pub fn info(&self, id: &str) -> Option<&PanelInfo> {
+        self.panels().find(|info| info.id == id)
+    }
+
+    /// Ask every panel for its title again, e.g. after the language was
+    /// switched
+    pub fn refresh_titles(&mut self) {
+        for entry in &mut self.entries {
+            entry.info.title = entry.sample.title();
+        }
+    }
+
+    /// A new tab showing the panel `id`, `None` if it is not registered
+    pub fn create(&self, id: &str) -> Option<PanelTab> {
+        let entry = self.entries.iter().find(|entry| entry.info.id == id)?;
+        Some(PanelTab::new((entry.factory)()))
+    }
+}
+
+/// Source of `PanelTab::instance`
+static NEXT_INSTANCE: AtomicUsize = AtomicUsize::new(0);
+
+/// PanelTab
+///
+/// A dock tab: one panel instance and an id that is unique among all
+/// tabs, so duplicated panels keep separate widget state.
+pub struct PanelTab {
+    panel    : Box<dyn Panel>,
+    instance : usize,
+}
+
+impl PanelTab {
+    pub fn new(panel: Box<dyn Panel>) -> Self {
+        let instance = NEXT_INSTANCE.fetch_add(1, Ordering::Relaxed);
+        Self { panel, instance }
+    }
+
+    /// Id of the panel shown in the tab
+    pub fn id(&self) -> &'static str {
+        self.panel.id()
+    }
+
+    pub fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// Icon and title of the panel
+    pub fn title(&self) -> String {
+        label(self.panel.icon(), &self.panel.title())
+    }
+
+    pub fn allows_duplicates(&self) -> bool {
+        self.panel.allows_duplicates()
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        self.panel.ui(ui, ctx);
+    }
+}
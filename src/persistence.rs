@@ -0,0 +1,310 @@
+//! Persistence service for configuration files
+//!
+//! Configuration is written atomically (a temporary file in the same
+//! directory is written, synced and then renamed over the target), so a
+//! crash or power loss never leaves a half-written file behind.
+//!
+//! `PersistenceService` owns a background worker that debounces save
+//! requests - e.g. while the user is dragging a color picker - and can be
+//! flushed synchronously on shutdown. Failures are returned as a typed
+//! `PersistError` and forwarded to an optional error sink, which the
+//! templates use to surface them in the terminal widget.
+use crate::watcher::content_hash;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Default name of the per-user configuration directory
+pub const CONFIG_DIR_NAME: &str = "egui_mobius_template";
+
+static CONFIG_DIR_NAME_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Default delay between the last save request and the actual write
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Number of recent writes remembered to recognize the service's own
+/// writes when the file is watched
+const WRITE_HISTORY: usize = 8;
+
+/// Returns the per-user configuration directory of the application,
+/// falling back to the current directory if the platform has none.
+pub fn config_dir() -> PathBuf {
+    let name = CONFIG_DIR_NAME_OVERRIDE.get().map_or(CONFIG_DIR_NAME, String::as_str);
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(name)
+}
+
+/// Name the configuration directory `name` instead of `CONFIG_DIR_NAME`
+/// for the rest of the process, so applications built from the template
+/// keep their settings apart. Only the first call has an effect; it
+/// returns false if the name was already set.
+pub fn set_config_dir_name(name: impl Into<String>) -> bool {
+    CONFIG_DIR_NAME_OVERRIDE.set(name.into()).is_ok()
+}
+
+/// PersistError
+///
+/// Errors raised while reading or writing configuration files.
+#[derive(Debug)]
+pub enum PersistError {
+    /// Reading, writing or renaming a file failed
+    Io { path: PathBuf, source: io::Error },
+    /// The value could not be serialized
+    Serialize(serde_json::Error),
+    /// The file exists but its contents could not be parsed
+    Parse { path: PathBuf, source: serde_json::Error },
+    /// The background worker is no longer running
+    WorkerStopped,
+}
+
+impl PersistError {
+    /// True if the error only means that the file does not exist yet,
+    /// which is expected on first launch.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, PersistError::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersistError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            PersistError::Serialize(e) => write!(f, "failed to serialize: {}", e),
+            PersistError::Parse { path, source } => {
+                write!(f, "failed to parse {}: {}", path.display(), source)
+            }
+            PersistError::WorkerStopped => write!(f, "persistence worker is not running"),
+        }
+    }
+}
+
+impl std::error::Error for PersistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistError::Io { source, .. } => Some(source),
+            PersistError::Serialize(e) => Some(e),
+            PersistError::Parse { source, .. } => Some(source),
+            PersistError::WorkerStopped => None,
+        }
+    }
+}
+
+/// Writes `contents` to `path` atomically, creating parent directories
+/// as needed.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), PersistError> {
+    let io_err = |source| PersistError::Io { path: path.to_path_buf(), source };
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir).map_err(io_err)?;
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("config");
+    let tmp_path = dir.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result.map_err(io_err)
+}
+
+/// Serializes `value` as pretty JSON and writes it atomically to `path`.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), PersistError> {
+    let json = serde_json::to_string_pretty(value).map_err(PersistError::Serialize)?;
+    write_atomic(path, json.as_bytes())
+}
+
+/// Reads and parses a JSON file.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, PersistError> {
+    let content = fs::read_to_string(path)
+        .map_err(|source| PersistError::Io { path: path.to_path_buf(), source })?;
+    serde_json::from_str(&content)
+        .map_err(|source| PersistError::Parse { path: path.to_path_buf(), source })
+}
+
+type ErrorSink = Arc<Mutex<Option<Box<dyn Fn(&PersistError) + Send>>>>;
+type WriteHistory = Arc<Mutex<VecDeque<u64>>>;
+
+/// Serialize and write `value`, remembering the hash of the contents
+/// before the file is replaced so watchers can recognize the write
+fn write_tracked<T: Serialize>(path: &Path, value: &T, history: &WriteHistory) -> Result<(), PersistError> {
+    let json = serde_json::to_string_pretty(value).map_err(PersistError::Serialize)?;
+    {
+        let mut history = history.lock().unwrap();
+        if history.len() == WRITE_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(content_hash(json.as_bytes()));
+    }
+    write_atomic(path, json.as_bytes())
+}
+
+enum Command<T> {
+    /// Value to write and its sequence number
+    Save(T, u64),
+    Flush(Sender<Result<(), PersistError>>),
+}
+
+/// PersistenceService
+///
+/// Debounced, atomic JSON persistence of a single value on a background
+/// thread. Dropping the service flushes any pending value.
+pub struct PersistenceService<T: Serialize + Send + 'static> {
+    path       : PathBuf,
+    tx         : Option<Sender<Command<T>>>,
+    worker     : Option<JoinHandle<()>>,
+    error_sink : ErrorSink,
+    written    : WriteHistory,
+    /// Sequence numbers of the last queued and the last written value
+    queued     : AtomicU64,
+    saved      : Arc<AtomicU64>,
+}
+
+impl<T: Serialize + Send + 'static> PersistenceService<T> {
+    /// Create a service writing to `path` with the default debounce delay
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self::with_debounce(path, DEFAULT_DEBOUNCE)
+    }
+
+    /// Create a service writing to `path`, waiting `debounce` after the
+    /// last save request before touching the disk
+    pub fn with_debounce(path: impl Into<PathBuf>, debounce: Duration) -> Self {
+        let path = path.into();
+        let error_sink: ErrorSink = Arc::new(Mutex::new(None));
+        let written: WriteHistory = Arc::new(Mutex::new(VecDeque::new()));
+        let (tx, rx) = mpsc::channel::<Command<T>>();
+
+        let worker_path = path.clone();
+        let worker_sink = error_sink.clone();
+        let worker_written = written.clone();
+        let saved = Arc::new(AtomicU64::new(0));
+        let worker_saved = saved.clone();
+        let write = move |(value, sequence): &(T, u64)| {
+            write_tracked(&worker_path, value, &worker_written)?;
+            worker_saved.store(*sequence, Ordering::SeqCst);
+            Ok(())
+        };
+        let worker = std::thread::spawn(move || {
+            let report = |result: Result<(), PersistError>| {
+                if let Err(e) = &result {
+                    if let Some(sink) = worker_sink.lock().unwrap().as_ref() {
+                        sink(e);
+                    }
+                }
+                result
+            };
+            let mut pending: Option<((T, u64), Instant)> = None;
+
+            loop {
+                let command = match &pending {
+                    Some((_, deadline)) => {
+                        let timeout = deadline.saturating_duration_since(Instant::now());
+                        rx.recv_timeout(timeout)
+                    }
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+
+                match command {
+                    Ok(Command::Save(value, sequence)) => {
+                        pending = Some(((value, sequence), Instant::now() + debounce));
+                    }
+                    Ok(Command::Flush(ack)) => {
+                        let result = match pending.take() {
+                            Some((value, _)) => report(write(&value)),
+                            None => Ok(()),
+                        };
+                        let _ = ack.send(result);
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Some((value, _)) = pending.take() {
+                            let _ = report(write(&value));
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        if let Some((value, _)) = pending.take() {
+                            let _ = report(write(&value));
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            path,
+            tx: Some(tx),
+            worker: Some(worker),
+            error_sink,
+            written,
+            queued: AtomicU64::new(0),
+            saved,
+        }
+    }
+
+    /// Register a callback invoked with every failed background write
+    pub fn on_error<F>(&self, sink: F)
+    where
+        F: Fn(&PersistError) + Send + 'static,
+    {
+        *self.error_sink.lock().unwrap() = Some(Box::new(sink));
+    }
+
+    /// The file this service writes to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// True if `contents` is one of the last few versions of the file
+    /// written by this service
+    pub fn wrote(&self, contents: &[u8]) -> bool {
+        let hash = content_hash(contents);
+        self.written.lock().unwrap().contains(&hash)
+    }
+
+    /// Queue `value` to be written once no further save request arrives
+    /// within the debounce delay
+    pub fn save(&self, value: T) {
+        if let Some(tx) = &self.tx {
+            let sequence = self.queued.fetch_add(1, Ordering::SeqCst) + 1;
+            let _ = tx.send(Command::Save(value, sequence));
+        }
+    }
+
+    /// True while the last value queued by `save` has not been written,
+    /// because it is waiting for the debounce delay or writing it failed
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.queued.load(Ordering::SeqCst) != self.saved.load(Ordering::SeqCst)
+    }
+
+    /// Write any pending value now and wait for the result
+    pub fn flush(&self) -> Result<(), PersistError> {
+        let tx = self.tx.as_ref().ok_or(PersistError::WorkerStopped)?;
+        let (ack_tx, ack_rx) = mpsc::channel();
+        tx.send(Command::Flush(ack_tx)).map_err(|_| PersistError::WorkerStopped)?;
+        ack_rx.recv().map_err(|_| PersistError::WorkerStopped)?
+    }
+}
+
+impl<T: Serialize + Send + 'static> Drop for PersistenceService<T> {
+    fn drop(&mut self) {
+        // Closing the channel makes the worker write what is pending and exit
+        self.tx.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
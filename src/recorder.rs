@@ -0,0 +1,474 @@
+//! Recording and replaying UI sessions
+//!
+//! A `Recorder` captures what the user did as high-level inputs with
+//! their time since the recording started: changes of tracked values,
+//! such as a slider or the circuit parameters, shell commands run from
+//! a menu, the palette or a shortcut, and presses of buttons registered
+//! with `Recorder::press`. A `Recording` is saved as JSON and replayed
+//! later, at the recorded pace in the application or one input per frame
+//! in a headless test, so a reported bug can be reproduced step by step.
+//!
+//! Tracked values are compared once per frame in `poll`, so the inputs
+//! of a frame are recorded in a fixed order and replay deterministically.
+//!
+//! ```
+//! use egui_mobius_reactive::Dynamic;
+//! use egui_mobius_template::recorder::{Recorder, ReplaySpeed};
+//!
+//! let slider = Dynamic::new(1.0_f32);
+//! let recorder = Recorder::new();
+//! recorder.track(&slider, "slider");
+//!
+//! recorder.start("Example");
+//! slider.set(5.0);
+//! recorder.poll();
+//! assert!(recorder.press("reset", true));
+//! let recording = recorder.stop().unwrap();
+//! assert_eq!(recording.events.len(), 2);
+//!
+//! // Replay from the recorded start, one input per frame
+//! slider.set(3.0);
+//! recorder.replay(recording, ReplaySpeed::Stepped).unwrap();
+//! assert_eq!(slider.get(), 1.0);
+//! recorder.poll();
+//! assert_eq!(slider.get(), 5.0);
+//! recorder.poll();
+//! assert!(recorder.press("reset", false));
+//! ```
+use crate::persistence::{read_json, write_json, PersistError};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui_mobius_reactive::Dynamic;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Version of the recording file format written by `Recording::save`
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Directory holding the recordings saved next to the settings file
+/// `settings_path`
+pub fn recordings_dir(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("recordings")
+}
+
+/// RecordError
+///
+/// Errors raised while saving, loading or replaying a recording.
+#[derive(Debug)]
+pub enum RecordError {
+    /// The file could not be read, parsed or written
+    Persist(PersistError),
+    /// The file was written by a newer version of the format
+    UnsupportedVersion(u32),
+    /// The recording changes a value that is not tracked
+    UnknownValue(String),
+    /// A recorded value does not fit the tracked value
+    InvalidValue { name: String, source: serde_json::Error },
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Persist(e) => write!(f, "{}", e),
+            RecordError::UnsupportedVersion(version) => {
+                write!(f, "unsupported recording format version {}", version)
+            }
+            RecordError::UnknownValue(name) => write!(f, "the recorded value {:?} is not tracked", name),
+            RecordError::InvalidValue { name, source } => {
+                write!(f, "invalid recorded value {:?}: {}", name, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecordError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RecordError::Persist(e) => Some(e),
+            RecordError::InvalidValue { source, .. } => Some(source),
+            RecordError::UnsupportedVersion(_) | RecordError::UnknownValue(_) => None,
+        }
+    }
+}
+
+impl From<PersistError> for RecordError {
+    fn from(e: PersistError) -> Self {
+        RecordError::Persist(e)
+    }
+}
+
+/// RecordedInput
+///
+/// One high-level input of the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedInput {
+    /// A tracked value changed to `value`
+    Value { name: String, value: Value },
+    /// A shell command ran, see `MobiusApp`'s command keys
+    Command { key: String },
+    /// A button registered with `Recorder::press` was pressed
+    Press { name: String },
+}
+
+/// RecordedEvent
+///
+/// An input and when it happened, in milliseconds since the recording
+/// started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub at_ms : u64,
+    #[serde(flatten)]
+    pub input : RecordedInput,
+}
+
+/// Recording
+///
+/// The tracked values when recording started and every input after.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub version : u32,
+    /// Name of the application that was recorded
+    pub app     : String,
+    pub started : DateTime<Local>,
+    pub initial : BTreeMap<String, Value>,
+    pub events  : Vec<RecordedEvent>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self, RecordError> {
+        let recording: Recording = read_json(path)?;
+        if recording.version > FORMAT_VERSION {
+            return Err(RecordError::UnsupportedVersion(recording.version));
+        }
+        Ok(recording)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RecordError> {
+        Ok(write_json(path, self)?)
+    }
+
+    /// Time from the start to the last input
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.events.last().map_or(0, |event| event.at_ms))
+    }
+
+    /// File name for a recording started at `started`, to the
+    /// millisecond so recordings started in the same second are kept
+    pub fn file_name(started: &DateTime<Local>) -> String {
+        format!("recording-{}.json", started.format("%Y%m%d-%H%M%S%.3f"))
+    }
+}
+
+/// ReplaySpeed
+///
+/// How fast a recording is replayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// At the recorded pace, multiplied by the factor, e.g. `2.0` for
+    /// twice as fast
+    Recorded(f32),
+    /// One input per `poll`, regardless of the recorded times, e.g. one
+    /// per frame of a headless test
+    Stepped,
+}
+
+/// RecorderState
+///
+/// What a `Recorder` is doing, e.g. for a status item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecorderState {
+    Idle,
+    Recording { events: usize, elapsed: Duration },
+    Replaying { done: usize, total: usize },
+}
+
+type Getter = Box<dyn Fn() -> Value + Send>;
+type Setter = Box<dyn Fn(Value) -> Result<(), serde_json::Error> + Send>;
+type Checker = Box<dyn Fn(&Value) -> Result<(), serde_json::Error> + Send>;
+
+struct Tracked {
+    name  : String,
+    get   : Getter,
+    set   : Setter,
+    /// Whether a value can be set, without setting it
+    check : Checker,
+    /// Value at the last `poll` while recording
+    seen : Value,
+}
+
+enum Mode {
+    Idle,
+    Recording { started: Instant, recording: Recording },
+    Replaying { started: Instant, speed: ReplaySpeed, events: VecDeque<RecordedEvent>, total: usize },
+}
+
+struct RecorderInner {
+    tracked : Vec<Tracked>,
+    mode    : Mode,
+    /// Replayed presses of the current frame
+    pressed : Vec<String>,
+    repaint : Option<egui::Context>,
+}
+
+/// Recorder
+///
+/// Cheap to clone handle recording and replaying the inputs of the
+/// application, see the module documentation. The shell polls it every
+/// frame and records the commands it runs.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<RecorderInner>>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RecorderInner {
+                tracked: Vec::new(),
+                mode: Mode::Idle,
+                pressed: Vec::new(),
+                repaint: None,
+            })),
+        }
+    }
+
+    /// Request repaints of `ctx` while replaying, so inputs are applied
+    /// on time without user interaction
+    pub fn with_repaint(self, ctx: &egui::Context) -> Self {
+        self.inner.lock().unwrap().repaint = Some(ctx.clone());
+        self
+    }
+
+    /// Record changes of `dynamic` as the value `name`, and set it when
+    /// a recording is replayed. A value tracked again under the same
+    /// name replaces the earlier one.
+    pub fn track<T>(&self, dynamic: &Dynamic<T>, name: &str)
+    where
+        T: Clone + Send + Sync + PartialEq + Serialize + DeserializeOwned + 'static,
+    {
+        let source = dynamic.clone();
+        let target = dynamic.clone();
+        let get: Getter = Box::new(move || serde_json::to_value(source.get()).unwrap_or(Value::Null));
+        let set: Setter = Box::new(move |value| {
+            let value: T = serde_json::from_value(value)?;
+            if target.get() != value {
+                target.set(value);
+            }
+            Ok(())
+        });
+        let check: Checker = Box::new(|value| T::deserialize(value).map(drop));
+        let seen = get();
+
+        let mut inner = self.inner.lock().unwrap();
+        let tracked = Tracked { name: name.to_string(), get, set, check, seen };
+        match inner.tracked.iter_mut().find(|known| known.name == name) {
+            Some(known) => *known = tracked,
+            None => inner.tracked.push(tracked),
+        }
+    }
+
+    /// Start a new recording of the application `app`, discarding one
+    /// in progress and stopping a replay
+    pub fn start(&self, app: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        let mut initial = BTreeMap::new();
+        for tracked in &mut inner.tracked {
+            tracked.seen = (tracked.get)();
+            initial.insert(tracked.name.clone(), tracked.seen.clone());
+        }
+        let recording = Recording {
+            version: FORMAT_VERSION,
+            app: app.to_string(),
+            started: Local::now(),
+            initial,
+            events: Vec::new(),
+        };
+        inner.mode = Mode::Recording { started: Instant::now(), recording };
+        inner.pressed.clear();
+    }
+
+    /// Stop recording or replaying. Returns the recording if one was in
+    /// progress, with the changes since the last `poll`.
+    pub fn stop(&self) -> Option<Recording> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.record_changes();
+        inner.pressed.clear();
+        match std::mem::replace(&mut inner.mode, Mode::Idle) {
+            Mode::Recording { recording, .. } => Some(recording),
+            _ => None,
+        }
+    }
+
+    pub fn state(&self) -> RecorderState {
+        match &self.inner.lock().unwrap().mode {
+            Mode::Idle => RecorderState::Idle,
+            Mode::Recording { started, recording } => RecorderState::Recording {
+                events: recording.events.len(),
+                elapsed: started.elapsed(),
+            },
+            Mode::Replaying { events, total, .. } => RecorderState::Replaying {
+                done: total - events.len(),
+                total: *total,
+            },
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self.inner.lock().unwrap().mode, Mode::Recording { .. })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.inner.lock().unwrap().mode, Mode::Replaying { .. })
+    }
+
+    /// Record that the command `key` ran
+    pub fn command(&self, key: &str) {
+        self.inner.lock().unwrap().record(RecordedInput::Command { key: key.to_string() });
+    }
+
+    /// Record a press of the button `name` if `clicked`. Returns true if
+    /// it was clicked or a replayed press of it is due this frame, so a
+    /// panel writes `if recorder.press("run", ui.button("Run").clicked())`.
+    pub fn press(&self, name: &str, clicked: bool) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if clicked {
+            inner.record(RecordedInput::Press { name: name.to_string() });
+            return true;
+        }
+        match inner.pressed.iter().position(|pressed| pressed == name) {
+            Some(index) => {
+                inner.pressed.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replay `recording`: the tracked values are set to their recorded
+    /// initial values now, the inputs follow from `poll`. Fails without
+    /// changing anything if the recording changes values that are not
+    /// tracked or holds values they cannot take.
+    pub fn replay(&self, recording: Recording, speed: ReplaySpeed) -> Result<(), RecordError> {
+        let mut inner = self.inner.lock().unwrap();
+        let values = recording.initial.iter().chain(recording.events.iter().filter_map(|event| match &event.input {
+            RecordedInput::Value { name, value } => Some((name, value)),
+            _ => None,
+        }));
+        for (name, value) in values {
+            let tracked = inner.tracked.iter()
+                .find(|tracked| tracked.name == *name)
+                .ok_or_else(|| RecordError::UnknownValue(name.clone()))?;
+            (tracked.check)(value).map_err(|source| RecordError::InvalidValue { name: name.clone(), source })?;
+        }
+
+        for (name, value) in recording.initial {
+            inner.set(&name, value)?;
+        }
+        let total = recording.events.len();
+        inner.mode = Mode::Replaying { started: Instant::now(), speed, events: recording.events.into(), total };
+        inner.pressed.clear();
+        if let Some(repaint) = &inner.repaint {
+            repaint.request_repaint();
+        }
+        Ok(())
+    }
+
+    /// Called once per frame before the panels are shown: records the
+    /// changes of tracked values, or applies the replayed inputs that
+    /// are due. Returns the replayed commands to run.
+    pub fn poll(&self) -> Vec<String> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.pressed.clear();
+        inner.record_changes();
+
+        let Mode::Replaying { started, speed, events, .. } = &mut inner.mode else {
+            return Vec::new();
+        };
+        let due: Vec<_> = match *speed {
+            ReplaySpeed::Stepped => events.pop_front().into_iter().collect(),
+            ReplaySpeed::Recorded(factor) => {
+                let elapsed = started.elapsed().as_secs_f64() * 1000.0 * factor.max(0.01) as f64;
+                let count = events.iter().take_while(|event| event.at_ms as f64 <= elapsed).count();
+                events.drain(..count).collect()
+            }
+        };
+        let next = match (events.front(), *speed) {
+            (None, _) => None,
+            (Some(_), ReplaySpeed::Stepped) => Some(Duration::ZERO),
+            (Some(event), ReplaySpeed::Recorded(factor)) => {
+                let at = Duration::from_secs_f64(event.at_ms as f64 / 1000.0 / factor.max(0.01) as f64);
+                Some(at.saturating_sub(started.elapsed()))
+            }
+        };
+        if next.is_none() {
+            inner.mode = Mode::Idle;
+        }
+
+        let mut commands = Vec::new();
+        for event in due {
+            match event.input {
+                RecordedInput::Value { name, value } => {
+                    if let Err(e) = inner.set(&name, value) {
+                        log::error!("Replay: {}", e);
+                    }
+                }
+                RecordedInput::Command { key } => commands.push(key),
+                RecordedInput::Press { name } => inner.pressed.push(name),
+            }
+        }
+        if let (Some(next), Some(repaint)) = (next, &inner.repaint) {
+            repaint.request_repaint_after(next);
+        }
+        commands
+    }
+}
+
+impl RecorderInner {
+    fn record(&mut self, input: RecordedInput) {
+        if let Mode::Recording { started, recording } = &mut self.mode {
+            let at_ms = started.elapsed().as_millis() as u64;
+            recording.events.push(RecordedEvent { at_ms, input });
+        }
+    }
+
+    /// Record the tracked values that changed since the last call
+    fn record_changes(&mut self) {
+        if !matches!(self.mode, Mode::Recording { .. }) {
+            return;
+        }
+        let mut changes = Vec::new();
+        for tracked in &mut self.tracked {
+            let value = (tracked.get)();
+            if value != tracked.seen {
+                tracked.seen = value.clone();
+                changes.push(RecordedInput::Value { name: tracked.name.clone(), value });
+            }
+        }
+        for change in changes {
+            self.record(change);
+        }
+    }
+
+    fn set(&mut self, name: &str, value: Value) -> Result<(), RecordError> {
+        let tracked = self.tracked.iter_mut()
+            .find(|tracked| tracked.name == name)
+            .ok_or_else(|| RecordError::UnknownValue(name.to_string()))?;
+        (tracked.set)(value.clone())
+            .map_err(|source| RecordError::InvalidValue { name: name.to_string(), source })?;
+        tracked.seen = value;
+        Ok(())
+    }
+}
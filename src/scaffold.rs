@@ -0,0 +1,544 @@
+//! Project generator
+//!
+//! Creates a new application from one of the examples, which double as
+//! the templates: their sources are embedded in this crate, so no
+//! network access is needed besides fetching the dependencies when the
+//! new project is built. The crate name, window title and configuration
+//! directory are substituted and optional panels can be left out.
+//! `mobius-new` is the command line front end:
+//!
+//! ```text
+//! cargo run --bin mobius-new -- my_app --template signals-slot --title "My App"
+//! ```
+//!
+//! ```
+//! use egui_mobius_template::scaffold::{generate, ProjectOptions, Template};
+//!
+//! let mut options = ProjectOptions::new("bench_tool", Template::Reactive);
+//! options.panels = Some(vec!["settings".into(), "logger".into()]);
+//! let files = generate(&options).unwrap();
+//!
+//! assert!(files.iter().any(|file| file.path.ends_with("Cargo.toml")));
+//! assert!(!files.iter().any(|file| file.path.ends_with("about_panel.rs")));
+//! ```
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Dependency on this crate written to generated projects: the release
+/// tag of the version generating them, whose API the templates use. The
+/// tag only exists once that version is released (see VERSIONING.md);
+/// before that, generate with `ProjectOptions::local_dependency`.
+pub const TEMPLATE_DEPENDENCY: &str = concat!(
+    r#"{ git = "https://github.com/saturn77/egui_mobius_template", tag = "v"#,
+    env!("CARGO_PKG_VERSION"),
+    r#"" }"#
+);
+
+/// Versions of the dependencies the templates use, matching this crate's
+const DEPENDENCIES: &[(&str, &str)] = &[
+    ("chrono", r#"{ version = "0.4", features = ["serde"] }"#),
+    ("eframe", r#""0.32""#),
+    ("egui", r#""0.32""#),
+    ("egui_dock", r#"{ version = "0.17.0", features = ["serde"] }"#),
+    ("egui_mobius", r#""=0.3.0-alpha.32""#),
+    ("egui_mobius_reactive", r#""=0.3.0-alpha.32""#),
+    ("egui_mobius_widgets", r#""=0.3.0-alpha.32""#),
+    ("egui_plot", r#""0.33.0""#),
+    ("env_logger", r#""0.11.7""#),
+    ("log", r#""0.4.27""#),
+    ("ndarray", r#""0.16.1""#),
+    ("once_cell", r#""1.19""#),
+    ("serde", r#"{ version = "1.0", features = ["derive"] }"#),
+    ("tokio", r#"{ version = "1.44.1", features = ["full"] }"#),
+];
+
+/// A template source file: path in the generated project and contents
+type TemplateFile = (&'static str, &'static [u8]);
+
+macro_rules! template_files {
+    ($example:literal: $($path:literal),* $(,)?) => {
+        &[$(($path, include_bytes!(concat!("../examples/", $example, "/", $path)))),*]
+    };
+}
+
+const REACTIVE_FILES: &[TemplateFile] = template_files!("reactive":
+    "src/main.rs",
+    "src/ui/mod.rs",
+    "src/ui/about_panel.rs",
+    "src/ui/control_panel.rs",
+    "src/ui/logger_panel.rs",
+    "src/ui/settings_panel.rs",
+    "src/assets/saturn_rocket_company.png",
+    "locales/en-US.ftl",
+    "locales/de.ftl",
+    "locales/fr.ftl",
+);
+
+const REACTIVE_ASYNC_FILES: &[TemplateFile] = template_files!("reactive-async":
+    "src/main.rs",
+    "src/runtime_integration.rs",
+    "src/state.rs",
+    "src/types.rs",
+    "src/ui/mod.rs",
+    "src/ui/about_panel.rs",
+    "src/ui/control_panel.rs",
+    "src/ui/logger_panel.rs",
+    "src/ui/settings_panel.rs",
+    "src/assets/saturn_rocket_company.png",
+    "locales/en-US.ftl",
+    "locales/de.ftl",
+    "locales/fr.ftl",
+);
+
+const SIGNALS_SLOT_FILES: &[TemplateFile] = template_files!("signals-slot":
+    "src/main.rs",
+    "src/circuit.rs",
+    "src/state.rs",
+    "src/types.rs",
+    "src/slots/mod.rs",
+    "src/slots/simulation.rs",
+    "src/ui/mod.rs",
+    "src/ui/parameter_panel.rs",
+    "src/ui/plot_panel.rs",
+    "locales/en-US.ftl",
+    "locales/de.ftl",
+    "locales/fr.ftl",
+);
+
+/// TemplatePanel
+///
+/// A panel registered by a template's `main.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplatePanel {
+    pub id        : &'static str,
+    pub type_name : &'static str,
+    /// Source file of the panel in the project, `None` for the panels of
+    /// this crate
+    pub file      : Option<&'static str>,
+    /// Whether the rest of the template depends on the panel
+    pub required  : bool,
+}
+
+impl TemplatePanel {
+    const fn local(id: &'static str, type_name: &'static str, file: &'static str, required: bool) -> Self {
+        Self { id, type_name, file: Some(file), required }
+    }
+
+    const fn shell(id: &'static str, type_name: &'static str) -> Self {
+        Self { id, type_name, file: None, required: false }
+    }
+}
+
+const SHELL_PANELS: [TemplatePanel; 3] = [
+    TemplatePanel::shell("history", "HistoryPanel"),
+    TemplatePanel::shell("shortcuts", "ShortcutsPanel"),
+    TemplatePanel::shell("notifications", "NotificationsPanel"),
+];
+
+const REACTIVE_PANELS: &[TemplatePanel] = &[
+    TemplatePanel::local("control", "ControlPanel", "src/ui/control_panel.rs", false),
+    TemplatePanel::local("settings", "SettingsPanel", "src/ui/settings_panel.rs", true),
+    TemplatePanel::local("logger", "LoggerPanel", "src/ui/logger_panel.rs", false),
+    SHELL_PANELS[0],
+    SHELL_PANELS[1],
+    SHELL_PANELS[2],
+    TemplatePanel::local("about", "AboutPanel", "src/ui/about_panel.rs", false),
+];
+
+const SIGNALS_SLOT_PANELS: &[TemplatePanel] = &[
+    TemplatePanel::local("parameters", "ParameterPanel", "src/ui/parameter_panel.rs", true),
+    TemplatePanel::local("plot", "PlotPanel", "src/ui/plot_panel.rs", false),
+    SHELL_PANELS[0],
+    SHELL_PANELS[1],
+    SHELL_PANELS[2],
+];
+
+/// Template
+///
+/// The example a project is generated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    Reactive,
+    ReactiveAsync,
+    SignalsSlot,
+}
+
+impl Template {
+    pub const ALL: [Template; 3] = [Template::Reactive, Template::ReactiveAsync, Template::SignalsSlot];
+
+    /// Name of the template, as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            Template::Reactive => "reactive",
+            Template::ReactiveAsync => "reactive-async",
+            Template::SignalsSlot => "signals-slot",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Template::Reactive => "UI state in Dynamic values, updated in place",
+            Template::ReactiveAsync => "reactive UI with a background tokio runtime",
+            Template::SignalsSlot => "signal/slot messages to a processing thread",
+        }
+    }
+
+    /// Panels of the template, in registration order
+    pub fn panels(self) -> &'static [TemplatePanel] {
+        match self {
+            Template::Reactive | Template::ReactiveAsync => REACTIVE_PANELS,
+            Template::SignalsSlot => SIGNALS_SLOT_PANELS,
+        }
+    }
+
+    fn files(self) -> &'static [TemplateFile] {
+        match self {
+            Template::Reactive => REACTIVE_FILES,
+            Template::ReactiveAsync => REACTIVE_ASYNC_FILES,
+            Template::SignalsSlot => SIGNALS_SLOT_FILES,
+        }
+    }
+
+    /// Title of the window in the template, replaced by the project title
+    fn title(self) -> &'static str {
+        match self {
+            Template::Reactive | Template::ReactiveAsync => "MobiusLoop Example",
+            Template::SignalsSlot => "RLC Circuit Simulator",
+        }
+    }
+
+    fn dependencies(self) -> &'static [&'static str] {
+        match self {
+            Template::Reactive => &[
+                "eframe", "egui", "egui_dock", "egui_mobius_reactive", "egui_mobius_widgets",
+                "once_cell", "serde",
+            ],
+            Template::ReactiveAsync => &[
+                "chrono", "eframe", "egui", "egui_dock", "egui_mobius", "egui_mobius_reactive",
+                "egui_mobius_widgets", "env_logger", "log", "once_cell", "serde", "tokio",
+            ],
+            Template::SignalsSlot => &[
+                "eframe", "egui", "egui_dock", "egui_mobius", "egui_plot", "env_logger", "ndarray",
+                "serde",
+            ],
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Template {
+    type Err = ScaffoldError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Template::ALL
+            .into_iter()
+            .find(|template| template.name() == name)
+            .ok_or_else(|| ScaffoldError::UnknownTemplate(name.to_string()))
+    }
+}
+
+/// ScaffoldError
+///
+/// Errors from generating or writing a project.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The project name is not a valid crate name
+    InvalidName(String),
+    UnknownTemplate(String),
+    /// A requested panel is not part of the template
+    UnknownPanel { template: Template, panel: String },
+    /// A panel the template depends on was left out
+    RequiredPanel { template: Template, panel: &'static str },
+    /// The output directory exists and is not empty
+    DirectoryNotEmpty(PathBuf),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidName(name) => write!(
+                f,
+                "invalid project name {:?}: use letters, digits, '-' and '_', starting with a letter",
+                name
+            ),
+            ScaffoldError::UnknownTemplate(name) => {
+                let names: Vec<_> = Template::ALL.iter().map(|template| template.name()).collect();
+                write!(f, "unknown template {:?}, expected one of {}", name, names.join(", "))
+            }
+            ScaffoldError::UnknownPanel { template, panel } => {
+                let ids: Vec<_> = template.panels().iter().map(|panel| panel.id).collect();
+                write!(f, "template {} has no panel {:?}, expected one of {}", template, panel, ids.join(", "))
+            }
+            ScaffoldError::RequiredPanel { template, panel } => {
+                write!(f, "template {} needs the {:?} panel", template, panel)
+            }
+            ScaffoldError::DirectoryNotEmpty(path) => {
+                write!(f, "{} already exists and is not empty", path.display())
+            }
+            ScaffoldError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// ProjectOptions
+///
+/// What to generate. Only the name and template are needed, the other
+/// options default to values derived from the name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectOptions {
+    /// Crate name of the project
+    pub name            : String,
+    pub template        : Template,
+    /// Window title, by default the name in title case
+    pub title           : Option<String>,
+    /// Name of the configuration directory, by default the crate name
+    pub config_dir_name : Option<String>,
+    /// Ids of the panels to include, all panels of the template if `None`
+    pub panels          : Option<Vec<String>>,
+    /// Dependency on this crate in the generated `Cargo.toml`, as a TOML
+    /// value
+    pub dependency      : String,
+}
+
+impl ProjectOptions {
+    pub fn new(name: impl Into<String>, template: Template) -> Self {
+        Self {
+            name: name.into(),
+            template,
+            title: None,
+            config_dir_name: None,
+            panels: None,
+            dependency: TEMPLATE_DEPENDENCY.to_string(),
+        }
+    }
+
+    /// Depend on a local checkout of this crate at `path` instead of the
+    /// git repository
+    pub fn local_dependency(mut self, path: &Path) -> Self {
+        self.dependency = format!("{{ path = {:?} }}", path.display().to_string());
+        self
+    }
+
+    /// Whether the project depends on the release tag, `TEMPLATE_DEPENDENCY`
+    pub fn uses_release_tag(&self) -> bool {
+        self.dependency == TEMPLATE_DEPENDENCY
+    }
+
+    fn title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| title_case(&self.name))
+    }
+}
+
+/// GeneratedFile
+///
+/// A file of the generated project, `path` relative to its root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    pub path     : PathBuf,
+    pub contents : Vec<u8>,
+}
+
+/// Whether `name` can be used as the crate name of a project
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `my-app` as "My App"
+fn title_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Generate the files of the project described by `options`
+pub fn generate(options: &ProjectOptions) -> Result<Vec<GeneratedFile>, ScaffoldError> {
+    if !is_valid_name(&options.name) {
+        return Err(ScaffoldError::InvalidName(options.name.clone()));
+    }
+    let template = options.template;
+    let removed = removed_panels(options)?;
+
+    let mut files = vec![
+        GeneratedFile { path: "Cargo.toml".into(), contents: cargo_toml(options).into_bytes() },
+        GeneratedFile { path: ".gitignore".into(), contents: b"/target\n".to_vec() },
+    ];
+    let about_removed = removed.iter().any(|panel| panel.id == "about");
+    for &(path, contents) in template.files() {
+        if removed.iter().any(|panel| panel.file == Some(path)) || (about_removed && path.starts_with("src/assets/")) {
+            continue;
+        }
+        let contents = match path {
+            "src/main.rs" => main_rs(options, &removed, text(contents)).into_bytes(),
+            "src/ui/mod.rs" => ui_mod_rs(&removed, text(contents)).into_bytes(),
+            _ => contents.to_vec(),
+        };
+        files.push(GeneratedFile { path: path.into(), contents });
+    }
+    Ok(files)
+}
+
+/// Generate the project into `dir`, which must not exist or be empty.
+/// Returns the paths of the files written.
+pub fn write(dir: &Path, options: &ProjectOptions) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let files = generate(options)?;
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| ScaffoldError::Io { path, source }
+    };
+    match fs::read_dir(dir) {
+        Ok(mut entries) => {
+            if entries.next().is_some() {
+                return Err(ScaffoldError::DirectoryNotEmpty(dir.to_path_buf()));
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(io_error(dir)(err)),
+    }
+
+    let mut written = Vec::new();
+    for file in files {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error(parent))?;
+        }
+        fs::write(&path, &file.contents).map_err(io_error(&path))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn text(contents: &'static [u8]) -> &'static str {
+    std::str::from_utf8(contents).expect("template sources are UTF-8")
+}
+
+/// Panels of the template left out by `options`
+fn removed_panels(options: &ProjectOptions) -> Result<Vec<TemplatePanel>, ScaffoldError> {
+    let template = options.template;
+    let Some(selected) = &options.panels else {
+        return Ok(Vec::new());
+    };
+    if let Some(unknown) = selected.iter().find(|id| !template.panels().iter().any(|panel| panel.id == id.as_str())) {
+        return Err(ScaffoldError::UnknownPanel { template, panel: unknown.clone() });
+    }
+    let removed: Vec<_> = template.panels().iter().filter(|panel| !selected.contains(&panel.id.to_string())).copied().collect();
+    if let Some(required) = removed.iter().find(|panel| panel.required) {
+        return Err(ScaffoldError::RequiredPanel { template, panel: required.id });
+    }
+    Ok(removed)
+}
+
+fn cargo_toml(options: &ProjectOptions) -> String {
+    let mut toml = format!(
+        "[package]\nname = {:?}\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\negui_mobius_template = {}\n",
+        options.name, options.dependency
+    );
+    for name in options.template.dependencies() {
+        let (_, version) = DEPENDENCIES.iter().find(|(dependency, _)| dependency == name).expect("known dependency");
+        toml.push_str(&format!("{} = {}\n", name, version));
+    }
+    toml
+}
+
+/// The template's `main.rs` with the new title and configuration
+/// directory, and without the registrations of removed panels
+fn main_rs(options: &ProjectOptions, removed: &[TemplatePanel], source: &str) -> String {
+    let config_dir_name = options.config_dir_name.as_deref().unwrap_or(&options.name);
+    let title_call = format!(".title({:?})", options.template.title());
+    let mut out = String::new();
+    for line in source.lines() {
+        let trimmed = line.trim();
+        // The template's own directory is replaced below the title, and
+        // its tests are not part of the project
+        if removed.iter().any(|panel| registers(trimmed, panel))
+            || trimmed.starts_with(".config_dir_name(")
+            || trimmed == "#[cfg(test)]"
+            || trimmed == "mod tests;"
+        {
+            continue;
+        }
+        if trimmed == title_call {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            out.push_str(&format!("{}.title({:?})\n", indent, options.title()));
+            out.push_str(&format!("{}.config_dir_name({:?})\n", indent, config_dir_name));
+            continue;
+        }
+        let mut line = line.to_string();
+        for panel in removed {
+            line = without_list_item(&line, ("{", '}'), |item| item.rsplit("::").next() == Some(panel.type_name));
+            let id = format!("{:?}", panel.id);
+            line = without_list_item(&line, ("vec![", ']'), |item| item == id);
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Whether `line` imports, registers or adds the actions of `panel` and
+/// nothing else
+fn registers(line: &str, panel: &TemplatePanel) -> bool {
+    line.contains(&format!(".panel::<{}>()", panel.type_name))
+        || line.contains(&format!("({}::actions())", panel.type_name))
+        || (line.starts_with("use ") && line.ends_with(&format!("::{};", panel.type_name)))
+}
+
+/// `line` without the items of its first `open`..`close` list matching
+/// `remove`. A brace list left with a single item loses its braces.
+fn without_list_item(line: &str, (open, close): (&str, char), remove: impl Fn(&str) -> bool) -> String {
+    let Some(start) = line.find(open).map(|start| start + open.len()) else {
+        return line.to_string();
+    };
+    let Some(end) = line[start..].find(close).map(|end| start + end) else {
+        return line.to_string();
+    };
+    let items: Vec<_> = line[start..end].split(',').map(str::trim).filter(|item| !item.is_empty()).collect();
+    let kept: Vec<_> = items.iter().copied().filter(|item| !remove(item)).collect();
+    if kept.len() == items.len() {
+        return line.to_string();
+    }
+    if open == "{" && kept.len() == 1 {
+        format!("{}{}{}", &line[..start - 1], kept[0], &line[end + 1..])
+    } else {
+        format!("{}{}{}", &line[..start], kept.join(", "), &line[end..])
+    }
+}
+
+/// The template's `ui/mod.rs` without the modules of removed panels
+fn ui_mod_rs(removed: &[TemplatePanel], source: &str) -> String {
+    let modules: Vec<_> = removed
+        .iter()
+        .filter_map(|panel| panel.file?.strip_prefix("src/ui/")?.strip_suffix(".rs"))
+        .map(|module| format!("pub mod {};", module))
+        .collect();
+    let mut out = String::new();
+    for line in source.lines().filter(|line| !modules.iter().any(|module| line.trim() == module)) {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
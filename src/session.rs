@@ -0,0 +1,50 @@
+//! Saved sessions
+//!
+//! A session is a named copy of the whole settings document - dock
+//! layout, log colors, shortcuts and the sections of the application -
+//! kept as `<name>.json` in a `sessions` directory next to the
+//! settings file. The application shell saves and opens them from its
+//! File menu with `SettingsStore::export` and `SettingsStore::import`.
+//!
+//! ```
+//! use egui_mobius_template::session::session_path;
+//! use std::path::Path;
+//!
+//! let path = session_path(Path::new("sessions"), "Bench: 10kHz");
+//! assert_eq!(path, Path::new("sessions").join("Bench_ 10kHz.json"));
+//! ```
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the sessions saved next to the settings file
+/// `settings_path`
+pub fn sessions_dir(settings_path: &Path) -> PathBuf {
+    settings_path.with_file_name("sessions")
+}
+
+/// File of the session `name` in `dir`. Characters that are not safe in
+/// a file name on every platform are replaced by `_`.
+pub fn session_path(dir: &Path, name: &str) -> PathBuf {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') { c } else { '_' })
+        .collect();
+    dir.join(format!("{}.json", stem))
+}
+
+/// Name and file of every session saved in `dir`, sorted by name. A
+/// missing or unreadable directory has no sessions.
+pub fn list(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sessions: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path)))
+        .collect();
+    sessions.sort_by_key(|(name, _)| name.to_lowercase());
+    sessions
+}
@@ -0,0 +1,446 @@
+//! Unified application settings store
+//!
+//! All persisted settings of an application live in a single JSON
+//! document (`settings.json` in the per-user config directory), split
+//! into named sections. Each section is a plain serde struct that
+//! implements `SettingsSection`:
+//!
+//! ```ignore
+//! #[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+//! struct ControlSettings { slider_value: f32 }
+//!
+//! impl SettingsSection for ControlSettings {
+//!     const KEY: &'static str = "control";
+//! }
+//!
+//! let settings = SettingsStore::new(settings::default_path());
+//! settings.load()?;
+//! let control: ControlSettings = settings.get();
+//! settings.set(&control); // debounced save, only if changed
+//! ```
+//!
+//! Reactive state can be bound with `SettingsStore::bind`, which loads
+//! the stored value into a `Dynamic` and saves it whenever it changes.
+//!
+//! With `SettingsStore::watch` the settings file is hot-reloaded: edits
+//! made in an external editor are applied by `apply_reload` on the UI
+//! thread. They replace the document, update bound values and invoke the
+//! `on_reload` callbacks of the sections that changed, with the default
+//! value of sections the edit removed, while the store's own writes are
+//! ignored. A file that fails to parse is reported to the error sink and
+//! the current settings are kept.
+//!
+//! Contents that cannot be read are never overwritten silently: before
+//! the store saves over a file that failed to load or parse, or over a
+//! section that failed to parse, the file is copied to `backup_path`.
+//! If that copy fails, the store stops saving.
+use crate::persistence::{config_dir, read_json, write_json, PersistError, PersistenceService};
+use crate::watcher::FileWatcher;
+use egui_mobius_reactive::{Dynamic, ValueExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The settings document: section key -> section value
+pub type SettingsDocument = serde_json::Map<String, Value>;
+
+/// Path of the settings file inside the per-user config directory
+pub fn default_path() -> PathBuf {
+    config_dir().join("settings.json")
+}
+
+/// Path the settings file `settings_path` is copied to before contents
+/// that could not be read are overwritten, e.g. `settings.json.bak`
+pub fn backup_path(settings_path: &Path) -> PathBuf {
+    let mut name = settings_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    settings_path.with_file_name(name)
+}
+
+/// SettingsSection
+///
+/// A typed section of the settings document. `KEY` must be unique
+/// within an application.
+pub trait SettingsSection:
+    Serialize + DeserializeOwned + Default + Clone + PartialEq + Send + Sync + 'static
+{
+    /// Key of the section in the settings document
+    const KEY: &'static str;
+
+    /// File name (next to the settings file) that held this section
+    /// before the unified store existed. It is read once as a fallback
+    /// when the section is missing from the settings document.
+    const LEGACY_FILE: Option<&'static str> = None;
+}
+
+type ErrorSink = Arc<Mutex<Option<Arc<dyn Fn(&PersistError) + Send + Sync>>>>;
+type Snapshot = Box<dyn Fn(&SettingsStore) + Send>;
+/// Called with the reloaded value of its section, `None` if the section
+/// was removed
+type ReloadListener = Box<dyn Fn(&SettingsStore, Option<&Value>) + Send>;
+
+struct StoreInner {
+    document    : Mutex<SettingsDocument>,
+    persistence : PersistenceService<SettingsDocument>,
+    bindings    : Mutex<Vec<Snapshot>>,
+    listeners   : Mutex<Vec<(&'static str, ReloadListener)>>,
+    error_sink  : ErrorSink,
+    /// Set when unreadable contents could not be backed up
+    read_only   : AtomicBool,
+    /// External edit of the file waiting for `apply_reload`
+    reload      : Mutex<Option<Vec<u8>>>,
+}
+
+/// SettingsStore
+///
+/// Cheap to clone handle to the settings document; all clones share
+/// the same document and persistence worker.
+#[derive(Clone)]
+pub struct SettingsStore {
+    inner: Arc<StoreInner>,
+}
+
+impl SettingsStore {
+    /// Create an empty store backed by `path`. Call `load` to read the
+    /// existing file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let error_sink: ErrorSink = Arc::new(Mutex::new(None));
+        let persistence = PersistenceService::new(path);
+        let sink = error_sink.clone();
+        persistence.on_error(move |e| {
+            if let Some(sink) = sink.lock().unwrap().as_ref() {
+                sink(e);
+            }
+        });
+
+        Self {
+            inner: Arc::new(StoreInner {
+                document: Mutex::new(SettingsDocument::new()),
+                persistence,
+                bindings: Mutex::new(Vec::new()),
+                listeners: Mutex::new(Vec::new()),
+                error_sink,
+                read_only: AtomicBool::new(false),
+                reload: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Register a callback invoked with every persistence failure,
+    /// including sections that fail to parse
+    pub fn on_error<F>(&self, sink: F)
+    where
+        F: Fn(&PersistError) + Send + Sync + 'static,
+    {
+        *self.inner.error_sink.lock().unwrap() = Some(Arc::new(sink));
+    }
+
+    /// The file backing this store
+    pub fn path(&self) -> &Path {
+        self.inner.persistence.path()
+    }
+
+    /// Read the settings file, replacing the in-memory document.
+    ///
+    /// A missing file is not an error; the store simply starts empty.
+    /// A file that cannot be read or parsed is backed up before the
+    /// store saves over it, see `backup_path`.
+    pub fn load(&self) -> Result<(), PersistError> {
+        match read_json::<SettingsDocument>(self.path()) {
+            Ok(document) => {
+                *self.inner.document.lock().unwrap() = document;
+                Ok(())
+            }
+            Err(e) if e.is_not_found() => Ok(()),
+            Err(e) => {
+                self.back_up();
+                Err(e)
+            }
+        }
+    }
+
+    /// True if the store stopped saving because contents it could not
+    /// read could not be backed up either; changes are kept in memory
+    pub fn is_read_only(&self) -> bool {
+        self.inner.read_only.load(Ordering::SeqCst)
+    }
+
+    /// Get a section, falling back to its legacy file and then to its
+    /// default value. Parse failures are reported to the error sink; the
+    /// stored section is backed up before `set` replaces it.
+    pub fn get<T: SettingsSection>(&self) -> T {
+        let stored = self.inner.document.lock().unwrap().get(T::KEY).cloned();
+        if let Some(value) = stored {
+            return match serde_json::from_value(value) {
+                Ok(section) => section,
+                Err(source) => {
+                    self.report(&PersistError::Parse {
+                        path: self.path().to_path_buf(),
+                        source,
+                    });
+                    T::default()
+                }
+            };
+        }
+
+        match T::LEGACY_FILE {
+            Some(file) => match read_json(&self.path().with_file_name(file)) {
+                Ok(section) => section,
+                Err(e) => {
+                    if !e.is_not_found() {
+                        self.report(&e);
+                    }
+                    T::default()
+                }
+            },
+            None => T::default(),
+        }
+    }
+
+    /// Store a section. A debounced save is queued only if the value
+    /// differs from what is already stored, so this is cheap to call
+    /// every frame.
+    pub fn set<T: SettingsSection>(&self, section: &T) {
+        let value = match serde_json::to_value(section) {
+            Ok(value) => value,
+            Err(e) => {
+                self.report(&PersistError::Serialize(e));
+                return;
+            }
+        };
+
+        let unreadable = match self.inner.document.lock().unwrap().get(T::KEY) {
+            Some(stored) if *stored == value => return,
+            stored => stored.is_some_and(|stored| serde_json::from_value::<T>(stored.clone()).is_err()),
+        };
+        // Keep the section that failed to parse in the backup
+        if unreadable {
+            self.back_up();
+        }
+
+        let mut document = self.inner.document.lock().unwrap();
+        document.insert(T::KEY.to_string(), value);
+        self.save(document.clone());
+    }
+
+    /// Load the stored section into `dynamic` and save it whenever the
+    /// value changes
+    pub fn bind<T: SettingsSection>(&self, dynamic: &Dynamic<T>) {
+        dynamic.set(self.get::<T>());
+
+        let store = self.clone();
+        let source = dynamic.clone();
+        dynamic.on_change(move || store.set(&source.get()));
+
+        // Change notifications are delivered on another thread, so flush
+        // and export take a fresh snapshot of every bound value as well.
+        let source = dynamic.clone();
+        self.inner
+            .bindings
+            .lock()
+            .unwrap()
+            .push(Box::new(move |store| store.set(&source.get())));
+
+        let target = dynamic.clone();
+        self.on_reload(move |section: T| target.set(section));
+    }
+
+    /// Call `f` with the new value whenever section `T` is changed by
+    /// an external edit of the settings file, or with its default value
+    /// when the edit removed it
+    pub fn on_reload<T, F>(&self, f: F)
+    where
+        T: SettingsSection,
+        F: Fn(T) + Send + 'static,
+    {
+        let listener: ReloadListener = Box::new(move |store, value| {
+            let Some(value) = value else {
+                return f(T::default());
+            };
+            match serde_json::from_value::<T>(value.clone()) {
+                Ok(section) => f(section),
+                Err(source) => store.report(&PersistError::Parse {
+                    path: store.path().to_path_buf(),
+                    source,
+                }),
+            }
+        });
+        self.inner.listeners.lock().unwrap().push((T::KEY, listener));
+    }
+
+    /// Hot-reload the settings file whenever it changes on disk. The
+    /// new contents wait for `apply_reload` and `repaint` is repainted
+    /// to call it.
+    pub fn watch(&self, watcher: &FileWatcher, repaint: &egui::Context) {
+        let store = self.clone();
+        let repaint = repaint.clone();
+        watcher.watch(self.path().to_path_buf(), move |contents| {
+            // Editors that truncate before writing briefly leave an empty file
+            if store.inner.persistence.wrote(contents) || contents.trim_ascii().is_empty() {
+                return;
+            }
+            *store.inner.reload.lock().unwrap() = Some(contents.to_vec());
+            repaint.request_repaint();
+        });
+    }
+
+    /// Apply the last external edit of the settings file noticed by
+    /// `watch`, returning false if there was none. Call it on the thread
+    /// that stores the settings, before it reads them, so values are not
+    /// stored while the reload replaces them.
+    pub fn apply_reload(&self) -> bool {
+        let Some(contents) = self.inner.reload.lock().unwrap().take() else {
+            return false;
+        };
+        match serde_json::from_slice(&contents) {
+            Ok(document) => self.apply(document),
+            // Keep the current settings rather than reverting to
+            // defaults, and the edit in the backup before it is saved over
+            Err(source) => {
+                self.back_up();
+                self.report(&PersistError::Parse {
+                    path: self.path().to_path_buf(),
+                    source,
+                });
+            }
+        }
+        true
+    }
+
+    /// Replace the current document with `document`, notifying the
+    /// reload listeners of every section that changed. Sections missing
+    /// from `document` are removed and their listeners reset them to
+    /// their default value.
+    fn apply(&self, document: SettingsDocument) {
+        let changed: Vec<(String, Option<Value>)> = {
+            let mut current = self.inner.document.lock().unwrap();
+            let mut changed: Vec<_> = document
+                .iter()
+                .filter(|(key, value)| current.get(*key) != Some(*value))
+                .map(|(key, value)| (key.clone(), Some(value.clone())))
+                .collect();
+            changed.extend(
+                current
+                    .keys()
+                    .filter(|key| !document.contains_key(*key))
+                    .map(|key| (key.clone(), None)),
+            );
+            *current = document;
+            changed
+        };
+
+        let listeners = self.inner.listeners.lock().unwrap();
+        for (key, value) in &changed {
+            for (_, listener) in listeners.iter().filter(|(k, _)| k == key) {
+                listener(self, value.as_ref());
+            }
+        }
+    }
+
+    /// A copy of the whole settings document, unsaved changes included
+    pub fn document(&self) -> SettingsDocument {
+        self.snapshot_bindings();
+        self.inner.document.lock().unwrap().clone()
+    }
+
+    /// Write a copy of the whole settings document to `path`, e.g. to
+    /// save a session
+    pub fn export(&self, path: &Path) -> Result<(), PersistError> {
+        write_json(path, &self.document())
+    }
+
+    /// Read a document written by `export` and apply it as if the
+    /// settings file had been edited: it replaces the current document,
+    /// bound values are updated and the `on_reload` callbacks of the
+    /// changed sections invoked, sections the session lacks are reset to
+    /// their defaults. The result is saved to the settings file.
+    pub fn import(&self, path: &Path) -> Result<(), PersistError> {
+        let document: SettingsDocument = read_json(path)?;
+        self.apply(document);
+        let document = self.inner.document.lock().unwrap().clone();
+        self.save(document);
+        Ok(())
+    }
+
+    /// True while changed sections have not been written to the file,
+    /// because the save is debounced or failed
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.inner.persistence.has_unsaved_changes()
+    }
+
+    /// Write all pending changes now and wait for the result
+    pub fn flush(&self) -> Result<(), PersistError> {
+        self.snapshot_bindings();
+        self.inner.persistence.flush()
+    }
+
+    fn save(&self, document: SettingsDocument) {
+        if !self.is_read_only() {
+            self.inner.persistence.save(document);
+        }
+    }
+
+    /// Copy the settings file to `backup_path` before contents that could
+    /// not be read are saved over, or stop saving if that fails
+    fn back_up(&self) {
+        let backup = backup_path(self.path());
+        match std::fs::copy(self.path(), &backup) {
+            Ok(_) => log::warn!("Kept a copy of {} as {}", self.path().display(), backup.display()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => {
+                self.inner.read_only.store(true, Ordering::SeqCst);
+                self.report(&PersistError::Io { path: backup, source });
+            }
+        }
+    }
+
+    // Change notifications are delivered on another thread, so take a
+    // fresh snapshot of every bound value
+    fn snapshot_bindings(&self) {
+        for snapshot in self.inner.bindings.lock().unwrap().iter() {
+            snapshot(self);
+        }
+    }
+
+    fn report(&self, error: &PersistError) {
+        let sink = self.inner.error_sink.lock().unwrap().clone();
+        if let Some(sink) = sink {
+            sink(error);
+        }
+    }
+}
+
+/// WindowSettings
+///
+/// Size of the main window, restored on the next launch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowSettings {
+    pub inner_size: [f32; 2],
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self { inner_size: [900.0, 800.0] }
+    }
+}
+
+impl SettingsSection for WindowSettings {
+    const KEY: &'static str = "window";
+}
+
+impl WindowSettings {
+    /// Read the current window size from the egui context, if known
+    pub fn from_context(ctx: &egui::Context) -> Option<Self> {
+        ctx.input(|i| i.viewport().inner_rect).map(|rect| Self {
+            inner_size: [rect.width(), rect.height()],
+        })
+    }
+
+    /// Resize the window to these settings, e.g. after a reload
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.inner_size.into()));
+    }
+}
@@ -0,0 +1,366 @@
+//! Configurable keyboard shortcuts
+//!
+//! Every command of the shell - opening a panel, resetting the layout,
+//! the registered actions - may come with a default binding. Users
+//! rebind or unbind commands in the `ShortcutsPanel`; only the bindings
+//! that differ from the defaults are kept, in the `shortcuts` section of
+//! the settings file. Shortcuts are stored portably, `Ctrl` standing for
+//! Cmd on macOS:
+//!
+//! ```
+//! use eframe::egui::{Key, KeyboardShortcut, Modifiers};
+//! use egui_mobius_template::shortcuts::{parse_shortcut, shortcut_to_string, ShortcutManager};
+//!
+//! let clear = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::L);
+//! assert_eq!(shortcut_to_string(&clear), "Ctrl+Shift+L");
+//! assert_eq!(parse_shortcut("Ctrl+Shift+L"), Some(clear));
+//!
+//! let mut shortcuts = ShortcutManager::new();
+//! shortcuts.register("action:logger.clear", "Logger: Clear logger", Some(clear));
+//! shortcuts.register("action:logger.export", "Logger: Export log", None);
+//! shortcuts.rebind("action:logger.export", Some(clear));
+//! assert_eq!(shortcuts.conflicts().len(), 1);
+//! ```
+//!
+//! Two commands bound to the same shortcut are a conflict: it is shown
+//! next to both and only the command registered first receives the key.
+use crate::panel::{AppContext, Panel};
+use crate::settings::SettingsSection;
+use crate::tr;
+use eframe::egui;
+use egui::{Event, Key, KeyboardShortcut, Modifiers};
+use egui_mobius_reactive::Dynamic;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// `shortcut` in the portable form used by the settings file, e.g.
+/// `Ctrl+Shift+P`
+pub fn shortcut_to_string(shortcut: &KeyboardShortcut) -> String {
+    let modifiers = shortcut.modifiers;
+    let mut parts = Vec::new();
+    if modifiers.command || modifiers.ctrl || modifiers.mac_cmd {
+        parts.push("Ctrl");
+    }
+    if modifiers.alt {
+        parts.push("Alt");
+    }
+    if modifiers.shift {
+        parts.push("Shift");
+    }
+    parts.push(shortcut.logical_key.name());
+    parts.join("+")
+}
+
+/// Parse a shortcut written by `shortcut_to_string`. `Cmd` and `Option`
+/// are accepted for `Ctrl` and `Alt`.
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let mut parts: Vec<_> = text.split('+').map(str::trim).collect();
+    let key = Key::from_name(parts.pop()?)?;
+    let mut modifiers = Modifiers::NONE;
+    for part in parts {
+        modifiers = modifiers.plus(match part {
+            "Ctrl" | "Cmd" | "Command" => Modifiers::COMMAND,
+            "Alt" | "Option" => Modifiers::ALT,
+            "Shift" => Modifiers::SHIFT,
+            _ => return None,
+        });
+    }
+    Some(KeyboardShortcut::new(modifiers, key))
+}
+
+/// Modifiers of a key press as they are bound: Ctrl and Cmd both
+/// become the platform's command key
+fn normalize(modifiers: Modifiers) -> Modifiers {
+    let mut normalized = Modifiers::NONE;
+    if modifiers.command {
+        normalized = normalized.plus(Modifiers::COMMAND);
+    }
+    if modifiers.alt {
+        normalized = normalized.plus(Modifiers::ALT);
+    }
+    if modifiers.shift {
+        normalized = normalized.plus(Modifiers::SHIFT);
+    }
+    normalized
+}
+
+/// Whether the shortcuts panel lets users assign `shortcut`. Keys other
+/// than F1-F35 need Ctrl/Cmd or Alt, without which they would take keys
+/// typed into text fields.
+pub fn is_assignable(shortcut: &KeyboardShortcut) -> bool {
+    let function_key = shortcut.logical_key.name().strip_prefix('F').is_some_and(|n| n.parse::<u8>().is_ok());
+    function_key || shortcut.modifiers.command || shortcut.modifiers.alt
+}
+
+fn modifier_count(modifiers: Modifiers) -> usize {
+    [modifiers.command, modifiers.alt, modifiers.shift].iter().filter(|set| **set).count()
+}
+
+/// ShortcutSettings
+///
+/// Bindings changed by the user: command key -> shortcut, an empty
+/// string for a command the user unbound.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutSettings {
+    pub bindings: BTreeMap<String, String>,
+}
+
+impl SettingsSection for ShortcutSettings {
+    const KEY: &'static str = "shortcuts";
+}
+
+/// ShortcutCommand
+///
+/// A command that can be bound to a shortcut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutCommand {
+    /// Key of the command, as used by the command palette
+    pub key     : String,
+    pub title   : String,
+    pub default : Option<KeyboardShortcut>,
+}
+
+/// ShortcutManager
+///
+/// The bindable commands with their default and user bindings.
+pub struct ShortcutManager {
+    commands  : Vec<ShortcutCommand>,
+    settings  : Dynamic<ShortcutSettings>,
+    capturing : Option<String>,
+    /// The last key pressed while capturing was not assignable
+    rejected  : bool,
+}
+
+impl Default for ShortcutManager {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            settings: Dynamic::new(ShortcutSettings::default()),
+            capturing: None,
+            rejected: false,
+        }
+    }
+}
+
+impl ShortcutManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the command `key`. A command with the key of an earlier
+    /// one replaces it.
+    pub fn register(&mut self, key: impl Into<String>, title: impl Into<String>,
+                    default: Option<KeyboardShortcut>) {
+        let command = ShortcutCommand { key: key.into(), title: title.into(), default };
+        match self.commands.iter_mut().find(|known| known.key == command.key) {
+            Some(known) => *known = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// All registered commands
+    pub fn commands(&self) -> &[ShortcutCommand] {
+        &self.commands
+    }
+
+    /// The user's bindings, to be bound to the settings store
+    pub fn settings(&self) -> &Dynamic<ShortcutSettings> {
+        &self.settings
+    }
+
+    /// Current binding of `key`: the user's, or else the default
+    pub fn binding(&self, key: &str) -> Option<KeyboardShortcut> {
+        match self.settings.get().bindings.get(key) {
+            Some(text) => parse_shortcut(text),
+            None => self.commands.iter().find(|command| command.key == key)?.default,
+        }
+    }
+
+    /// Bind `key` to `shortcut`, or unbind it with `None`
+    pub fn rebind(&self, key: &str, shortcut: Option<KeyboardShortcut>) {
+        let default = self.commands.iter().find(|command| command.key == key).and_then(|command| command.default);
+        let mut settings = self.settings.get();
+        if shortcut == default {
+            settings.bindings.remove(key);
+        } else {
+            let text = shortcut.as_ref().map(shortcut_to_string).unwrap_or_default();
+            settings.bindings.insert(key.to_string(), text);
+        }
+        self.settings.set(settings);
+    }
+
+    /// Restore the default binding of `key`
+    pub fn reset(&self, key: &str) {
+        let mut settings = self.settings.get();
+        if settings.bindings.remove(key).is_some() {
+            self.settings.set(settings);
+        }
+    }
+
+    pub fn reset_all(&self) {
+        self.settings.set(ShortcutSettings::default());
+    }
+
+    /// Shortcuts bound to more than one command, with the keys of those
+    /// commands in registration order
+    pub fn conflicts(&self) -> Vec<(KeyboardShortcut, Vec<&str>)> {
+        let mut bound: Vec<(KeyboardShortcut, Vec<&str>)> = Vec::new();
+        for command in &self.commands {
+            let Some(shortcut) = self.binding(&command.key) else {
+                continue;
+            };
+            match bound.iter_mut().find(|(known, _)| *known == shortcut) {
+                Some((_, keys)) => keys.push(&command.key),
+                None => bound.push((shortcut, vec![&command.key])),
+            }
+        }
+        bound.retain(|(_, keys)| keys.len() > 1);
+        bound
+    }
+
+    /// Consume the bound shortcuts pressed this frame and return the
+    /// keys of their commands. Nothing is dispatched while a text field
+    /// has focus, which handles the keys itself, or while a shortcut is
+    /// being recorded.
+    pub fn dispatch(&self, ctx: &egui::Context) -> Vec<String> {
+        if self.capturing.is_some() || ctx.wants_keyboard_input() {
+            return Vec::new();
+        }
+        let mut bound: Vec<(KeyboardShortcut, &str)> = Vec::new();
+        for command in &self.commands {
+            if let Some(shortcut) = self.binding(&command.key) {
+                if !bound.iter().any(|(known, _)| *known == shortcut) {
+                    bound.push((shortcut, &command.key));
+                }
+            }
+        }
+        // Extra modifiers are ignored when matching, so the more
+        // specific shortcuts must be consumed first
+        bound.sort_by_key(|(shortcut, _)| Reverse(modifier_count(shortcut.modifiers)));
+        ctx.input_mut(|i| {
+            bound.iter()
+                .filter(|(shortcut, _)| i.consume_shortcut(shortcut))
+                .map(|(_, key)| key.to_string())
+                .collect()
+        })
+    }
+
+    /// Bindings of all commands. Clicking a binding records the next
+    /// assignable key press as its new shortcut, see `is_assignable`;
+    /// Backspace unbinds, Escape cancels.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(key) = self.capturing.clone() {
+            let pressed = ui.input(|i| i.events.iter().find_map(|event| match event {
+                Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                _ => None,
+            }));
+            match pressed {
+                Some((Key::Escape, _)) => self.capturing = None,
+                Some((Key::Backspace | Key::Delete, modifiers)) if modifiers.is_none() => {
+                    self.rebind(&key, None);
+                    self.capturing = None;
+                }
+                Some((pressed, modifiers)) => {
+                    let shortcut = KeyboardShortcut::new(normalize(modifiers), pressed);
+                    self.rejected = !is_assignable(&shortcut);
+                    if !self.rejected {
+                        self.rebind(&key, Some(shortcut));
+                        self.capturing = None;
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let conflicts = self.conflicts();
+        let overridden = self.settings.get().bindings;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!overridden.is_empty(), egui::Button::new(tr!("shortcuts-reset-all"))).clicked() {
+                self.reset_all();
+            }
+            if !conflicts.is_empty() {
+                ui.colored_label(ui.visuals().warn_fg_color, tr!("shortcuts-conflicts", count = conflicts.len()));
+            }
+        });
+        ui.add_space(4.0);
+
+        let mut capture = None;
+        let mut reset = None;
+        egui::Grid::new("shortcut_bindings")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for command in &self.commands {
+                    ui.label(command.title.as_str());
+
+                    let text = if self.capturing.as_deref() == Some(command.key.as_str()) {
+                        tr!(if self.rejected { "shortcuts-needs-modifier" } else { "shortcuts-press" })
+                    } else {
+                        self.binding(&command.key)
+                            .map(|shortcut| ui.ctx().format_shortcut(&shortcut))
+                            .unwrap_or_else(|| "—".to_string())
+                    };
+                    if ui.button(text)
+                        .on_hover_text(tr!("shortcuts-hint"))
+                        .clicked()
+                    {
+                        capture = Some(command.key.clone());
+                    }
+
+                    ui.horizontal(|ui| {
+                        let others: Vec<_> = conflicts.iter()
+                            .filter(|(_, keys)| keys.contains(&command.key.as_str()))
+                            .flat_map(|(_, keys)| keys.iter().filter(|key| **key != command.key))
+                            .filter_map(|key| self.commands.iter().find(|known| known.key == *key))
+                            .map(|known| known.title.as_str())
+                            .collect();
+                        if !others.is_empty() {
+                            ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                                .on_hover_text(tr!("shortcuts-also-bound", commands = others.join(", ")));
+                        }
+                        if overridden.contains_key(&command.key) && ui.small_button(tr!("button-reset")).clicked() {
+                            reset = Some(command.key.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(key) = capture {
+            self.capturing = Some(key);
+            self.rejected = false;
+        }
+        if let Some(key) = reset {
+            self.reset(&key);
+        }
+    }
+}
+
+/// ShortcutsPanel
+///
+/// Dock panel showing `ShortcutManager::ui` for the shortcuts of the
+/// app context.
+#[derive(Default)]
+pub struct ShortcutsPanel;
+
+impl Panel for ShortcutsPanel {
+    fn id(&self) -> &'static str {
+        "shortcuts"
+    }
+
+    fn title(&self) -> String {
+        tr!("panel-shortcuts")
+    }
+
+    fn icon(&self) -> &'static str {
+        "⌨"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &mut AppContext) {
+        egui::ScrollArea::vertical()
+            .id_salt("shortcut_bindings")
+            .auto_shrink([false, true])
+            .show(ui, |ui| ctx.shortcuts.ui(ui));
+    }
+}
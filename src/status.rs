@@ -0,0 +1,131 @@
+//! Status bar
+//!
+//! A strip at the bottom of the window with short indicators. The shell
+//! shows its own on the right - log counts, notifications, the current
+//! theme and whether the settings are saved - and the application adds
+//! its items on the left through the `StatusBar` of the app context,
+//! from `setup`, `on_update`, a panel or a background thread:
+//!
+//! ```
+//! use egui_mobius_template::status::{StatusBar, StatusItem};
+//!
+//! let status = StatusBar::new();
+//! status.set(StatusItem::new("clock", "🕐 12:00:00").command("panel:control"));
+//! status.set(StatusItem::new("clock", "🕐 12:00:01").command("panel:control"));
+//! assert_eq!(status.items().len(), 1);
+//! assert_eq!(status.items()[0].text, "🕐 12:00:01");
+//! ```
+//!
+//! Clicking an item with a command runs it like a palette entry, so most
+//! items open the panel showing the details, e.g. `"panel:logger"`.
+use eframe::egui;
+use egui::Color32;
+use std::sync::{Arc, Mutex};
+
+/// StatusItem
+///
+/// One indicator of the status bar, identified by `id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusItem {
+    pub id      : &'static str,
+    pub text    : String,
+    pub tooltip : Option<String>,
+    pub color   : Option<Color32>,
+    /// Command run when the item is clicked, e.g. `"panel:logger"`
+    pub command : Option<String>,
+}
+
+impl StatusItem {
+    pub fn new(id: &'static str, text: impl Into<String>) -> Self {
+        Self { id, text: text.into(), tooltip: None, color: None, command: None }
+    }
+
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// Show the item, clickable if it has a command. Returns the command
+    /// if it was clicked.
+    pub fn ui(&self, ui: &mut egui::Ui) -> Option<String> {
+        let mut text = egui::RichText::new(&self.text);
+        if let Some(color) = self.color {
+            text = text.color(color);
+        }
+        let sense = if self.command.is_some() { egui::Sense::click() } else { egui::Sense::hover() };
+        let mut response = ui.add(egui::Label::new(text).sense(sense));
+        if let Some(tooltip) = &self.tooltip {
+            response = response.on_hover_text(tooltip);
+        }
+        if self.command.is_some() {
+            response = response.on_hover_cursor(egui::CursorIcon::PointingHand);
+        }
+        if response.clicked() { self.command.clone() } else { None }
+    }
+}
+
+struct StatusInner {
+    items   : Vec<StatusItem>,
+    repaint : Option<egui::Context>,
+}
+
+/// StatusBar
+///
+/// The application's status items, in the order they were first set,
+/// shared by all clones.
+#[derive(Clone)]
+pub struct StatusBar {
+    inner: Arc<Mutex<StatusInner>>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(StatusInner { items: Vec::new(), repaint: None })),
+        }
+    }
+
+    /// Request a repaint of `ctx` whenever an item changes, so items set
+    /// from other threads show up immediately
+    pub fn with_repaint(self, ctx: &egui::Context) -> Self {
+        self.inner.lock().unwrap().repaint = Some(ctx.clone());
+        self
+    }
+
+    /// Add `item`, or replace the item with the same id in place
+    pub fn set(&self, item: StatusItem) {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.items.iter_mut().find(|known| known.id == item.id) {
+            Some(known) if *known == item => return,
+            Some(known) => *known = item,
+            None => inner.items.push(item),
+        }
+        if let Some(ctx) = &inner.repaint {
+            ctx.request_repaint();
+        }
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.inner.lock().unwrap().items.retain(|item| item.id != id);
+    }
+
+    pub fn items(&self) -> Vec<StatusItem> {
+        self.inner.lock().unwrap().items.clone()
+    }
+}
@@ -0,0 +1,178 @@
+//! Tabs of the shell's dock
+//!
+//! `Tabs` holds the dock's `PanelTab`s and applies the `TabAction`s
+//! requested from a tab's context menu, the add-tab popup, the View menu
+//! or a command. A closed tab comes back by showing its panel again, a
+//! panel that does not allow duplicates is focused instead of opened a
+//! second time, and any tab can be moved into a new split of its leaf.
+//!
+//! `add_popup` and `context_menu` render those menus; the shell's tab
+//! viewer shows them for egui_dock, which hands out the leaf and tab.
+use crate::panel::{PanelRegistry, PanelTab};
+use crate::tr;
+use eframe::egui;
+use egui_dock::{DockState, Node, NodeIndex, Split, SurfaceIndex};
+
+/// TabAction
+///
+/// Change of the dock requested from a tab's context menu, the add-tab
+/// popup or a command. The dock cannot be changed while it is shown, so
+/// the actions are applied after `DockArea::show` returns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TabAction {
+    /// Open the panel in the given leaf, or the focused one. A panel
+    /// that may exist only once is focused instead if it is open.
+    Open(&'static str, Option<(SurfaceIndex, NodeIndex)>),
+    /// Focus a tab of the panel, opening one if none is open
+    Show(&'static str),
+    /// Open another tab of the same panel next to the tab `instance`
+    Duplicate(usize),
+    /// Move the tab `instance` into a new split of its leaf
+    Split(usize, Split),
+    /// Replace the layout with the default one
+    ResetLayout,
+}
+
+/// Tabs
+///
+/// The dock of the shell and the layout it is reset to.
+pub struct Tabs {
+    pub dock       : DockState<PanelTab>,
+    default_layout : DockState<&'static str>,
+}
+
+impl Tabs {
+    /// Tabs laid out as `default_layout`, whose tabs are panel ids.
+    /// Ids that are not registered are left out.
+    pub fn new(default_layout: DockState<&'static str>, panels: &PanelRegistry) -> Self {
+        let dock = default_layout.filter_map_tabs(|id| panels.create(id));
+        Self { dock, default_layout }
+    }
+
+    /// Ids of the panels of all open tabs
+    pub fn open_panels(&self) -> Vec<&'static str> {
+        self.dock.iter_all_tabs().map(|(_, tab)| tab.id()).collect()
+    }
+
+    /// Instances of the tabs that are alone in their leaf, which have
+    /// nothing to split from
+    pub fn lonely_tabs(&self) -> Vec<usize> {
+        self.dock.iter_leaves()
+            .filter(|(_, leaf)| leaf.tabs().len() == 1)
+            .map(|(_, leaf)| leaf.tabs()[0].instance())
+            .collect()
+    }
+
+    /// Make the first tab of panel `id` the active, focused one.
+    /// Returns false if no tab of it is open.
+    pub fn focus(&mut self, id: &str) -> bool {
+        match self.dock.find_tab_from(|tab| tab.id() == id) {
+            Some((surface, node, tab)) => {
+                self.dock.set_active_tab((surface, node, tab));
+                self.dock.set_focused_node_and_surface((surface, node));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply `action`, creating new tabs from `panels`
+    pub fn apply(&mut self, panels: &PanelRegistry, action: TabAction) {
+        match action {
+            TabAction::Open(id, leaf) => {
+                let duplicates = panels.info(id).is_some_and(|info| info.allows_duplicates);
+                if duplicates || !self.focus(id) {
+                    self.open(panels, id, leaf);
+                }
+            }
+            TabAction::Show(id) => {
+                if !self.focus(id) {
+                    self.open(panels, id, None);
+                }
+            }
+            TabAction::Duplicate(instance) => {
+                let found = self.dock.iter_all_tabs()
+                    .find(|(_, tab)| tab.instance() == instance)
+                    .filter(|(_, tab)| tab.allows_duplicates())
+                    .map(|(leaf, tab)| (leaf, tab.id()));
+                if let Some((leaf, id)) = found {
+                    self.open(panels, id, Some(leaf));
+                }
+            }
+            TabAction::Split(instance, split) => {
+                let Some((surface, node, index)) = self.dock.find_tab_from(|tab| tab.instance() == instance) else {
+                    return;
+                };
+                if self.dock[surface][node].tabs_count() < 2 {
+                    return;
+                }
+                if let Some(tab) = self.dock.remove_tab((surface, node, index)) {
+                    self.dock.split((surface, node), split, 0.5, Node::leaf(tab));
+                }
+            }
+            TabAction::ResetLayout => {
+                self.dock = self.default_layout.filter_map_tabs(|id| panels.create(id));
+            }
+        }
+    }
+
+    fn open(&mut self, panels: &PanelRegistry, id: &str, leaf: Option<(SurfaceIndex, NodeIndex)>) {
+        let Some(tab) = panels.create(id) else {
+            return;
+        };
+        if let Some(leaf) = leaf {
+            self.dock.set_focused_node_and_surface(leaf);
+        }
+        self.dock.push_to_focused_leaf(tab);
+    }
+}
+
+/// Show every panel of `panels` for opening it in `leaf`. Panels in
+/// `open_panels` that may exist only once explain that they will be
+/// focused instead.
+pub fn add_popup(
+    ui: &mut egui::Ui,
+    panels: &PanelRegistry,
+    open_panels: &[&'static str],
+    leaf: (SurfaceIndex, NodeIndex),
+    actions: &mut Vec<TabAction>,
+) {
+    ui.set_min_width(120.0);
+    for panel in panels.panels() {
+        let mut response = ui.selectable_label(false, panel.label());
+        if !panel.allows_duplicates && open_panels.contains(&panel.id) {
+            response = response.on_hover_text(tr!("tab-already-open"));
+        }
+        if response.clicked() {
+            actions.push(TabAction::Open(panel.id, Some(leaf)));
+            ui.close();
+        }
+    }
+}
+
+/// Show the entries egui_dock's tab context menu gets besides "Close":
+/// duplicating `tab` if its panel allows it and moving it into a new
+/// split unless it is alone in its leaf (`lonely`)
+pub fn context_menu(ui: &mut egui::Ui, tab: &PanelTab, lonely: bool, actions: &mut Vec<TabAction>) {
+    let instance = tab.instance();
+    if ui.add_enabled(tab.allows_duplicates(), egui::Button::new(tr!("tab-duplicate"))).clicked() {
+        actions.push(TabAction::Duplicate(instance));
+        ui.close();
+    }
+    ui.add_enabled_ui(!lonely, |ui| {
+        ui.menu_button(tr!("tab-move-to-split"), |ui| {
+            for (label, split) in [
+                ("tab-split-right", Split::Right),
+                ("tab-split-below", Split::Below),
+                ("tab-split-left", Split::Left),
+                ("tab-split-above", Split::Above),
+            ] {
+                if ui.button(tr!(label)).clicked() {
+                    actions.push(TabAction::Split(instance, split));
+                    ui.close();
+                }
+            }
+        });
+    });
+    ui.separator();
+}
@@ -0,0 +1,449 @@
+//! Headless UI tests
+//!
+//! `Harness` runs frames of a synthetic `egui::Context` without a window
+//! or GPU, feeds them clicks, typing and keys aimed at the text shown
+//! by the previous frame, and renders what was painted as plain text
+//! for snapshot comparison. `TestApp` holds the shell's services, so
+//! panels can be shown with a real `AppContext`:
+//!
+//! ```
+//! use egui_mobius_template::testing::{Harness, TestApp};
+//!
+//! let mut harness = Harness::new();
+//! let mut app = TestApp::new(harness.ctx());
+//! let mut clicks = 0;
+//!
+//! harness.run(|ui| {
+//!     if ui.button("Count").clicked() {
+//!         clicks += 1;
+//!     }
+//!     ui.label(format!("{} log entries", app.terminal.logs.get().len()));
+//! });
+//! harness.click("Count");
+//! harness.run(|ui| {
+//!     if ui.button("Count").clicked() {
+//!         clicks += 1;
+//!     }
+//! });
+//! assert_eq!(clicks, 1);
+//! assert!(harness.contains("Count"));
+//! ```
+//!
+//! Snapshots are kept in `tests/snapshots` of the package under test, see
+//! `Harness::assert_snapshot`.
+use crate::panel::{AppContext, Panel, Services};
+use crate::recorder::Recorder;
+use crate::settings::SettingsStore;
+use crate::themes::ThemeManager;
+use crate::{LogColors, TerminalWidget};
+use eframe::egui;
+use egui::epaint::{ClippedShape, Shape};
+use egui::{Color32, Event, Key, Modifiers, Pos2, Rect, Vec2};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Size of the screen unless set with `Harness::with_size`
+pub const DEFAULT_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+
+/// Frames `Harness::run` runs at most while the UI keeps asking for a
+/// repaint, e.g. during animations
+pub const MAX_FRAMES: usize = 60;
+
+/// Environment variable that makes `assert_snapshot` rewrite snapshots
+/// instead of comparing against them
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+const FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// Harness
+///
+/// A headless `egui::Context` showing one UI closure per frame inside a
+/// central panel. Input queued by `click`, `type_text` and `press_key`
+/// is delivered over the following frames of the next `run`.
+pub struct Harness {
+    ctx       : egui::Context,
+    size      : Vec2,
+    time      : f64,
+    modifiers : Modifiers,
+    /// Events of the coming frames, one entry per frame
+    queue     : VecDeque<Vec<Event>>,
+    /// Shapes painted by the last frame
+    shapes    : Vec<ClippedShape>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            size: DEFAULT_SIZE,
+            time: 0.0,
+            modifiers: Modifiers::NONE,
+            queue: VecDeque::new(),
+            shapes: Vec::new(),
+        }
+    }
+
+    /// Size of the screen in points
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.size = Vec2::new(width, height);
+        self
+    }
+
+    /// The context the frames run in, e.g. for a `TerminalWidget`
+    pub fn ctx(&self) -> &egui::Context {
+        &self.ctx
+    }
+
+    /// Run frames showing `app` until the queued input is delivered and
+    /// the UI stops asking for a repaint, at most `MAX_FRAMES` after the
+    /// input. Always runs at least one frame.
+    pub fn run(&mut self, mut app: impl FnMut(&mut egui::Ui)) {
+        let mut idle_frames = 0;
+        loop {
+            let input = self.queue.pop_front();
+            let delivering = input.is_some();
+            let repaint = self.frame(input.unwrap_or_default(), &mut app);
+            if !delivering {
+                idle_frames += 1;
+            }
+            if self.queue.is_empty() && (!repaint || idle_frames >= MAX_FRAMES) {
+                break;
+            }
+        }
+    }
+
+    /// Show `panel` with the services of `app`, see `run`. Like the
+    /// shell, each frame polls `app.services.recorder` first, so a replay runs to
+    /// its end; replayed shell commands are not run.
+    pub fn run_panel(&mut self, panel: &mut dyn Panel, app: &mut TestApp) {
+        self.run(|ui| {
+            app.services.recorder.poll();
+            panel.ui(ui, &mut app.context());
+        });
+    }
+
+    /// Run a single frame. Returns whether a repaint was requested.
+    fn frame(&mut self, events: Vec<Event>, app: &mut impl FnMut(&mut egui::Ui)) -> bool {
+        let input = egui::RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, self.size)),
+            time: Some(self.time),
+            predicted_dt: FRAME_TIME as f32,
+            modifiers: self.modifiers,
+            events,
+            ..Default::default()
+        };
+        self.time += FRAME_TIME;
+        let output = self.ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| app(ui));
+        });
+        self.shapes = output.shapes;
+        output
+            .viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .is_some_and(|viewport| viewport.repaint_delay.is_zero())
+    }
+
+    /// Modifiers held during the coming input, e.g. `Modifiers::SHIFT`
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Move the pointer over the text `text`. Panics if the last frame
+    /// did not show it.
+    #[track_caller]
+    pub fn hover(&mut self, text: &str) {
+        let pos = self.expect(text).center();
+        self.queue.push_back(vec![Event::PointerMoved(pos)]);
+    }
+
+    /// Click the text `text`, e.g. the label of a button. Panics if the
+    /// last frame did not show it.
+    #[track_caller]
+    pub fn click(&mut self, text: &str) {
+        let pos = self.expect(text).center();
+        self.click_at(pos);
+    }
+
+    /// Click at `pos`, e.g. next to a label
+    pub fn click_at(&mut self, pos: Pos2) {
+        let button = |pressed| Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: self.modifiers,
+        };
+        self.queue.push_back(vec![Event::PointerMoved(pos)]);
+        self.queue.push_back(vec![button(true)]);
+        self.queue.push_back(vec![button(false)]);
+    }
+
+    /// Type `text` into the focused widget
+    pub fn type_text(&mut self, text: &str) {
+        self.queue.push_back(vec![Event::Text(text.to_string())]);
+    }
+
+    /// Press and release `key`
+    pub fn press_key(&mut self, key: Key) {
+        let event = |pressed| Event::Key {
+            key,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: self.modifiers,
+        };
+        self.queue.push_back(vec![event(true)]);
+        self.queue.push_back(vec![event(false)]);
+    }
+
+    /// Screen rectangles of the visible texts equal to `text`, in
+    /// painting order
+    pub fn find_all(&self, text: &str) -> Vec<Rect> {
+        self.texts()
+            .into_iter()
+            .filter(|(shown, _)| shown.trim() == text.trim())
+            .map(|(_, rect)| rect)
+            .collect()
+    }
+
+    /// Screen rectangle of the first visible text equal to `text`
+    pub fn find(&self, text: &str) -> Option<Rect> {
+        self.find_all(text).into_iter().next()
+    }
+
+    /// Whether the last frame showed a text containing `text`
+    pub fn contains(&self, text: &str) -> bool {
+        self.texts().iter().any(|(shown, _)| shown.contains(text))
+    }
+
+    #[track_caller]
+    fn expect(&self, text: &str) -> Rect {
+        match self.find(text) {
+            Some(rect) => rect,
+            None => {
+                let shown: Vec<_> = self.texts().into_iter().map(|(shown, _)| shown).collect();
+                panic!("{:?} is not shown, the last frame showed {:?}", text, shown)
+            }
+        }
+    }
+
+    /// Every visible text of the last frame with its screen rectangle
+    pub fn texts(&self) -> Vec<(String, Rect)> {
+        let mut texts = Vec::new();
+        for clipped in &self.shapes {
+            visit(&clipped.shape, &mut |shape| {
+                if let Shape::Text(text) = shape {
+                    let rect = text.visual_bounding_rect();
+                    if clipped.clip_rect.intersects(rect) {
+                        texts.push((text.galley.text().to_string(), rect));
+                    }
+                }
+            });
+        }
+        texts
+    }
+
+    /// What the last frame painted, one visible text or rectangle per
+    /// line with its position, size and color, other shapes by kind.
+    /// Coordinates are rounded to whole points.
+    pub fn snapshot(&self) -> String {
+        let mut out = String::new();
+        for clipped in &self.shapes {
+            visit(&clipped.shape, &mut |shape| {
+                let bounds = shape.visual_bounding_rect();
+                if !bounds.is_positive() && !matches!(shape, Shape::Text(_)) || !clipped.clip_rect.intersects(bounds) {
+                    return;
+                }
+                let line = match shape {
+                    Shape::Text(text) => {
+                        let section = text.galley.job.sections.first().map(|section| section.format.color);
+                        let color = text.override_text_color.unwrap_or(match section {
+                            Some(color) if color != Color32::PLACEHOLDER => color,
+                            _ => text.fallback_color,
+                        });
+                        format!("text {:?} {} {}", text.galley.text(), rect(bounds), hex(color))
+                    }
+                    Shape::Rect(rect_shape) => {
+                        if rect_shape.fill.a() == 0 && rect_shape.stroke.is_empty() {
+                            return;
+                        }
+                        let mut line = format!("rect {}", rect(rect_shape.rect));
+                        if rect_shape.fill.a() > 0 {
+                            let _ = write!(line, " fill {}", hex(rect_shape.fill));
+                        }
+                        if !rect_shape.stroke.is_empty() {
+                            let _ = write!(line, " stroke {}", hex(rect_shape.stroke.color));
+                        }
+                        line
+                    }
+                    Shape::Circle(_) | Shape::Ellipse(_) => format!("circle {}", rect(bounds)),
+                    Shape::LineSegment { .. } => format!("line {}", rect(bounds)),
+                    Shape::Path(path) => format!("path {} points {}", path.points.len(), rect(bounds)),
+                    Shape::Mesh(mesh) => format!("mesh {} vertices {}", mesh.vertices.len(), rect(bounds)),
+                    Shape::QuadraticBezier(_) | Shape::CubicBezier(_) => format!("curve {}", rect(bounds)),
+                    Shape::Callback(_) => format!("callback {}", rect(bounds)),
+                    Shape::Noop | Shape::Vec(_) => return,
+                };
+                out.push_str(&line);
+                out.push('\n');
+            });
+        }
+        out
+    }
+
+    /// Compare `snapshot()` with `tests/snapshots/<name>.txt` of the
+    /// package under test. A missing or different snapshot fails,
+    /// writing the new output next to it as `<name>.new.txt`. Set
+    /// `UPDATE_SNAPSHOTS=1` to record or accept it.
+    #[track_caller]
+    pub fn assert_snapshot(&self, name: &str) {
+        assert_snapshot(name, &self.snapshot());
+    }
+}
+
+/// Call `f` with every shape in `shape`, flattening `Shape::Vec`
+fn visit(shape: &Shape, f: &mut impl FnMut(&Shape)) {
+    match shape {
+        Shape::Vec(shapes) => shapes.iter().for_each(|shape| visit(shape, f)),
+        shape => f(shape),
+    }
+}
+
+fn rect(rect: Rect) -> String {
+    let round = |value: f32| value.round() as i32;
+    format!(
+        "{},{} {}x{}",
+        round(rect.min.x),
+        round(rect.min.y),
+        round(rect.width()),
+        round(rect.height())
+    )
+}
+
+fn hex(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+/// Directory of the snapshots of the package under test
+pub fn snapshot_dir() -> PathBuf {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from);
+    root.join("tests").join("snapshots")
+}
+
+/// Compare `actual` with the snapshot `name`, see
+/// `Harness::assert_snapshot`
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let dir = snapshot_dir();
+    let path = dir.join(format!("{}.txt", name));
+    let new_path = dir.join(format!("{}.new.txt", name));
+    let update = std::env::var_os(UPDATE_SNAPSHOTS).is_some_and(|value| value != "0");
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => Some(expected),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => panic!("failed to read snapshot {}: {}", path.display(), e),
+    };
+    if expected.as_deref() == Some(actual) {
+        std::fs::remove_file(&new_path).ok();
+        return;
+    }
+    if update {
+        write_snapshot(&path, actual);
+        std::fs::remove_file(&new_path).ok();
+        return;
+    }
+
+    write_snapshot(&new_path, actual);
+    let Some(expected) = expected else {
+        panic!(
+            "snapshot {} does not exist, the output is in {}, rerun with {}=1 to record it",
+            path.display(),
+            new_path.display(),
+            UPDATE_SNAPSHOTS,
+        );
+    };
+    let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a)
+        .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+    panic!(
+        "snapshot {} differs from line {}:\n  expected: {}\n  actual:   {}\n\
+         the new output is in {}, rerun with {}=1 to accept it",
+        path.display(),
+        line + 1,
+        expected.lines().nth(line).unwrap_or("<end>"),
+        actual.lines().nth(line).unwrap_or("<end>"),
+        new_path.display(),
+        UPDATE_SNAPSHOTS,
+    );
+}
+
+fn write_snapshot(path: &Path, contents: &str) {
+    if let Err(e) = crate::persistence::write_atomic(path, contents.as_bytes()) {
+        panic!("failed to write snapshot {}: {}", path.display(), e);
+    }
+}
+
+/// TestApp
+///
+/// The shell's services for panels under test, with the settings and
+/// themes in a fresh temporary directory that is removed on drop.
+pub struct TestApp {
+    pub terminal : TerminalWidget,
+    pub services : Services,
+    dir          : PathBuf,
+}
+
+impl TestApp {
+    /// Services repainting `ctx`, usually `Harness::ctx`
+    pub fn new(ctx: &egui::Context) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "mobius_test_app_{}_{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let (themes, _) = ThemeManager::new(dir.join("themes"));
+        Self {
+            terminal: TerminalWidget::new(ctx.clone(), LogColors::default()),
+            services: Services {
+                recorder: Recorder::new().with_repaint(ctx),
+                ..Services::new(SettingsStore::new(dir.join("settings.json")), themes)
+            },
+            dir,
+        }
+    }
+
+    /// Add `value` to the resources
+    pub fn with<T: 'static>(mut self, value: T) -> Self {
+        self.services.resources.insert(value);
+        self
+    }
+
+    /// The services as handed to panels
+    pub fn context(&mut self) -> AppContext<'_> {
+        AppContext::new(&mut self.terminal, &mut self.services)
+    }
+
+    /// The log messages, oldest first
+    pub fn logs(&self) -> Vec<String> {
+        self.terminal.logs.get().into_iter().map(|(msg, _)| msg).collect()
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
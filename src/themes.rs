@@ -0,0 +1,398 @@
+//! Theme files for sharing log color schemes
+//!
+//! A theme is a small JSON file holding a name and a complete set of
+//! `LogColors`. Colors may be written in any format accepted by
+//! `colors::parse_color`, so themes can be authored by hand:
+//!
+//! ```json
+//! {
+//!   "name": "House Style",
+//!   "log_colors": { "clock": "#64c8ff", "slider": "orange", ... }
+//! }
+//! ```
+//!
+//! Themes placed in `themes_dir()` are picked up by `ThemeManager`,
+//! which also provides the export/import/preview UI of the settings
+//! panel. An installed theme is never replaced without asking: the UI
+//! offers to replace it or to keep both under a new name.
+use crate::persistence::{config_dir, read_json, write_json, PersistError};
+use crate::{tr, LogColors};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory scanned for theme files
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+/// ThemeError
+///
+/// Errors raised while reading, validating or writing theme files.
+#[derive(Debug)]
+pub enum ThemeError {
+    /// The file could not be read, parsed or written
+    Persist(PersistError),
+    /// The file parsed but is not a usable theme
+    Invalid { path: PathBuf, reason: String },
+    /// A theme is already installed under the same file name
+    Exists { path: PathBuf, name: String },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Persist(e) => write!(f, "{}", e),
+            ThemeError::Invalid { path, reason } => {
+                write!(f, "invalid theme {}: {}", path.display(), reason)
+            }
+            ThemeError::Exists { path, name } => {
+                write!(f, "a theme named \"{}\" already exists at {}", name, path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ThemeError::Persist(e) => Some(e),
+            ThemeError::Invalid { .. } | ThemeError::Exists { .. } => None,
+        }
+    }
+}
+
+impl From<PersistError> for ThemeError {
+    fn from(e: PersistError) -> Self {
+        ThemeError::Persist(e)
+    }
+}
+
+/// Theme
+///
+/// A named set of log colors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub name       : String,
+    pub log_colors : LogColors,
+}
+
+impl Theme {
+    pub fn new(name: impl Into<String>, log_colors: LogColors) -> Self {
+        Self { name: name.into(), log_colors }
+    }
+
+    /// Read and validate a theme file
+    pub fn load(path: &Path) -> Result<Self, ThemeError> {
+        let theme: Theme = read_json(path)?;
+        theme.validate(path)?;
+        Ok(theme)
+    }
+
+    /// Write the theme to `<dir>/<name>.json` and return the path.
+    /// Fails with `ThemeError::Exists` if that file exists, unless
+    /// `replace` is set.
+    pub fn export(&self, dir: &Path, replace: bool) -> Result<PathBuf, ThemeError> {
+        let path = self.path_in(dir);
+        self.validate(&path)?;
+        if !replace && path.exists() {
+            return Err(ThemeError::Exists { path, name: self.name.clone() });
+        }
+        write_json(&path, self)?;
+        Ok(path)
+    }
+
+    /// File name derived from the theme name, safe on every platform
+    pub fn file_stem(&self) -> String {
+        self.name
+            .trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c.to_ascii_lowercase() } else { '_' })
+            .collect()
+    }
+
+    /// Path of the theme file in `dir`
+    pub fn path_in(&self, dir: &Path) -> PathBuf {
+        dir.join(format!("{}.json", self.file_stem()))
+    }
+
+    fn validate(&self, path: &Path) -> Result<(), ThemeError> {
+        let invalid = |reason: String| ThemeError::Invalid { path: path.to_path_buf(), reason };
+        if self.name.trim().is_empty() {
+            return Err(invalid("the theme has no name".to_string()));
+        }
+        if let Some((entry, _)) = self.log_colors.entries().iter().find(|(_, c)| c.a() == 0) {
+            return Err(invalid(format!("{} is fully transparent", tr!(entry))));
+        }
+        Ok(())
+    }
+}
+
+/// Read every `*.json` theme in `dir`, sorted by name. Files that fail
+/// to load are returned as errors next to the valid themes.
+pub fn scan(dir: &Path) -> (Vec<(PathBuf, Theme)>, Vec<ThemeError>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return (themes, errors),
+        Err(source) => {
+            errors.push(PersistError::Io { path: dir.to_path_buf(), source }.into());
+            return (themes, errors);
+        }
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        match Theme::load(&path) {
+            Ok(theme) => themes.push((path, theme)),
+            Err(e) => errors.push(e),
+        }
+    }
+    themes.sort_by_key(|(_, theme)| theme.name.to_lowercase());
+    (themes, errors)
+}
+
+/// OnConflict
+///
+/// What to do when a theme is imported or exported under the file name
+/// of an installed theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Fail with `ThemeError::Exists`
+    Reject,
+    /// Overwrite the installed theme
+    Replace,
+    /// Install it under a new name, e.g. "Solar (2)"
+    KeepBoth,
+}
+
+/// ThemeEvent
+///
+/// Result of user interaction with the `ThemeManager` UI.
+#[derive(Debug)]
+pub enum ThemeEvent {
+    /// The previewed theme was applied
+    Applied(Theme),
+    /// The current colors were exported to a theme file
+    Exported(PathBuf),
+    /// A theme file was imported into the theme directory
+    Imported(PathBuf),
+    /// An export, import or rescan failed
+    Failed(ThemeError),
+}
+
+/// ThemeManager
+///
+/// Keeps the themes found in the theme directory and renders the
+/// export / import / preview UI.
+pub struct ThemeManager {
+    dir         : PathBuf,
+    themes      : Vec<(PathBuf, Theme)>,
+    export_name : String,
+    import_path : String,
+    preview     : Option<Theme>,
+    /// Import (`true`) or export waiting for the user to resolve a
+    /// name conflict
+    conflict    : Option<(Theme, bool)>,
+}
+
+impl ThemeManager {
+    /// Create a manager for `dir` and scan it. Themes that failed to
+    /// load are returned alongside.
+    pub fn new(dir: impl Into<PathBuf>) -> (Self, Vec<ThemeError>) {
+        let mut manager = Self {
+            dir: dir.into(),
+            themes: Vec::new(),
+            export_name: String::new(),
+            import_path: String::new(),
+            preview: None,
+            conflict: None,
+        };
+        let errors = manager.rescan();
+        (manager, errors)
+    }
+
+    /// Scan the theme directory again
+    pub fn rescan(&mut self) -> Vec<ThemeError> {
+        let (themes, errors) = scan(&self.dir);
+        self.themes = themes;
+        errors
+    }
+
+    /// The themes found by the last scan
+    pub fn themes(&self) -> &[(PathBuf, Theme)] {
+        &self.themes
+    }
+
+    /// The theme whose colors are exactly `colors`, if any
+    pub fn matching(&self, colors: &LogColors) -> Option<&Theme> {
+        self.themes.iter().map(|(_, theme)| theme).find(|theme| &theme.log_colors == colors)
+    }
+
+    /// Copy the theme file at `path` into the theme directory after
+    /// validating it, and select it for preview
+    pub fn import(&mut self, path: &Path, on_conflict: OnConflict) -> Result<PathBuf, ThemeError> {
+        let theme = Theme::load(path)?;
+        let (imported, theme) = self.install(theme, on_conflict)?;
+        self.preview = Some(theme);
+        Ok(imported)
+    }
+
+    /// Write `theme` to the theme directory
+    pub fn export(&mut self, theme: Theme, on_conflict: OnConflict) -> Result<PathBuf, ThemeError> {
+        self.install(theme, on_conflict).map(|(path, _)| path)
+    }
+
+    /// `name`, or the first of "name (2)", "name (3)", … whose file is
+    /// not taken in the theme directory
+    pub fn unique_name(&self, name: &str) -> String {
+        let name = name.trim();
+        let taken = |candidate: &str| Theme::new(candidate, LogColors::default()).path_in(&self.dir).exists();
+        if !taken(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|n| format!("{} ({})", name, n))
+            .find(|candidate| !taken(candidate))
+            .expect("unbounded range")
+    }
+
+    fn install(&mut self, mut theme: Theme, on_conflict: OnConflict) -> Result<(PathBuf, Theme), ThemeError> {
+        if on_conflict == OnConflict::KeepBoth {
+            theme.name = self.unique_name(&theme.name);
+        }
+        let path = theme.export(&self.dir, on_conflict == OnConflict::Replace)?;
+        self.rescan();
+        Ok((path, theme))
+    }
+
+    /// Install a theme imported or exported from the UI, holding name
+    /// conflicts back until the user resolved them
+    fn install_from_ui(&mut self, theme: Theme, import: bool, on_conflict: OnConflict) -> Option<ThemeEvent> {
+        match self.install(theme.clone(), on_conflict) {
+            Ok((path, installed)) if import => {
+                self.import_path.clear();
+                self.preview = Some(installed);
+                Some(ThemeEvent::Imported(path))
+            }
+            Ok((path, _)) => {
+                self.export_name.clear();
+                Some(ThemeEvent::Exported(path))
+            }
+            Err(ThemeError::Exists { .. }) => {
+                self.conflict = Some((theme, import));
+                None
+            }
+            Err(e) => Some(ThemeEvent::Failed(e)),
+        }
+    }
+
+    /// Render the theme UI for the colors currently in use
+    pub fn ui(&mut self, ui: &mut egui::Ui, current: &LogColors) -> Option<ThemeEvent> {
+        let mut event = None;
+
+        // Export
+        ui.horizontal(|ui| {
+            ui.label(tr!("themes-export-as"));
+            ui.add(egui::TextEdit::singleline(&mut self.export_name).hint_text(tr!("themes-name-hint")).desired_width(140.0));
+            if ui.add_enabled(!self.export_name.trim().is_empty(), egui::Button::new(tr!("themes-export"))).clicked() {
+                let theme = Theme::new(self.export_name.trim(), current.clone());
+                event = self.install_from_ui(theme, false, OnConflict::Reject);
+            }
+        });
+
+        // Import
+        ui.horizontal(|ui| {
+            ui.label(tr!("themes-import-file"));
+            ui.add(egui::TextEdit::singleline(&mut self.import_path).hint_text("/path/to/theme.json").desired_width(140.0));
+            if ui.add_enabled(!self.import_path.trim().is_empty(), egui::Button::new(tr!("themes-import"))).clicked() {
+                let path = PathBuf::from(self.import_path.trim());
+                event = match Theme::load(&path) {
+                    Ok(theme) => self.install_from_ui(theme, true, OnConflict::Reject),
+                    Err(e) => Some(ThemeEvent::Failed(e)),
+                };
+            }
+        });
+
+        // Confirmation before an installed theme is replaced
+        if let Some((theme, import)) = self.conflict.clone() {
+            let mut choice = None;
+            egui::Frame::group(ui.style()).show(ui, |ui| {
+                ui.colored_label(ui.visuals().warn_fg_color, tr!("themes-exists", name = theme.name.as_str()));
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("themes-replace")).clicked() {
+                        choice = Some(Some(OnConflict::Replace));
+                    }
+                    if ui.button(tr!("themes-keep-both", name = self.unique_name(&theme.name))).clicked() {
+                        choice = Some(Some(OnConflict::KeepBoth));
+                    }
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        choice = Some(None);
+                    }
+                });
+            });
+            if let Some(choice) = choice {
+                self.conflict = None;
+                if let Some(on_conflict) = choice {
+                    event = self.install_from_ui(theme, import, on_conflict);
+                }
+            }
+        }
+
+        // Installed themes
+        ui.horizontal(|ui| {
+            ui.label(tr!("themes-installed"));
+            let selected = self.preview.as_ref().map(|t| t.name.clone()).unwrap_or_else(|| tr!("themes-choose"));
+            egui::ComboBox::from_id_salt("installed_themes")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    if self.themes.is_empty() {
+                        ui.label(tr!("themes-none", dir = self.dir.display().to_string()));
+                    }
+                    for (_, theme) in &self.themes {
+                        if ui.selectable_label(self.preview.as_ref() == Some(theme), &theme.name).clicked() {
+                            self.preview = Some(theme.clone());
+                        }
+                    }
+                });
+            if ui.button("⟳").on_hover_text(tr!("themes-rescan")).clicked() {
+                if let Some(error) = self.rescan().into_iter().next() {
+                    event = Some(ThemeEvent::Failed(error));
+                }
+            }
+        });
+
+        // Preview of the selected theme before it is applied
+        if let Some(theme) = &self.preview {
+            let mut close = false;
+            egui::Frame::group(ui.style())
+                .fill(ui.visuals().panel_fill)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new(tr!("themes-preview", name = theme.name.as_str())).strong());
+                    for (name, color) in theme.log_colors.entries() {
+                        ui.label(egui::RichText::new(tr!("themes-sample", name = tr!(name))).monospace().color(color));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("button-apply")).clicked() {
+                            event = Some(ThemeEvent::Applied(theme.clone()));
+                            close = true;
+                        }
+                        if ui.button(tr!("button-cancel")).clicked() {
+                            close = true;
+                        }
+                    });
+                });
+            if close {
+                self.preview = None;
+            }
+        }
+
+        event
+    }
+}
@@ -0,0 +1,351 @@
+//! Batched updates of several `Dynamic` values
+//!
+//! A `Transaction` generalizes `Latch` to any number of `Dynamic`s:
+//! writes are staged in the transaction and only applied when the
+//! closure that received it returns `Ok`. If it returns `Err`, nothing
+//! is written. Staging the same value several times coalesces into a
+//! single `set`, values equal to the current one are not written at all,
+//! and any number of repaint requests results in one repaint.
+//! `Transaction::nested` runs part of a transaction that can fail on
+//! its own: its writes are kept if it returns `Ok` and dropped otherwise.
+//!
+//! Every applied write still notifies the subscribers of its `Dynamic`
+//! once. `Derived` values that combine several `Dynamic`s should depend
+//! on a `ReactiveScope` instead: the scope forwards changes of its
+//! members, but a transaction committed through it notifies the scope's
+//! subscribers exactly once, however many members it changed.
+//!
+//! ```
+//! use egui_mobius_reactive::{Derived, Dynamic, ReactiveValue};
+//! use egui_mobius_template::transaction::ReactiveScope;
+//! use std::sync::Arc;
+//!
+//! let width = Dynamic::new(2);
+//! let height = Dynamic::new(3);
+//! let scope = ReactiveScope::new().with(&width).with(&height);
+//!
+//! let (w, h) = (width.clone(), height.clone());
+//! let area = Derived::new(&[Arc::new(scope.clone()) as Arc<dyn ReactiveValue>], move || w.get() * h.get());
+//!
+//! // Both values change, `area` is recomputed once
+//! scope.transaction(|tx| {
+//!     tx.set(&width, 4);
+//!     tx.update(&height, |h| *h += 2);
+//!     Ok::<_, ()>(())
+//! }).unwrap();
+//! assert_eq!(area.get(), 20);
+//!
+//! // An error rolls back every staged write
+//! let result: Result<(), &str> = scope.transaction(|tx| {
+//!     tx.set(&width, 100);
+//!     Err("invalid size")
+//! });
+//! assert!(result.is_err());
+//! assert_eq!(width.get(), 4);
+//! ```
+use eframe::egui;
+use egui_mobius_reactive::{Dynamic, ReactiveValue};
+use std::any::Any;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Identity of a `Dynamic`: the address of the value behind its lock,
+/// shared by all clones and stable for as long as one of them lives
+pub(crate) fn dynamic_id<T>(dynamic: &Dynamic<T>) -> usize {
+    &*dynamic.lock() as *const T as usize
+}
+
+type Subscriber = Box<dyn Fn() + Send + Sync>;
+
+struct Member {
+    id         : usize,
+    /// Notifications caused by committed transactions that have not
+    /// arrived yet and must not be forwarded
+    suppressed : usize,
+}
+
+#[derive(Default)]
+struct ScopeInner {
+    members     : Mutex<Vec<Member>>,
+    subscribers : Mutex<Vec<Subscriber>>,
+}
+
+impl ScopeInner {
+    fn member_changed(&self, id: usize) {
+        {
+            let mut members = self.members.lock().unwrap();
+            if let Some(member) = members.iter_mut().find(|m| m.id == id) {
+                if member.suppressed > 0 {
+                    member.suppressed -= 1;
+                    return;
+                }
+            }
+        }
+        self.notify();
+    }
+
+    fn notify(&self) {
+        for subscriber in self.subscribers.lock().unwrap().iter() {
+            subscriber();
+        }
+    }
+}
+
+/// ReactiveScope
+///
+/// A reactive value that changes whenever one of its member `Dynamic`s
+/// changes, except that all changes made by one transaction are
+/// reported as a single change.
+#[derive(Clone, Default)]
+pub struct ReactiveScope {
+    inner: Arc<ScopeInner>,
+}
+
+impl ReactiveScope {
+    /// Create an empty scope
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `dynamic` as a member of the scope
+    pub fn with<T>(self, dynamic: &Dynamic<T>) -> Self
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        self.add(dynamic);
+        self
+    }
+
+    /// Add `dynamic` as a member of the scope. Adding the same value
+    /// twice has no effect.
+    pub fn add<T>(&self, dynamic: &Dynamic<T>)
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        let id = dynamic_id(dynamic);
+        {
+            let mut members = self.inner.members.lock().unwrap();
+            if members.iter().any(|m| m.id == id) {
+                return;
+            }
+            members.push(Member { id, suppressed: 0 });
+        }
+
+        let scope: Weak<ScopeInner> = Arc::downgrade(&self.inner);
+        dynamic.subscribe(Box::new(move || {
+            if let Some(scope) = scope.upgrade() {
+                scope.member_changed(id);
+            }
+        }));
+    }
+
+    /// Run `f` with a new transaction and commit it if `f` returns `Ok`.
+    /// Subscribers of the scope are notified once if any member changed.
+    pub fn transaction<R, E, F>(&self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Transaction) -> Result<R, E>,
+    {
+        let mut tx = Transaction { scope: Some(self.clone()), ..Transaction::default() };
+        let result = f(&mut tx)?;
+        tx.commit();
+        Ok(result)
+    }
+
+    /// True once the member notifications caused by committed
+    /// transactions have all arrived. `Dynamic` notifications are
+    /// delivered on other threads, so until then the scope may still
+    /// receive, and swallow, notifications of earlier commits.
+    pub fn is_settled(&self) -> bool {
+        self.inner.members.lock().unwrap().iter().all(|m| m.suppressed == 0)
+    }
+
+    /// Mark the next notification of member `id` as already handled.
+    /// Returns false if `id` is not a member.
+    fn suppress(&self, id: usize) -> bool {
+        let mut members = self.inner.members.lock().unwrap();
+        match members.iter_mut().find(|m| m.id == id) {
+            Some(member) => {
+                member.suppressed += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl ReactiveValue for ReactiveScope {
+    fn subscribe(&self, callback: Box<dyn Fn() + Send + Sync>) {
+        self.inner.subscribers.lock().unwrap().push(callback);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Run `f` with a new transaction that is not bound to a scope and
+/// commit it if `f` returns `Ok`
+pub fn transaction<R, E, F>(f: F) -> Result<R, E>
+where
+    F: FnOnce(&mut Transaction) -> Result<R, E>,
+{
+    let mut tx = Transaction::default();
+    let result = f(&mut tx)?;
+    tx.commit();
+    Ok(result)
+}
+
+trait StagedWrite: Send {
+    fn id(&self) -> usize;
+    fn changed(&self) -> bool;
+    fn apply(self: Box<Self>);
+    fn boxed_clone(&self) -> Box<dyn StagedWrite>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct Staged<T> {
+    id      : usize,
+    dynamic : Dynamic<T>,
+    value   : T,
+}
+
+impl<T: Clone + Send + Sync + PartialEq + 'static> StagedWrite for Staged<T> {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn changed(&self) -> bool {
+        *self.dynamic.lock() != self.value
+    }
+
+    fn apply(self: Box<Self>) {
+        self.dynamic.set(self.value);
+    }
+
+    fn boxed_clone(&self) -> Box<dyn StagedWrite> {
+        Box::new(Staged { id: self.id, dynamic: self.dynamic.clone(), value: self.value.clone() })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Transaction
+///
+/// Writes staged for several `Dynamic` values, applied together on
+/// commit. Created by `transaction`, `ReactiveScope::transaction` or
+/// `TerminalWidget::transaction`.
+#[derive(Default)]
+pub struct Transaction {
+    scope   : Option<ReactiveScope>,
+    writes  : Vec<Box<dyn StagedWrite>>,
+    repaint : Option<egui::Context>,
+}
+
+impl Transaction {
+    fn staged<T>(&mut self, dynamic: &Dynamic<T>) -> &mut T
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        let id = dynamic_id(dynamic);
+        let index = match self.writes.iter().position(|w| w.id() == id) {
+            Some(index) => index,
+            None => {
+                let value = dynamic.get();
+                self.writes.push(Box::new(Staged { id, dynamic: dynamic.clone(), value }));
+                self.writes.len() - 1
+            }
+        };
+        let staged = self.writes[index]
+            .as_any_mut()
+            .downcast_mut::<Staged<T>>()
+            .expect("staged write has the type of its Dynamic");
+        &mut staged.value
+    }
+
+    /// The value of `dynamic` as it will be after the commit
+    pub fn get<T>(&self, dynamic: &Dynamic<T>) -> T
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        let id = dynamic_id(dynamic);
+        self.writes
+            .iter()
+            .find(|w| w.id() == id)
+            .and_then(|w| w.as_any().downcast_ref::<Staged<T>>())
+            .map(|staged| staged.value.clone())
+            .unwrap_or_else(|| dynamic.get())
+    }
+
+    /// Stage `value` to be written to `dynamic`
+    pub fn set<T>(&mut self, dynamic: &Dynamic<T>, value: T)
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+    {
+        *self.staged(dynamic) = value;
+    }
+
+    /// Stage an in-place modification of `dynamic`, applied to the value
+    /// staged so far
+    pub fn update<T, F>(&mut self, dynamic: &Dynamic<T>, f: F)
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        F: FnOnce(&mut T),
+    {
+        f(self.staged(dynamic));
+    }
+
+    /// Run `f` on this transaction and undo what it staged if it returns
+    /// `Err`. Writes staged by `f` are committed with the rest of the
+    /// transaction, never on their own.
+    pub fn nested<R, E, F>(&mut self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Transaction) -> Result<R, E>,
+    {
+        let writes: Vec<_> = self.writes.iter().map(|w| w.boxed_clone()).collect();
+        let repaint = self.repaint.clone();
+        let result = f(self);
+        if result.is_err() {
+            self.writes = writes;
+            self.repaint = repaint;
+        }
+        result
+    }
+
+    /// Request one repaint of `ctx` after the commit
+    pub fn request_repaint(&mut self, ctx: &egui::Context) {
+        self.repaint = Some(ctx.clone());
+    }
+
+    /// True if nothing has been staged
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    fn commit(self) {
+        let mut scope_changed = false;
+        for write in self.writes {
+            if !write.changed() {
+                continue;
+            }
+            if let Some(scope) = &self.scope {
+                scope_changed |= scope.suppress(write.id());
+            }
+            write.apply();
+        }
+
+        if scope_changed {
+            if let Some(scope) = &self.scope {
+                scope.inner.notify();
+            }
+        }
+        if let Some(ctx) = self.repaint {
+            ctx.request_repaint();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Polling file watcher for configuration files
+//!
+//! Config files are small and few, so a background thread polling
+//! their metadata is cheap and behaves the same on every platform.
+//! The contents are only read when the modification time or length
+//! changed, and callbacks only fire when the contents actually differ.
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+/// Default polling interval
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Hash of a file's contents, used to recognize writes by the app itself
+pub fn content_hash(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+type ChangeCallback = Box<dyn Fn(&[u8]) + Send>;
+
+struct WatchEntry {
+    path     : PathBuf,
+    stamp    : Option<(SystemTime, u64)>,
+    hash     : Option<u64>,
+    callback : ChangeCallback,
+}
+
+impl WatchEntry {
+    fn new(path: PathBuf, callback: ChangeCallback) -> Self {
+        let stamp = Self::stamp(&path);
+        let hash = fs::read(&path).ok().map(|c| content_hash(&c));
+        Self { path, stamp, hash, callback }
+    }
+
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let meta = fs::metadata(path).ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+
+    fn poll(&mut self) {
+        let stamp = Self::stamp(&self.path);
+        if stamp.is_none() || stamp == self.stamp {
+            return;
+        }
+        self.stamp = stamp;
+
+        let Ok(contents) = fs::read(&self.path) else {
+            return;
+        };
+        let hash = content_hash(&contents);
+        if self.hash != Some(hash) {
+            self.hash = Some(hash);
+            (self.callback)(&contents);
+        }
+    }
+}
+
+/// FileWatcher
+///
+/// Watches files for changes on a background thread and invokes a
+/// callback with the new contents. Deleted files are ignored until
+/// they reappear. The thread stops when the watcher is dropped.
+pub struct FileWatcher {
+    entries : Arc<Mutex<Vec<WatchEntry>>>,
+    stop    : Arc<AtomicBool>,
+    thread  : Option<JoinHandle<()>>,
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileWatcher {
+    /// Create a watcher polling at the default interval
+    pub fn new() -> Self {
+        Self::with_interval(DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Create a watcher polling every `interval`
+    pub fn with_interval(interval: Duration) -> Self {
+        let entries: Arc<Mutex<Vec<WatchEntry>>> = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_entries = entries.clone();
+        let thread_stop = stop.clone();
+        let thread = std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(interval);
+                for entry in thread_entries.lock().unwrap().iter_mut() {
+                    entry.poll();
+                }
+            }
+        });
+
+        Self {
+            entries,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Call `on_change` with the new contents whenever `path` changes
+    pub fn watch<F>(&self, path: impl Into<PathBuf>, on_change: F)
+    where
+        F: Fn(&[u8]) + Send + 'static,
+    {
+        let entry = WatchEntry::new(path.into(), Box::new(on_change));
+        self.entries.lock().unwrap().push(entry);
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
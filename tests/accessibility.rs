@@ -0,0 +1,72 @@
+//! Contrast checks and color blind safe palettes.
+use egui::Color32;
+use egui_mobius_template::accessibility::{
+    background, color_difference, contrast_ratio, fix_contrast, ContrastLevel, Palette, WCAG_AA, WCAG_AAA,
+};
+
+#[test]
+fn contrast_ratios_follow_wcag() {
+    assert_eq!(contrast_ratio(Color32::BLACK, Color32::WHITE), 21.0);
+    assert_eq!(contrast_ratio(Color32::WHITE, Color32::BLACK), 21.0);
+    assert_eq!(contrast_ratio(Color32::RED, Color32::RED), 1.0);
+
+    assert_eq!(ContrastLevel::from_ratio(2.9), ContrastLevel::Fail);
+    assert_eq!(ContrastLevel::from_ratio(3.0), ContrastLevel::AaLarge);
+    assert_eq!(ContrastLevel::from_ratio(WCAG_AA), ContrastLevel::Aa);
+    assert_eq!(ContrastLevel::from_ratio(WCAG_AAA), ContrastLevel::Aaa);
+}
+
+#[test]
+fn fixed_colors_reach_the_target_on_both_themes() {
+    let gray = Color32::from_gray(128);
+    for dark_mode in [true, false] {
+        let background = background(dark_mode);
+        for target in [WCAG_AA, WCAG_AAA] {
+            let fixed = fix_contrast(gray, background, target);
+            assert!(contrast_ratio(fixed, background) >= target, "{:?} on {:?}", fixed, background);
+        }
+    }
+}
+
+#[test]
+fn every_palette_entry_reaches_aa_on_both_themes() {
+    for palette in Palette::ALL {
+        for dark_mode in [true, false] {
+            let background = background(dark_mode);
+            for (name, ratio) in palette.colors(dark_mode).contrast_report(background) {
+                assert!(ratio >= WCAG_AA, "{:?} {} is {:.2}:1 on {:?}", palette, name, ratio, background);
+            }
+        }
+    }
+}
+
+#[test]
+fn color_blind_palettes_stay_distinguishable() {
+    for palette in [Palette::Deuteranopia, Palette::Protanopia, Palette::Tritanopia] {
+        for dark_mode in [true, false] {
+            let entries = palette.colors(dark_mode).entries();
+            for (i, (a, color_a)) in entries.iter().enumerate() {
+                for (b, color_b) in &entries[i + 1..] {
+                    let seen = color_difference(palette.simulate(*color_a), palette.simulate(*color_b));
+                    let normal = color_difference(*color_a, *color_b);
+                    assert!(
+                        seen.min(normal) >= 12.0,
+                        "{:?} {} and {} differ by {:.1} (dark mode: {})",
+                        palette, a, b, seen.min(normal), dark_mode
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn simulation_keeps_grays_and_confuses_red_with_green() {
+    let gray = Color32::from_gray(120);
+    for palette in Palette::ALL {
+        assert!(color_difference(palette.simulate(gray), gray) < 2.0, "{:?}", palette);
+    }
+    let (red, green) = (Color32::from_rgb(200, 80, 0), Color32::from_rgb(100, 120, 0));
+    assert!(color_difference(red, green) > 30.0);
+    assert!(color_difference(Palette::Deuteranopia.simulate(red), Palette::Deuteranopia.simulate(green)) < 15.0);
+}
@@ -0,0 +1,81 @@
+//! What the application builder hands to the shell.
+use eframe::egui;
+use egui_dock::DockState;
+use egui_mobius_template::actions::Action;
+use egui_mobius_template::app::{MobiusApp, MobiusAppBuilder};
+use egui_mobius_template::tabs::Tabs;
+use egui_mobius_template::{AppContext, Panel};
+
+struct Named(&'static str);
+
+impl Panel for Named {
+    fn id(&self) -> &'static str {
+        self.0
+    }
+
+    fn title(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+#[derive(Default)]
+struct Logger;
+
+impl Panel for Logger {
+    fn id(&self) -> &'static str {
+        "logger"
+    }
+
+    fn title(&self) -> String {
+        "Logger".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+fn action_ids(builder: &MobiusAppBuilder) -> Vec<&'static str> {
+    builder.registered_actions().actions().map(|info| info.id).collect()
+}
+
+#[test]
+fn the_builder_starts_with_the_shell_actions_only() {
+    let builder = MobiusApp::builder();
+    assert_eq!(builder.registered_panels().panels().count(), 0);
+    assert_eq!(
+        action_ids(&builder),
+        ["edit.undo", "edit.redo", "edit.copy_log", "file.export_log", "help.export_diagnostics"]
+    );
+    assert_eq!(builder.layout().iter_all_tabs().count(), 0);
+}
+
+#[test]
+fn panels_are_laid_out_in_registration_order() {
+    let builder = MobiusApp::builder()
+        .panel_with(|| Named("plot"))
+        .panel::<Logger>()
+        .panel_with(|| Named("parameters"));
+    let ids: Vec<_> = builder.registered_panels().panels().map(|info| info.id).collect();
+    assert_eq!(ids, ["plot", "logger", "parameters"]);
+
+    // Without a default layout the shell opens them all in one leaf
+    let tabs = Tabs::new(builder.layout(), builder.registered_panels());
+    assert_eq!(tabs.open_panels(), ["plot", "logger", "parameters"]);
+
+    let builder = builder.default_layout(DockState::new(vec!["parameters", "plot"]));
+    let tabs = Tabs::new(builder.layout(), builder.registered_panels());
+    assert_eq!(tabs.open_panels(), ["parameters", "plot"]);
+}
+
+#[test]
+fn application_actions_replace_shell_actions_in_place() {
+    let builder = MobiusApp::builder()
+        .action(Action::new("app.reset", "Reset", |_| {}))
+        .action(Action::new("edit.undo", "Undo twice", |_| {}));
+    assert_eq!(
+        action_ids(&builder),
+        ["edit.undo", "edit.redo", "edit.copy_log", "file.export_log", "help.export_diagnostics", "app.reset"]
+    );
+    assert_eq!(builder.registered_actions().info("edit.undo").unwrap().title(), "Undo twice");
+}
@@ -0,0 +1,89 @@
+//! Color serialization and parsing.
+use egui::Color32;
+use egui_mobius_template::colors::{parse_color, ColorParseError};
+use egui_mobius_template::LogColors;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Swatch {
+    #[serde(with = "egui_mobius_template::colors::color32_serde")]
+    color: Color32,
+}
+
+fn swatch(json: &str) -> Result<Color32, serde_json::Error> {
+    serde_json::from_str::<Swatch>(json).map(|swatch| swatch.color)
+}
+
+fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color32 {
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+#[test]
+fn colors_round_trip_through_json_and_hex_strings() {
+    let mut colors = vec![Color32::BLACK, Color32::WHITE, Color32::TRANSPARENT, Color32::from_rgb(255, 180, 100)];
+    for alpha in [1, 17, 64, 128, 200, 254] {
+        colors.push(rgba(255, 128, 0, alpha));
+        colors.push(rgba(12, 200, 99, alpha));
+    }
+    for color in colors {
+        let json = serde_json::to_string(&Swatch { color }).unwrap();
+        assert_eq!(swatch(&json).unwrap(), color, "{}", json);
+        assert_eq!(parse_color(&color.to_hex()).unwrap(), color, "{}", color.to_hex());
+    }
+
+    // Colors are written as unmultiplied components
+    let json = serde_json::to_string(&Swatch { color: rgba(255, 128, 0, 128) }).unwrap();
+    assert_eq!(json, r#"{"color":[255,128,0,128]}"#);
+
+    // Saving and loading repeatedly does not drift
+    let mut log_colors = LogColors { slider: rgba(255, 180, 100, 77), ..LogColors::default() };
+    for _ in 0..5 {
+        log_colors = serde_json::from_str(&serde_json::to_string(&log_colors).unwrap()).unwrap();
+    }
+    assert_eq!(log_colors.slider, rgba(255, 180, 100, 77));
+}
+
+#[test]
+fn hand_written_formats_are_parsed() {
+    let orange = Color32::from_rgb(255, 136, 0);
+    assert_eq!(parse_color("#f80").unwrap(), orange);
+    assert_eq!(parse_color("#f808").unwrap(), rgba(255, 136, 0, 136));
+    assert_eq!(parse_color("#FF8800").unwrap(), orange);
+    assert_eq!(parse_color("  #ff880080 ").unwrap(), rgba(255, 136, 0, 128));
+
+    assert_eq!(parse_color("orange").unwrap(), Color32::from_rgb(255, 165, 0));
+    assert_eq!(parse_color("RebeccaPurple").unwrap(), Color32::from_rgb(102, 51, 153));
+    assert_eq!(parse_color("transparent").unwrap(), Color32::TRANSPARENT);
+
+    assert_eq!(parse_color("rgb(255, 136, 0)").unwrap(), orange);
+    assert_eq!(parse_color("rgba(255, 136, 0, 0.5)").unwrap(), rgba(255, 136, 0, 128));
+    assert_eq!(parse_color("rgb(100% 0% 0% / 50%)").unwrap(), rgba(255, 0, 0, 128));
+    assert_eq!(parse_color("hsl(0, 100%, 50%)").unwrap(), Color32::from_rgb(255, 0, 0));
+    assert_eq!(parse_color("hsl(120deg 100% 25%)").unwrap(), Color32::from_rgb(0, 128, 0));
+    assert_eq!(parse_color("hsla(240, 100%, 50%, 0.5)").unwrap(), rgba(0, 0, 255, 128));
+
+    // Theme files may use any of them instead of arrays
+    assert_eq!(swatch(r##"{"color":"#f80"}"##).unwrap(), orange);
+    assert_eq!(swatch(r#"{"color":"hsl(0, 100%, 50%)"}"#).unwrap(), Color32::from_rgb(255, 0, 0));
+    assert_eq!(swatch(r#"{"color":[255, 136, 0]}"#).unwrap(), orange);
+}
+
+#[test]
+fn malformed_colors_are_rejected() {
+    for input in [
+        "", "#", "#ff", "#ff88000", "#gg8800", "#ff8800aa00", "blurple", "rgb(255, 0)",
+        "rgb(255, 0, 0, 1, 1)", "rgb(red, 0, 0)", "rgb(255, 0, 0", "hsl(0, 100, 50)",
+        "hsl(x, 100%, 50%)", "cmyk(0, 0, 0, 0)",
+    ] {
+        assert!(parse_color(input).is_err(), "{:?} should be rejected", input);
+    }
+    let error: ColorParseError = parse_color("blurple").unwrap_err();
+    assert_eq!(error.to_string(), "invalid color \"blurple\"");
+
+    for json in [
+        r#"{"color":"blurple"}"#, r#"{"color":[255, 0]}"#, r#"{"color":[255, 0, 0, 255, 0]}"#,
+        r#"{"color":[256, 0, 0]}"#, r#"{"color":12}"#,
+    ] {
+        assert!(swatch(json).is_err(), "{} should be rejected", json);
+    }
+}
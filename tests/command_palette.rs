@@ -0,0 +1,82 @@
+//! Fuzzy search and recent commands of the palette, and running actions.
+use eframe::egui;
+use egui_mobius_template::actions::{Action, ActionRegistry};
+use egui_mobius_template::palette::{fuzzy_score, search, PaletteEntry, RecentCommands, MAX_RECENT};
+use egui_mobius_template::testing::TestApp;
+
+fn entries() -> Vec<PaletteEntry> {
+    vec![
+        PaletteEntry::new("panel:logger", "Open: 📜 Logger"),
+        PaletteEntry::new("action:logger.clear", "Logger: Clear logger"),
+        PaletteEntry::new("action:logger.export", "Logger: Export log"),
+        PaletteEntry::new("action:control.toggle_run", "Control: Toggle run/stop"),
+    ]
+}
+
+fn keys<'a>(found: &[&'a PaletteEntry]) -> Vec<&'a str> {
+    found.iter().map(|entry| entry.key.as_str()).collect()
+}
+
+#[test]
+fn fuzzy_score_prefers_word_starts_and_runs() {
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    assert!(fuzzy_score("CLEAR", "Logger: Clear logger").is_some());
+    assert!(fuzzy_score("clx", "Logger: Clear logger").is_none());
+
+    // Initials of words beat the same letters scattered in a word
+    let initials = fuzzy_score("el", "Export log").unwrap();
+    let scattered = fuzzy_score("el", "Help").unwrap();
+    assert!(initials > scattered);
+
+    let run = fuzzy_score("log", "Logger").unwrap();
+    let spread = fuzzy_score("log", "Load big").unwrap();
+    assert!(run > spread);
+}
+
+#[test]
+fn search_ranks_matches_and_lists_recent_first() {
+    let entries = entries();
+    let none = RecentCommands::default();
+
+    assert_eq!(keys(&search("export", &none, &entries)), ["action:logger.export"]);
+    assert_eq!(keys(&search("tog run", &none, &entries)), ["action:control.toggle_run"]);
+    assert!(search("zzz", &none, &entries).is_empty());
+
+    let mut recent = RecentCommands::default();
+    recent.push("action:logger.export");
+    recent.push("action:missing");
+    recent.push("action:control.toggle_run");
+    assert_eq!(
+        keys(&search("", &recent, &entries)),
+        ["action:control.toggle_run", "action:logger.export", "panel:logger", "action:logger.clear"],
+    );
+}
+
+#[test]
+fn recent_commands_are_unique_and_bounded() {
+    let mut recent = RecentCommands::default();
+    for i in 0..MAX_RECENT + 2 {
+        recent.push(&format!("action:{}", i));
+    }
+    recent.push("action:3");
+    assert_eq!(recent.keys.len(), MAX_RECENT);
+    assert_eq!(recent.keys[0], "action:3");
+    assert_eq!(recent.keys.iter().filter(|key| *key == "action:3").count(), 1);
+}
+
+#[test]
+fn registry_runs_and_replaces_actions() {
+    let mut actions = ActionRegistry::new()
+        .with(Action::new("count", "Count", |ctx| *ctx.resources.get_mut::<u32>().unwrap() += 1))
+        .with(Action::new("other", "Other", |_| {}).category("Misc"));
+    actions.register(Action::new("count", "Count twice", |ctx| *ctx.resources.get_mut::<u32>().unwrap() += 2));
+
+    let ids: Vec<_> = actions.actions().map(|info| info.id).collect();
+    assert_eq!(ids, ["count", "other"]);
+    assert_eq!(actions.info("other").unwrap().title(), "Misc: Other");
+
+    let mut app = TestApp::new(&egui::Context::default()).with(0_u32);
+    assert!(actions.run("count", &mut app.context()));
+    assert!(!actions.run("missing", &mut app.context()));
+    assert_eq!(app.services.resources.get::<u32>(), Some(&2));
+}
@@ -0,0 +1,136 @@
+//! Crash reports written by the panic hook and diagnostics bundles.
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::diagnostics::{
+    self, CrashHandler, CrashReport, DiagnosticsBundle, DiagnosticsSettings, SystemInfo,
+};
+use egui_mobius_template::{LogColors, LogType, Notifier, SettingsStore};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+fn settings(name: &str) -> SettingsStore {
+    let dir = std::env::temp_dir().join(format!("mobius_diagnostics_{}_{}", name, std::process::id()));
+    let settings = SettingsStore::new(dir.join("settings.json"));
+    settings.set(&LogColors::default());
+    settings
+}
+
+type PanicHook = Box<dyn Fn(&std::panic::PanicHookInfo<'_>) + Sync + Send>;
+
+/// Puts the panic hook taken on creation back when dropped, so the
+/// crash handler a test installs does not outlive it
+struct RestoreHook(Option<PanicHook>);
+
+impl RestoreHook {
+    fn take() -> Self {
+        Self(Some(std::panic::take_hook()))
+    }
+}
+
+impl Drop for RestoreHook {
+    fn drop(&mut self) {
+        // Setting a hook panics while unwinding
+        if let Some(hook) = self.0.take().filter(|_| !std::thread::panicking()) {
+            std::panic::set_hook(hook);
+        }
+    }
+}
+
+fn logs(entries: &[(&str, LogType)]) -> VecDeque<(String, LogType)> {
+    entries.iter().map(|(message, log_type)| (message.to_string(), *log_type)).collect()
+}
+
+#[test]
+fn panics_are_reported_once_and_shown_until_seen() {
+    let settings = settings("crash");
+    let dir = diagnostics::crashes_dir(settings.path());
+    let restore = RestoreHook::take();
+    let handler = CrashHandler::install(&dir);
+    let log = Dynamic::new(logs(&[("Started", LogType::Default), ("Failed to save", LogType::Error)]));
+    let notifier = Notifier::new();
+    handler.attach_logs(&log);
+    handler.attach_settings(&settings);
+    handler.attach_notifier(&notifier);
+    assert!(CrashReport::list(&dir).is_empty());
+
+    let result = std::thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("simulation diverged at step {}", 42))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+
+    let reports = CrashReport::list(&dir);
+    assert_eq!(reports.len(), 1);
+    let report = CrashReport::load(&reports[0]).unwrap();
+    assert_eq!(report.message, "simulation diverged at step 42");
+    assert_eq!(report.thread, "worker");
+    assert!(report.location.as_ref().unwrap().contains("diagnostics.rs"));
+    assert!(!report.backtrace.is_empty());
+    assert_eq!(report.logs, ["[Default] Started", "[Error] Failed to save"]);
+    assert!(report.settings.get("log_colors").is_some());
+    assert_eq!(report.system, SystemInfo::current());
+    assert!(report.to_text().contains("in thread 'worker': simulation diverged at step 42"));
+
+    // The running application is told about background panics
+    let started = Instant::now();
+    while notifier.history().is_empty() && started.elapsed() < Duration::from_secs(2) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let notification = notifier.history().pop().unwrap();
+    assert_eq!(notification.body.as_deref(), Some("simulation diverged at step 42"));
+    assert_eq!(notification.actions[0].command, "action:help.export_diagnostics");
+
+    // Shown on the next launch until it was seen
+    let unseen = DiagnosticsSettings::default().unseen_crash(&dir);
+    assert_eq!(unseen.as_ref(), Some(&reports[0]));
+    let name = reports[0].file_name().unwrap().to_string_lossy().into_owned();
+    let seen = DiagnosticsSettings { last_seen_crash: Some(name) };
+    assert_eq!(seen.unseen_crash(&dir), None);
+
+    // Panics after the hook was put back are not reported
+    drop(restore);
+    assert!(std::thread::spawn(|| panic!("after the test")).join().is_err());
+    assert_eq!(CrashReport::list(&dir).len(), 1);
+}
+
+#[test]
+fn bundles_hold_logs_settings_and_crash_reports() {
+    let settings = settings("bundle");
+    let crashes = diagnostics::crashes_dir(settings.path());
+    let report = CrashReport {
+        time: chrono::Local::now(),
+        thread: "main".into(),
+        message: "index out of bounds".into(),
+        location: Some("src/ui/plot_panel.rs:10:5".into()),
+        backtrace: String::new(),
+        logs: Vec::new(),
+        settings: serde_json::Value::Null,
+        system: SystemInfo::current(),
+    };
+    let report_name = CrashReport::file_name(&report.time);
+    report.save(&crashes.join(&report_name)).unwrap();
+
+    let log = logs(&[("Ui Slider Event 42.0", LogType::Slider), ("Failed to save", LogType::Error)]);
+    let bundle = DiagnosticsBundle::collect(&log, &settings).file("notes.txt", "Reproduced twice");
+    let crash_entry = format!("crashes/{}", report_name);
+    let names: Vec<_> = bundle.names().collect();
+    assert_eq!(names, ["system.txt", "log.txt", "settings.json", crash_entry.as_str(), "notes.txt"]);
+
+    let path: PathBuf = diagnostics::diagnostics_dir(settings.path()).join("bundle.zip");
+    bundle.write(&path).unwrap();
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let mut read = |name: &str| {
+        let mut contents = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut contents).unwrap();
+        contents
+    };
+    assert_eq!(read("log.txt"), "[Slider] Ui Slider Event 42.0\n[Error] Failed to save\n");
+    assert!(read("system.txt").contains(&format!("egui_mobius_template: {}", env!("CARGO_PKG_VERSION"))));
+    let document: serde_json::Value = serde_json::from_str(&read("settings.json")).unwrap();
+    assert!(document.get("log_colors").is_some());
+    let saved: CrashReport = serde_json::from_str(&read(&crash_entry)).unwrap();
+    assert_eq!(saved, report);
+    assert_eq!(read("notes.txt"), "Reproduced twice");
+}
@@ -0,0 +1,51 @@
+//! Round trips of the dock layout through the settings store.
+use egui_dock::{DockState, NodeIndex};
+use egui_mobius_template::{DockLayout, SettingsStore};
+
+fn layout() -> DockState<&'static str> {
+    let mut dock = DockState::new(vec!["control", "about"]);
+    let [left, _] = dock.main_surface_mut().split_right(NodeIndex::root(), 0.3, vec!["logger"]);
+    dock.main_surface_mut().split_below(left, 0.7, vec!["settings"]);
+    dock
+}
+
+fn tabs(dock: &DockState<&'static str>) -> Vec<&'static str> {
+    let mut tabs: Vec<_> = dock.iter_all_tabs().map(|(_, tab)| *tab).collect();
+    tabs.sort();
+    tabs
+}
+
+#[test]
+fn layout_survives_the_settings_file() {
+    let dir = std::env::temp_dir().join(format!("dock_layout_{}", std::process::id()));
+    let path = dir.join("settings.json");
+
+    let store = SettingsStore::new(&path);
+    store.set(&DockLayout::capture(&layout(), |tab| tab.to_string()));
+    store.flush().unwrap();
+
+    let reloaded = SettingsStore::new(&path);
+    reloaded.load().unwrap();
+    let known = ["about", "control", "logger", "settings"];
+    let restored = reloaded
+        .get::<DockLayout>()
+        .restore(|id| known.iter().find(|k| *k == id).copied())
+        .expect("layout restored");
+    assert_eq!(tabs(&restored), known);
+    assert_eq!(restored.main_surface().num_tabs(), 4);
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn unknown_tabs_are_dropped() {
+    let layout = DockLayout::capture(&layout(), |tab| tab.to_string());
+
+    let restored = layout
+        .restore(|id| (id != "logger").then(|| if id == "about" { "about" } else { "control" }))
+        .expect("layout restored");
+    assert_eq!(restored.iter_all_tabs().count(), 3);
+
+    assert!(layout.restore(|_| None::<&str>).is_none());
+    assert!(DockLayout::default().restore(|_| Some("control")).is_none());
+}
@@ -0,0 +1,27 @@
+//! Comparing UI snapshots.
+use egui_mobius_template::testing::{assert_snapshot, snapshot_dir, UPDATE_SNAPSHOTS};
+use std::panic::catch_unwind;
+
+#[test]
+fn missing_and_changed_snapshots_fail() {
+    // Update mode records snapshots instead
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_some() {
+        return;
+    }
+    let name = format!("harness_{}", std::process::id());
+    let path = snapshot_dir().join(format!("{}.txt", name));
+    let new_path = snapshot_dir().join(format!("{}.new.txt", name));
+
+    assert!(catch_unwind(|| assert_snapshot(&name, "label \"A\"\n")).is_err());
+    assert!(!path.exists());
+    assert_eq!(std::fs::read_to_string(&new_path).unwrap(), "label \"A\"\n");
+
+    std::fs::write(&path, "label \"A\"\n").unwrap();
+    assert_snapshot(&name, "label \"A\"\n");
+    assert!(!new_path.exists());
+    assert!(catch_unwind(|| assert_snapshot(&name, "label \"B\"\n")).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "label \"A\"\n");
+
+    std::fs::remove_file(path).ok();
+    std::fs::remove_file(new_path).ok();
+}
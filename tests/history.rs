@@ -0,0 +1,157 @@
+//! Undo/redo ordering, limits and coalescing of tracked changes.
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::history::{Command, History, MergeRule};
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A step that logs when it is undone or redone
+struct Step {
+    name : &'static str,
+    log  : Arc<Mutex<Vec<String>>>,
+}
+
+impl Command for Step {
+    fn label(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn undo(&mut self) {
+        self.log.lock().unwrap().push(format!("undo {}", self.name));
+    }
+
+    fn redo(&mut self) {
+        self.log.lock().unwrap().push(format!("redo {}", self.name));
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn push_steps(history: &History, names: &[&'static str]) -> Arc<Mutex<Vec<String>>> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    for &name in names {
+        history.push(Box::new(Step { name, log: log.clone() }));
+    }
+    log
+}
+
+/// Wait until every tracked change reached `history`
+fn settle(history: &History) {
+    let started = Instant::now();
+    while !history.is_settled() {
+        assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn steps_are_undone_newest_first_and_redone_in_order() {
+    let history = History::new();
+    let log = push_steps(&history, &["a", "b", "c"]);
+
+    while history.undo() {}
+    assert!(!history.can_undo());
+    assert!(history.redo());
+    assert!(history.redo());
+    assert_eq!(*log.lock().unwrap(), ["undo c", "undo b", "undo a", "redo a", "redo b"]);
+    assert_eq!(history.steps(), (vec!["a".to_string(), "b".into(), "c".into()], 2));
+
+    // Jumping undoes or redoes every step in between
+    history.go_to(0);
+    history.go_to(3);
+    assert_eq!(log.lock().unwrap()[5..], ["undo b", "undo a", "redo a", "redo b", "redo c"]);
+    assert!(!history.redo());
+}
+
+#[test]
+fn a_new_step_clears_the_redo_stack() {
+    let history = History::new();
+    push_steps(&history, &["a", "b"]);
+    assert!(history.undo());
+    assert!(history.can_redo());
+
+    push_steps(&history, &["c"]);
+    assert!(!history.can_redo());
+    assert_eq!(history.steps(), (vec!["a".to_string(), "c".into()], 2));
+}
+
+#[test]
+fn the_oldest_steps_are_dropped_beyond_the_limit() {
+    let history = History::with_limit(3);
+    let log = push_steps(&history, &["a", "b", "c", "d", "e"]);
+    assert_eq!(history.steps().0, ["c", "d", "e"]);
+
+    while history.undo() {}
+    assert_eq!(*log.lock().unwrap(), ["undo e", "undo d", "undo c"]);
+}
+
+#[test]
+fn tracked_changes_are_recorded_and_undone() {
+    let option = Dynamic::new(0);
+    let history = History::new();
+    history.track(&option, "Option", MergeRule::Never);
+
+    option.set(1);
+    settle(&history);
+    option.set(2);
+    settle(&history);
+    assert_eq!(history.steps(), (vec!["Option".to_string(), "Option".into()], 2));
+
+    assert!(history.undo());
+    assert_eq!(option.get(), 1);
+    // Undoing writes the value without recording a new step
+    settle(&history);
+    assert!(history.can_redo());
+    assert!(history.undo());
+    assert_eq!(option.get(), 0);
+    assert!(history.redo());
+    assert_eq!(option.get(), 1);
+}
+
+#[test]
+fn continuous_changes_are_coalesced() {
+    let slider = Dynamic::new(0.0_f32);
+    let option = Dynamic::new(0);
+    let history = History::new();
+    history.track(&slider, "Slider", MergeRule::Within(Duration::from_secs(60)));
+    history.track(&option, "Option", MergeRule::Never);
+
+    for value in [1.0, 2.0, 3.0] {
+        slider.set(value);
+        settle(&history);
+    }
+    assert_eq!(history.steps().0, ["Slider"]);
+    assert!(history.undo());
+    assert_eq!(slider.get(), 0.0);
+    assert!(history.redo());
+    assert_eq!(slider.get(), 3.0);
+
+    // Another value in between starts a new step
+    option.set(1);
+    settle(&history);
+    slider.set(4.0);
+    settle(&history);
+    assert_eq!(history.steps().0, ["Slider", "Option", "Slider"]);
+
+    // A drag back to where it started leaves no step behind
+    slider.set(3.0);
+    settle(&history);
+    assert_eq!(history.steps().0, ["Slider", "Option"]);
+}
+
+#[test]
+fn changes_outside_the_merge_window_are_separate_steps() {
+    let slider = Dynamic::new(0);
+    let history = History::new();
+    history.track(&slider, "Slider", MergeRule::Within(Duration::from_millis(10)));
+
+    slider.set(1);
+    settle(&history);
+    // Let the window pass; the step times are taken when changes arrive
+    std::thread::sleep(Duration::from_millis(30));
+    slider.set(2);
+    settle(&history);
+    assert_eq!(history.steps().0, ["Slider", "Slider"]);
+}
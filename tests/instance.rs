@@ -0,0 +1,71 @@
+//! Single instance lock and argument forwarding.
+use egui_mobius_template::instance::{self, Instance};
+use std::path::PathBuf;
+
+fn settings_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("mobius_instance_{}_{}", name, std::process::id()))
+        .join("settings.json")
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn arguments_become_commands() {
+    let commands = instance::commands(&args(&[
+        "--command", "panel:logger", "/tmp/session.json", "-c", "layout:reset",
+        "--command=theme:dark.json", "--verbose", "-c",
+    ]));
+    assert_eq!(commands, [
+        "panel:logger",
+        "open:/tmp/session.json",
+        "layout:reset",
+        "theme:dark.json",
+    ]);
+    assert!(instance::commands(&[]).is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn later_launches_forward_their_arguments() {
+    let path = settings_path("forward");
+    let Instance::Primary(server) = instance::acquire(&path).unwrap() else {
+        panic!("the first launch should hold the lock");
+    };
+    assert!(instance::lock_path(&path).exists());
+    assert_eq!(server.socket(), instance::socket_path(&path));
+    assert!(server.take().is_empty());
+
+    let Instance::Secondary(client) = instance::acquire(&path).unwrap() else {
+        panic!("a second launch should find the running instance");
+    };
+    client.forward(&args(&["-c", "panel:logger"])).unwrap();
+    client.forward(&args(&["/tmp/a.json"])).unwrap();
+    assert_eq!(server.take(), [args(&["-c", "panel:logger"]), args(&["/tmp/a.json"])]);
+    assert!(server.take().is_empty());
+
+    // Once the instance is gone the next launch takes over
+    drop(server);
+    assert!(!instance::socket_path(&path).exists());
+    assert!(client.forward(&args(&["-c", "panel:logger"])).is_err());
+    assert!(matches!(instance::acquire(&path).unwrap(), Instance::Primary(_)));
+}
+
+#[cfg(unix)]
+#[test]
+fn stale_sockets_are_replaced() {
+    let path = settings_path("stale");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(instance::socket_path(&path), "left behind by a crash").unwrap();
+
+    let Instance::Primary(server) = instance::acquire(&path).unwrap() else {
+        panic!("a stale socket should not block the first launch");
+    };
+    let Instance::Secondary(client) = instance::acquire(&path).unwrap() else {
+        panic!("a second launch should find the running instance");
+    };
+    client.forward(&args(&["-c", "app:about"])).unwrap();
+    assert_eq!(server.take(), [args(&["-c", "app:about"])]);
+}
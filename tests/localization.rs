@@ -0,0 +1,69 @@
+//! Message lookup, language switching and locale formats. The language
+//! is process-wide, so everything that depends on it is in one test.
+use chrono::{FixedOffset, TimeZone};
+use egui_mobius_template::i18n::{self, I18nError};
+use egui_mobius_template::tr;
+
+#[test]
+fn unknown_ids_are_shown_as_they_are() {
+    assert_eq!(i18n::tr("Plain label"), "Plain label");
+    assert!(!i18n::has_message("Plain label"));
+    assert_eq!(tr!("no-such-message", count = 2), "no-such-message");
+}
+
+#[test]
+fn invalid_messages_are_reported() {
+    assert_eq!(
+        i18n::add_messages("not a language", "test-greeting = Hi"),
+        Err(I18nError::InvalidLanguage("not a language".into()))
+    );
+
+    // The valid entries of a file with errors are still added
+    let result = i18n::add_messages("en-US", "test-valid = Valid\ntest-broken = { $\n");
+    assert!(matches!(result, Err(I18nError::Parse { ref language, .. }) if language == "en-US"));
+    assert!(i18n::has_message("test-valid"));
+}
+
+#[test]
+fn languages_switch_at_runtime() {
+    let languages: Vec<_> = i18n::languages().into_iter().map(|(id, _)| id).collect();
+    for id in ["en-US", "de", "fr"] {
+        assert!(languages.iter().any(|language| language == id), "{} missing", id);
+    }
+
+    assert_eq!(i18n::language(), i18n::DEFAULT_LANGUAGE);
+    assert_eq!(tr!("menu-quit"), "Quit");
+    assert_eq!(tr!("notifications-count", count = 1), "1 notification");
+    assert_eq!(i18n::format_number(1234.5, 2), "1,234.50");
+    assert_eq!(i18n::format_number(-1234567.0, 0), "-1,234,567");
+
+    // Applications add their own messages; missing translations fall
+    // back to the default language
+    i18n::add_messages("en-US", "test-app-title = Circuit\ntest-only-english = English").unwrap();
+    i18n::add_messages("de", "test-app-title = Schaltung").unwrap();
+
+    // A regional variant selects the language
+    assert!(i18n::set_language("de-CH"));
+    assert_eq!(i18n::language(), "de");
+    assert_eq!(tr!("menu-quit"), "Beenden");
+    assert_eq!(tr!("test-app-title"), "Schaltung");
+    assert_eq!(tr!("test-only-english"), "English");
+    assert_eq!(tr!("notifications-count", count = 3), "3 Benachrichtigungen");
+    assert_eq!(i18n::format_number(1234.5, 2), "1.234,50");
+
+    let time = FixedOffset::east_opt(0).unwrap().with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap();
+    assert_eq!(i18n::format_datetime(&time), "09.03.2024 14:05:07");
+    assert_eq!(i18n::format_clock(&time, false), "2:05:07 PM");
+
+    // Unknown languages keep the current one
+    assert!(!i18n::set_language("xx"));
+    assert_eq!(i18n::language(), "de");
+
+    assert!(i18n::set_language("fr"));
+    assert_eq!(i18n::format_number(1234.5, 1), "1\u{202F}234,5");
+    assert_eq!(i18n::format_date(&time), "09/03/2024");
+
+    assert!(i18n::set_language("en-US"));
+    assert_eq!(i18n::format_clock(&time, true), "14:05:07");
+    assert_eq!(i18n::format_time(&time), "2:05:07 PM");
+}
@@ -0,0 +1,131 @@
+//! Compile and behavior tests for every arm of the logging macros.
+//!
+//! Only the macros are imported, so these tests also check that the
+//! expansions resolve everything else through `$crate`.
+use egui_mobius_template::{
+    log_to_terminal, set_combo_log, set_slider_log, set_timestamp_log, terminal_log,
+    with_terminal_logs,
+};
+
+fn terminal() -> egui_mobius_template::TerminalWidget {
+    egui_mobius_template::TerminalWidget::new(
+        eframe::egui::Context::default(),
+        egui_mobius_template::LogColors::default(),
+    )
+}
+
+fn last(terminal: &egui_mobius_template::TerminalWidget) -> (String, String) {
+    let (message, log_type) = terminal.logs.get().back().cloned().expect("no log entry");
+    (message, format!("{:?}", log_type))
+}
+
+#[test]
+fn log_to_terminal_pushes_entry() {
+    let terminal = terminal();
+    log_to_terminal!(terminal, ("entry".to_string(), egui_mobius_template::LogType::Primary));
+    assert_eq!(last(&terminal), ("entry".to_string(), "Primary".to_string()));
+}
+
+#[test]
+fn log_to_terminal_caps_at_max_logs() {
+    let terminal = terminal();
+    for i in 0..egui_mobius_template::MAX_LOGS + 5 {
+        log_to_terminal!(terminal, (i.to_string(), egui_mobius_template::LogType::Default));
+    }
+    let logs = terminal.logs.get();
+    assert_eq!(logs.len(), egui_mobius_template::MAX_LOGS);
+    assert_eq!(logs.front().unwrap().0, "5");
+}
+
+#[test]
+fn set_timestamp_log_default_type() {
+    let terminal = terminal();
+    set_timestamp_log!(terminal, "Info");
+    let (message, log_type) = last(&terminal);
+    assert!(message.starts_with("Info : "));
+    assert_eq!(log_type, "Default");
+}
+
+#[test]
+fn set_timestamp_log_custom_type() {
+    let terminal = terminal();
+    set_timestamp_log!(terminal, "Info", egui_mobius_template::LogType::Timestamp);
+    let (message, log_type) = last(&terminal);
+    assert!(message.starts_with("Info : "));
+    assert_eq!(log_type, "Timestamp");
+}
+
+#[test]
+fn set_slider_log_default_type() {
+    let terminal = terminal();
+    let value = 2.5;
+    set_slider_log!(terminal, format!("Slider {:.1}", value));
+    assert_eq!(last(&terminal), ("Slider 2.5".to_string(), "Slider".to_string()));
+}
+
+#[test]
+fn set_slider_log_custom_type() {
+    let terminal = terminal();
+    set_slider_log!(terminal, "Slider moved", egui_mobius_template::LogType::CustomEvent);
+    assert_eq!(last(&terminal), ("Slider moved".to_string(), "Custom Event".to_string()));
+}
+
+#[test]
+fn set_combo_log_prefixes_label() {
+    let terminal = terminal();
+    set_combo_log!(terminal, "Option B", egui_mobius_template::LogType::OptionB);
+    assert_eq!(last(&terminal), ("Selected: Option B".to_string(), "Option B".to_string()));
+}
+
+#[test]
+fn terminal_log_literal() {
+    let terminal = terminal();
+    terminal_log!(terminal, "Plain");
+    assert_eq!(last(&terminal), ("Plain".to_string(), "Default".to_string()));
+}
+
+#[test]
+fn terminal_log_format_args() {
+    let terminal = terminal();
+    let name = "sensor";
+    terminal_log!(terminal, "{} read {} values", name, 3);
+    assert_eq!(last(&terminal), ("sensor read 3 values".to_string(), "Default".to_string()));
+    terminal_log!(terminal, "{name} done",);
+    assert_eq!(last(&terminal), ("sensor done".to_string(), "Default".to_string()));
+}
+
+#[test]
+fn terminal_log_expression() {
+    let terminal = terminal();
+    let message = String::from("Owned {braces}");
+    terminal_log!(terminal, message);
+    assert_eq!(last(&terminal), ("Owned {braces}".to_string(), "Default".to_string()));
+}
+
+#[test]
+fn terminal_log_typed_format_args() {
+    let terminal = terminal();
+    terminal_log!(terminal, type: egui_mobius_template::LogType::Error, "Failed after {} retries", 3);
+    assert_eq!(last(&terminal), ("Failed after 3 retries".to_string(), "Error".to_string()));
+}
+
+#[test]
+fn terminal_log_typed_expression() {
+    let terminal = terminal();
+    let error = std::io::Error::other("disk full");
+    terminal_log!(terminal, type: egui_mobius_template::LogType::Error, error);
+    assert_eq!(last(&terminal), ("disk full".to_string(), "Error".to_string()));
+}
+
+#[test]
+fn with_terminal_logs_writes_back_once() {
+    let terminal = terminal();
+    terminal_log!(terminal, "old");
+    with_terminal_logs!(terminal, |logs| {
+        logs.clear();
+        logs.push_back(("new".to_string(), egui_mobius_template::LogType::Default));
+    });
+    let logs = terminal.logs.get();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].0, "new");
+}
@@ -0,0 +1,89 @@
+//! Toasts, history and commands of the notifier.
+use eframe::egui;
+use egui_mobius_template::notifications::{Notification, Notifier, Severity, MAX_HISTORY};
+use std::time::Duration;
+
+/// Run one frame showing the toasts of `notifier`
+fn show(notifier: &Notifier) {
+    let ctx = egui::Context::default();
+    let _ = ctx.run(egui::RawInput::default(), |ctx| notifier.show(ctx));
+}
+
+#[test]
+fn notifications_from_threads_are_toasted_and_kept() {
+    let notifier = Notifier::new();
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let notifier = notifier.clone();
+            std::thread::spawn(move || notifier.info(format!("Task {} finished", i)))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(notifier.toasts().len(), 4);
+    assert_eq!(notifier.history().len(), 4);
+    assert!(notifier.toasts().iter().all(|toast| toast.severity == Severity::Info));
+
+    notifier.dismiss_all();
+    assert!(notifier.toasts().is_empty());
+    assert_eq!(notifier.history().len(), 4);
+}
+
+#[test]
+fn toasts_expire_unless_sticky() {
+    assert_eq!(Severity::Error.default_timeout(), None);
+    assert!(Severity::Info.default_timeout().is_some());
+
+    let notifier = Notifier::new();
+    notifier.notify(Notification::info("Saved").timeout(Duration::ZERO));
+    let failed = notifier.error("Save failed");
+
+    // The timer starts when a toast is first shown
+    show(&notifier);
+    show(&notifier);
+    let titles: Vec<_> = notifier.toasts().into_iter().map(|toast| toast.title).collect();
+    assert_eq!(titles, ["Save failed"]);
+
+    notifier.dismiss(failed);
+    assert!(notifier.toasts().is_empty());
+}
+
+#[test]
+fn repeated_notifications_share_a_toast() {
+    let notifier = Notifier::new();
+    let first = notifier.notify(Notification::error("Settings error").body("disk full"));
+    let again = notifier.notify(Notification::error("Settings error").body("disk full"));
+    notifier.notify(Notification::error("Settings error").body("permission denied"));
+
+    assert_eq!(first, again);
+    assert_eq!(notifier.toasts().len(), 2);
+    assert_eq!(notifier.history().len(), 2);
+}
+
+#[test]
+fn history_is_newest_first_and_bounded() {
+    let notifier = Notifier::new();
+    for i in 0..MAX_HISTORY + 3 {
+        notifier.info(format!("Step {}", i));
+    }
+    let history = notifier.history();
+    assert_eq!(history.len(), MAX_HISTORY);
+    assert_eq!(history[0].title, format!("Step {}", MAX_HISTORY + 2));
+
+    notifier.clear_history();
+    assert!(notifier.history().is_empty());
+}
+
+#[test]
+fn requested_commands_are_taken_once() {
+    let notifier = Notifier::new();
+    let error = Notification::error("Simulation failed").action("Show parameters", "panel:parameters");
+    assert_eq!(error.actions[0].command, "panel:parameters");
+
+    notifier.request_command("panel:parameters");
+    notifier.request_command("action:simulation.run");
+    assert_eq!(notifier.take_commands(), ["panel:parameters", "action:simulation.run"]);
+    assert!(notifier.take_commands().is_empty());
+}
@@ -0,0 +1,103 @@
+//! Panel registration, tab creation and the resources type map.
+use eframe::egui;
+use egui_mobius_template::{AppContext, Panel, PanelRegistry, Resources};
+
+struct Named(&'static str, &'static str);
+
+impl Panel for Named {
+    fn id(&self) -> &'static str {
+        self.0
+    }
+
+    fn title(&self) -> String {
+        self.1.to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+#[derive(Default)]
+struct Viewer;
+
+impl Panel for Viewer {
+    fn id(&self) -> &'static str {
+        "viewer"
+    }
+
+    fn title(&self) -> String {
+        "Viewer".to_string()
+    }
+
+    fn icon(&self) -> &'static str {
+        "👁"
+    }
+
+    fn allows_duplicates(&self) -> bool {
+        true
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+#[test]
+fn registry_keeps_order_and_replaces_ids() {
+    let mut panels = PanelRegistry::new().with::<Viewer>();
+    panels.register(|| Named("editor", "Editor"));
+    panels.register(|| Named("viewer", "Plain viewer"));
+
+    let ids: Vec<_> = panels.panels().map(|info| info.id).collect();
+    assert_eq!(ids, ["viewer", "editor"]);
+
+    let viewer = panels.info("viewer").unwrap();
+    assert_eq!(viewer.label(), "Plain viewer");
+    assert!(!viewer.allows_duplicates);
+}
+
+#[test]
+fn tabs_are_unique_instances() {
+    let panels = PanelRegistry::new().with::<Viewer>();
+    let first = panels.create("viewer").unwrap();
+    let second = panels.create("viewer").unwrap();
+
+    assert_eq!(first.title(), "👁 Viewer");
+    assert!(first.allows_duplicates());
+    assert_ne!(first.instance(), second.instance());
+    assert!(panels.create("missing").is_none());
+}
+
+#[test]
+fn titles_are_refreshed_without_new_panels() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let created = Rc::new(Cell::new(0));
+    let mut panels = PanelRegistry::new();
+    let counter = created.clone();
+    panels.register(move || {
+        counter.set(counter.get() + 1);
+        Viewer
+    });
+    assert_eq!(created.get(), 1);
+
+    panels.refresh_titles();
+    assert_eq!(created.get(), 1);
+    assert_eq!(panels.info("viewer").unwrap().title, "Viewer");
+
+    panels.create("viewer").unwrap();
+    assert_eq!(created.get(), 2);
+}
+
+#[test]
+fn resources_by_type() {
+    struct Counter(u32);
+
+    let mut resources = Resources::new().with(Counter(1)).with("label");
+    resources.get_mut::<Counter>().unwrap().0 += 1;
+    assert_eq!(resources.get::<Counter>().unwrap().0, 2);
+    assert_eq!(resources.get::<&str>(), Some(&"label"));
+    assert!(resources.get::<String>().is_none());
+
+    let old = resources.insert(Counter(10)).unwrap();
+    assert_eq!(old.0, 2);
+    assert_eq!(resources.get::<Counter>().unwrap().0, 10);
+}
@@ -0,0 +1,109 @@
+//! Atomic writes and the debounced persistence service.
+use egui_mobius_template::persistence::{read_json, write_atomic, PersistError};
+use egui_mobius_template::PersistenceService;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mobius_persistence_{}_{}", name, std::process::id()))
+}
+
+/// Names of the files in `dir`, sorted
+fn files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn json(value: u32) -> Vec<u8> {
+    serde_json::to_string_pretty(&value).unwrap().into_bytes()
+}
+
+#[test]
+fn atomic_writes_replace_the_file_and_leave_no_temporary_behind() {
+    let dir = temp_dir("atomic");
+    let path = dir.join("nested").join("config.json");
+    write_atomic(&path, b"first").unwrap();
+    write_atomic(&path, b"second").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"second");
+    assert_eq!(files(path.parent().unwrap()), ["config.json"]);
+
+    // A target that cannot be replaced keeps its contents and the
+    // temporary file is removed
+    let blocked = dir.join("blocked");
+    std::fs::create_dir_all(blocked.join("inside")).unwrap();
+    let result = write_atomic(&blocked, b"partial");
+    assert!(matches!(result, Err(PersistError::Io { ref path, .. }) if *path == blocked));
+    assert_eq!(files(&dir), ["blocked", "nested"]);
+    assert_eq!(files(&blocked), ["inside"]);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn save_requests_are_coalesced_until_flushed() {
+    let dir = temp_dir("debounce");
+    let path = dir.join("value.json");
+    let service = PersistenceService::with_debounce(&path, Duration::from_secs(60));
+    assert!(!service.has_unsaved_changes());
+
+    for value in 1..=3_u32 {
+        service.save(value);
+    }
+    assert!(service.has_unsaved_changes());
+    assert!(!path.exists());
+
+    // Only the last value is written
+    service.flush().unwrap();
+    assert!(!service.has_unsaved_changes());
+    assert_eq!(read_json::<u32>(&path).unwrap(), 3);
+    assert!(service.wrote(&json(3)));
+    assert!(!service.wrote(&json(1)) && !service.wrote(&json(2)));
+
+    // Nothing pending: flushing does not write again
+    std::fs::remove_file(&path).unwrap();
+    service.flush().unwrap();
+    assert!(!path.exists());
+
+    // Dropping the service writes what is pending
+    service.save(4);
+    drop(service);
+    assert_eq!(read_json::<u32>(&path).unwrap(), 4);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn pending_values_are_written_after_the_debounce_delay() {
+    let dir = temp_dir("delay");
+    let path = dir.join("value.json");
+    let service = PersistenceService::with_debounce(&path, Duration::from_millis(20));
+    service.save(7_u32);
+
+    let started = Instant::now();
+    while service.has_unsaved_changes() && started.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(!service.has_unsaved_changes());
+    assert_eq!(read_json::<u32>(&path).unwrap(), 7);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn failed_writes_are_reported_and_stay_unsaved() {
+    let dir = temp_dir("failure");
+    std::fs::create_dir_all(dir.join("value.json").join("inside")).unwrap();
+    let service = PersistenceService::with_debounce(dir.join("value.json"), Duration::from_secs(60));
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = reported.clone();
+    service.on_error(move |e| sink.lock().unwrap().push(e.to_string()));
+
+    service.save(1_u32);
+    assert!(matches!(service.flush(), Err(PersistError::Io { .. })));
+    assert_eq!(reported.lock().unwrap().len(), 1);
+    assert!(service.has_unsaved_changes());
+    assert!(read_json::<u32>(&dir.join("value.json")).is_err());
+    std::fs::remove_dir_all(dir).ok();
+}
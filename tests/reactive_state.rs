@@ -0,0 +1,58 @@
+//! Tests for `#[derive(ReactiveState)]`
+use egui_mobius_template::{LogColors, ReactiveState, TerminalWidget};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Default, PartialEq, ReactiveState)]
+struct Counter {
+    count : u32,
+    label : String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ReactiveState)]
+#[reactive(name = "SessionState", snapshot, log)]
+pub struct Session {
+    pub user    : String,
+    pub volume  : f32,
+    #[reactive(skip_log)]
+    pub elapsed : u64,
+}
+
+#[test]
+fn generates_accessors_with_default_name() {
+    let counter = ReactiveCounter::new(Counter { count: 1, label: "a".to_string() });
+    counter.set_count(2);
+    counter.set_label("b".to_string());
+    assert_eq!(counter.count(), 2);
+    assert_eq!(counter.label.get(), "b");
+}
+
+#[test]
+fn snapshot_and_restore() {
+    let session = SessionState::from(Session::default());
+    session.set_user("ada".to_string());
+    let saved = session.snapshot();
+    session.set_volume(0.5);
+    session.restore(saved.clone());
+    assert_eq!(session.snapshot(), saved);
+    assert_eq!(session.volume(), 0.0);
+}
+
+#[test]
+fn logs_changes_except_skipped_fields() {
+    let terminal = TerminalWidget::new(eframe::egui::Context::default(), LogColors::default());
+    let session = SessionState::new(Session::default());
+    session.log_changes(&terminal);
+
+    // Skipped fields are not subscribed to, so nothing is logged for
+    // `elapsed` even later; changes arrive on another thread
+    session.set_elapsed(10);
+    session.set_volume(0.75);
+    let started = Instant::now();
+    while terminal.logs.get().is_empty() {
+        assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+        std::thread::yield_now();
+    }
+
+    let logs: Vec<String> = terminal.logs.get().into_iter().map(|(message, _)| message).collect();
+    assert_eq!(logs, vec!["Session.volume = 0.75".to_string()]);
+}
@@ -0,0 +1,178 @@
+//! Recording inputs, saving recordings and replaying them.
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::recorder::{
+    RecordError, RecordedEvent, RecordedInput, Recorder, RecorderState, Recording, ReplaySpeed, FORMAT_VERSION,
+};
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("mobius_recorder_{}", std::process::id()))
+        .join(name)
+}
+
+#[test]
+fn inputs_are_recorded_in_order() {
+    let slider = Dynamic::new(1.0_f32);
+    let option = Dynamic::new(0_usize);
+    let recorder = Recorder::new();
+    recorder.track(&slider, "slider");
+    recorder.track(&option, "option");
+
+    // Nothing is recorded before a recording starts
+    slider.set(2.0);
+    recorder.poll();
+    recorder.command("panel:logger");
+    assert!(!recorder.press("run", false));
+    assert!(recorder.press("run", true));
+    assert_eq!(recorder.state(), RecorderState::Idle);
+
+    recorder.start("Test");
+    assert!(recorder.is_recording());
+    recorder.command("theme:dark.json");
+    option.set(2);
+    slider.set(3.0);
+    recorder.poll();
+    recorder.poll();
+    assert!(recorder.press("run", true));
+    slider.set(4.0);
+    // Changes since the last poll are recorded when stopping
+    let recording = recorder.stop().unwrap();
+    assert!(!recorder.is_recording());
+    assert!(recorder.stop().is_none());
+
+    assert_eq!(recording.version, FORMAT_VERSION);
+    assert_eq!(recording.app, "Test");
+    assert_eq!(recording.initial["slider"], 2.0);
+    assert_eq!(recording.initial["option"], 0);
+    let inputs: Vec<_> = recording.events.iter().map(|event| event.input.clone()).collect();
+    assert_eq!(inputs, [
+        RecordedInput::Command { key: "theme:dark.json".into() },
+        RecordedInput::Value { name: "slider".into(), value: 3.0.into() },
+        RecordedInput::Value { name: "option".into(), value: 2.into() },
+        RecordedInput::Press { name: "run".into() },
+        RecordedInput::Value { name: "slider".into(), value: 4.0.into() },
+    ]);
+    assert!(recording.events.windows(2).all(|pair| pair[0].at_ms <= pair[1].at_ms));
+}
+
+#[test]
+fn saved_recordings_replay_step_by_step() {
+    let slider = Dynamic::new(1.0_f32);
+    let recorder = Recorder::new();
+    recorder.track(&slider, "slider");
+    recorder.start("Test");
+    slider.set(5.0);
+    recorder.poll();
+    recorder.press("reset", true);
+    recorder.command("layout:reset");
+    let recording = recorder.stop().unwrap();
+
+    let path = temp_path("saved.json");
+    recording.save(&path).unwrap();
+    let loaded = Recording::load(&path).unwrap();
+    assert_eq!(loaded, recording);
+
+    // The initial values are restored when the replay starts
+    slider.set(9.0);
+    recorder.replay(loaded, ReplaySpeed::Stepped).unwrap();
+    assert_eq!(slider.get(), 1.0);
+    assert_eq!(recorder.state(), RecorderState::Replaying { done: 0, total: 3 });
+
+    assert!(recorder.poll().is_empty());
+    assert_eq!(slider.get(), 5.0);
+    assert!(!recorder.press("reset", false));
+
+    // A replayed press is due for one frame only
+    assert!(recorder.poll().is_empty());
+    assert!(recorder.press("reset", false));
+    assert!(!recorder.press("reset", false));
+
+    assert_eq!(recorder.poll(), ["layout:reset"]);
+    assert!(!recorder.is_replaying());
+    assert!(recorder.poll().is_empty());
+
+    // Nothing is recorded while replaying
+    recorder.replay(recording, ReplaySpeed::Stepped).unwrap();
+    recorder.command("panel:logger");
+    recorder.poll();
+    assert!(recorder.stop().is_none());
+    assert_eq!(recorder.state(), RecorderState::Idle);
+}
+
+#[test]
+fn replays_follow_the_recorded_pace() {
+    let slider = Dynamic::new(1.0_f32);
+    let recorder = Recorder::new();
+    recorder.track(&slider, "slider");
+    recorder.start("Test");
+    slider.set(2.0);
+    let mut recording = recorder.stop().unwrap();
+    recording.events[0].at_ms = 60_000;
+
+    recorder.replay(recording.clone(), ReplaySpeed::Recorded(1.0)).unwrap();
+    recorder.poll();
+    assert_eq!(slider.get(), 1.0);
+    assert!(recorder.is_replaying());
+
+    // Inputs of the first moment are applied at once
+    recording.events[0].at_ms = 0;
+    recorder.replay(recording, ReplaySpeed::Recorded(1.0)).unwrap();
+    recorder.poll();
+    assert_eq!(slider.get(), 2.0);
+    assert!(!recorder.is_replaying());
+}
+
+#[test]
+fn invalid_recordings_are_rejected() {
+    let slider = Dynamic::new(1.0_f32);
+    let option = Dynamic::new(0_usize);
+    let recorder = Recorder::new();
+    recorder.track(&slider, "slider");
+    recorder.track(&option, "option");
+    recorder.start("Test");
+    let recording = recorder.stop().unwrap();
+
+    // Values the application does not track
+    let mut unknown = recording.clone();
+    unknown.initial.insert("missing".into(), 1.into());
+    let result = recorder.replay(unknown, ReplaySpeed::Stepped);
+    assert!(matches!(result, Err(RecordError::UnknownValue(ref name)) if name == "missing"));
+    assert!(!recorder.is_replaying());
+
+    // Values they cannot take; the valid ones are not applied either
+    let mut invalid = recording.clone();
+    invalid.initial.insert("option".into(), 2.into());
+    invalid.initial.insert("slider".into(), "loud".into());
+    let result = recorder.replay(invalid, ReplaySpeed::Stepped);
+    assert!(matches!(result, Err(RecordError::InvalidValue { ref name, .. }) if name == "slider"));
+    assert_eq!(option.get(), 0);
+
+    let mut invalid = recording.clone();
+    invalid.initial.insert("option".into(), 2.into());
+    invalid.events.push(RecordedEvent {
+        at_ms: 10,
+        input: RecordedInput::Value { name: "option".into(), value: (-1).into() },
+    });
+    let result = recorder.replay(invalid, ReplaySpeed::Stepped);
+    assert!(matches!(result, Err(RecordError::InvalidValue { ref name, .. }) if name == "option"));
+    assert_eq!(option.get(), 0);
+    assert!(!recorder.is_replaying());
+
+    // Files of a newer format
+    let path = temp_path("newer.json");
+    Recording { version: FORMAT_VERSION + 1, ..recording }.save(&path).unwrap();
+    let result = Recording::load(&path);
+    assert!(matches!(result, Err(RecordError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1));
+
+    let result = Recording::load(&temp_path("missing.json"));
+    assert!(matches!(result, Err(RecordError::Persist(_))));
+}
+
+#[test]
+fn recordings_of_the_same_second_get_distinct_names() {
+    let started = chrono::Local::now();
+    let later = started + chrono::Duration::milliseconds(1);
+    assert_ne!(Recording::file_name(&started), Recording::file_name(&later));
+    assert!(Recording::file_name(&started).ends_with(".json"));
+}
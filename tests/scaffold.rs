@@ -0,0 +1,117 @@
+//! Generating projects from the embedded templates.
+use egui_mobius_template::scaffold::{self, generate, GeneratedFile, ProjectOptions, ScaffoldError, Template};
+use std::path::Path;
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> Option<&'a str> {
+    files
+        .iter()
+        .find(|file| file.path == Path::new(path))
+        .map(|file| std::str::from_utf8(&file.contents).unwrap())
+}
+
+#[test]
+fn names_and_title_are_substituted() {
+    for template in Template::ALL {
+        let mut options = ProjectOptions::new("bench-tool", template);
+        options.config_dir_name = Some("bench".into());
+        let files = generate(&options).unwrap();
+
+        let manifest = file(&files, "Cargo.toml").unwrap();
+        assert!(manifest.contains("name = \"bench-tool\""));
+        assert!(manifest.contains(&format!("egui_mobius_template = {}", scaffold::TEMPLATE_DEPENDENCY)));
+        assert!(options.uses_release_tag());
+        assert!(!options.clone().local_dependency(Path::new("/tmp")).uses_release_tag());
+
+        let main = file(&files, "src/main.rs").unwrap();
+        assert!(main.contains(".title(\"Bench Tool\")"), "{}", template);
+        assert_eq!(main.matches(".config_dir_name(").count(), 1, "{}", template);
+        assert!(main.contains(".config_dir_name(\"bench\")"), "{}", template);
+        assert!(!main.contains("mod tests;"), "{}", template);
+        assert_eq!(template.name().parse::<Template>().unwrap(), template);
+    }
+
+    let options = ProjectOptions::new("bench", Template::Reactive).local_dependency(Path::new("/src/template"));
+    let files = generate(&options).unwrap();
+    assert!(file(&files, "Cargo.toml").unwrap().contains("egui_mobius_template = { path = \"/src/template\" }"));
+}
+
+#[test]
+fn removed_panels_are_left_out() {
+    let mut options = ProjectOptions::new("plotless", Template::SignalsSlot);
+    options.panels = Some(vec!["parameters".into(), "shortcuts".into()]);
+    let files = generate(&options).unwrap();
+
+    assert!(file(&files, "src/ui/plot_panel.rs").is_none());
+    assert_eq!(file(&files, "src/ui/mod.rs").unwrap(), "pub mod parameter_panel;\n");
+
+    let main = file(&files, "src/main.rs").unwrap();
+    for removed in ["PlotPanel", "HistoryPanel", "NotificationsPanel", "\"plot\"", "\"history\""] {
+        assert!(!main.contains(removed), "{} left in main.rs", removed);
+    }
+    assert!(main.contains("    ui::parameter_panel::ParameterPanel,\n"));
+    assert!(main.contains("use egui_mobius_template::history::MergeRule;"));
+    assert!(main.contains(".panel::<ShortcutsPanel>()"));
+
+    let mut options = ProjectOptions::new("minimal", Template::Reactive);
+    options.panels = Some(vec!["settings".into()]);
+    let files = generate(&options).unwrap();
+    assert!(!files.iter().any(|file| file.path.starts_with("src/assets")));
+}
+
+#[test]
+fn invalid_options_are_rejected() {
+    assert!(matches!(
+        generate(&ProjectOptions::new("2fast", Template::Reactive)),
+        Err(ScaffoldError::InvalidName(_))
+    ));
+    assert!(matches!("reactive-sync".parse::<Template>(), Err(ScaffoldError::UnknownTemplate(_))));
+
+    let mut options = ProjectOptions::new("app", Template::Reactive);
+    options.panels = Some(vec!["settings".into(), "plot".into()]);
+    assert!(matches!(generate(&options), Err(ScaffoldError::UnknownPanel { .. })));
+
+    options.panels = Some(vec!["logger".into()]);
+    assert!(matches!(generate(&options), Err(ScaffoldError::RequiredPanel { panel: "settings", .. })));
+}
+
+#[test]
+fn projects_are_not_written_over_existing_files() {
+    let dir = std::env::temp_dir().join(format!("scaffold_{}", std::process::id()));
+    let options = ProjectOptions::new("app", Template::Reactive);
+
+    let written = scaffold::write(&dir, &options).unwrap();
+    assert!(written.contains(&dir.join("src/ui/about_panel.rs")));
+    assert!(dir.join("src/assets/saturn_rocket_company.png").is_file());
+    assert!(matches!(scaffold::write(&dir, &options), Err(ScaffoldError::DirectoryNotEmpty(_))));
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn generated_projects_build() {
+    let root = std::env::temp_dir().join(format!("scaffold_check_{}", std::process::id()));
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut trimmed = ProjectOptions::new("bench_tool", Template::SignalsSlot).local_dependency(crate_dir);
+    trimmed.panels = Some(vec!["parameters".into(), "history".into()]);
+
+    for options in [
+        ProjectOptions::new("reactive_app", Template::Reactive).local_dependency(crate_dir),
+        ProjectOptions::new("async_app", Template::ReactiveAsync).local_dependency(crate_dir),
+        trimmed,
+    ] {
+        let dir = root.join(&options.name);
+        scaffold::write(&dir, &options).unwrap();
+        // Resolve the versions this crate is built with, which are
+        // available offline
+        std::fs::copy(crate_dir.join("Cargo.lock"), dir.join("Cargo.lock")).ok();
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["check", "--offline", "--quiet"])
+            .current_dir(&dir)
+            .env("CARGO_TARGET_DIR", Path::new(env!("CARGO_TARGET_TMPDIR")).join("scaffold_check"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}:\n{}", options.name, String::from_utf8_lossy(&output.stderr));
+    }
+
+    std::fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,79 @@
+//! Saving and opening sessions through the settings store.
+use egui_mobius_template::session::{self, session_path};
+use egui_mobius_template::{SettingsSection, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Control {
+    slider: f32,
+}
+
+impl SettingsSection for Control {
+    const KEY: &'static str = "control";
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("sessions_{}_{}", name, std::process::id()))
+}
+
+#[test]
+fn opening_a_session_restores_and_reloads_sections() {
+    let dir = temp_dir("restore");
+    let store = SettingsStore::new(dir.join("settings.json"));
+    let sessions = session::sessions_dir(store.path());
+
+    store.set(&Control { slider: 1.0 });
+    store.export(&session_path(&sessions, "bench")).unwrap();
+    store.set(&Control { slider: 7.0 });
+
+    let reloaded = Arc::new(Mutex::new(Vec::new()));
+    let seen = reloaded.clone();
+    store.on_reload(move |control: Control| seen.lock().unwrap().push(control.slider));
+
+    store.import(&session_path(&sessions, "bench")).unwrap();
+    assert_eq!(store.get::<Control>(), Control { slider: 1.0 });
+    assert_eq!(*reloaded.lock().unwrap(), [1.0]);
+
+    // The opened session becomes the saved settings
+    store.flush().unwrap();
+    let fresh = SettingsStore::new(store.path());
+    fresh.load().unwrap();
+    assert_eq!(fresh.get::<Control>(), Control { slider: 1.0 });
+
+    assert!(store.import(&session_path(&sessions, "missing")).is_err());
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn sections_missing_from_a_session_are_reset() {
+    let dir = temp_dir("reset");
+    let store = SettingsStore::new(dir.join("settings.json"));
+    let path = session_path(&session::sessions_dir(store.path()), "empty");
+    store.export(&path).unwrap();
+
+    store.set(&Control { slider: 3.0 });
+    let control = egui_mobius_reactive::Dynamic::new(Control::default());
+    store.bind(&control);
+    assert_eq!(control.get(), Control { slider: 3.0 });
+    store.import(&path).unwrap();
+    assert_eq!(control.get(), Control::default());
+    assert!(store.document().get("control").is_some_and(|control| control["slider"] == 0.0));
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn sessions_are_listed_by_name() {
+    let dir = temp_dir("list");
+    assert!(session::list(&dir).is_empty());
+
+    let store = SettingsStore::new(dir.join("settings.json"));
+    for name in ["zeta", "Alpha", "bench: 10kHz"] {
+        store.export(&session_path(&dir, name)).unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), "not a session").unwrap();
+
+    let names: Vec<_> = session::list(&dir).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["Alpha", "bench_ 10kHz", "zeta"]);
+    std::fs::remove_dir_all(dir).ok();
+}
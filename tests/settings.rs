@@ -0,0 +1,149 @@
+//! Loading, reading and saving settings sections.
+use egui_mobius_template::settings::backup_path;
+use egui_mobius_template::{PersistError, SettingsSection, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Control {
+    slider: f32,
+    option: usize,
+}
+
+impl SettingsSection for Control {
+    const KEY: &'static str = "control";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Colors {
+    name: String,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Self { name: "default".into() }
+    }
+}
+
+impl SettingsSection for Colors {
+    const KEY: &'static str = "colors";
+    const LEGACY_FILE: Option<&'static str> = Some("colors.json");
+}
+
+fn settings_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mobius_settings_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("settings.json")
+}
+
+fn document(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn sections_round_trip_through_the_file() {
+    let path = settings_path("round_trip");
+    let store = SettingsStore::new(&path);
+    store.load().unwrap();
+    assert_eq!(store.get::<Control>(), Control::default());
+    assert!(!store.has_unsaved_changes());
+
+    let control = Control { slider: 2.5, option: 2 };
+    store.set(&control);
+    store.set(&Colors { name: "dark".into() });
+    assert!(store.has_unsaved_changes());
+    store.flush().unwrap();
+    assert!(!store.has_unsaved_changes());
+
+    // Setting an unchanged value does not queue another save
+    store.set(&control);
+    assert!(!store.has_unsaved_changes());
+
+    let fresh = SettingsStore::new(&path);
+    fresh.load().unwrap();
+    assert_eq!(fresh.get::<Control>(), control);
+    assert_eq!(fresh.get::<Colors>(), Colors { name: "dark".into() });
+    assert_eq!(document(&path)["control"]["slider"], 2.5);
+}
+
+#[test]
+fn missing_sections_fall_back_to_their_legacy_file() {
+    let path = settings_path("legacy");
+    let legacy = path.with_file_name("colors.json");
+    std::fs::write(&legacy, r#"{ "name": "legacy" }"#).unwrap();
+
+    let store = SettingsStore::new(&path);
+    store.load().unwrap();
+    assert_eq!(store.get::<Colors>(), Colors { name: "legacy".into() });
+
+    // The settings document takes precedence once the section is stored
+    store.set(&Colors { name: "stored".into() });
+    assert_eq!(store.get::<Colors>(), Colors { name: "stored".into() });
+
+    // A broken legacy file is reported and the default used
+    std::fs::write(&legacy, "{ broken").unwrap();
+    let store = SettingsStore::new(&path);
+    let errors = Arc::new(Mutex::new(0));
+    let count = errors.clone();
+    store.on_error(move |_| *count.lock().unwrap() += 1);
+    assert_eq!(store.get::<Colors>(), Colors::default());
+    assert_eq!(*errors.lock().unwrap(), 1);
+}
+
+#[test]
+fn a_corrupt_file_is_not_overwritten() {
+    let path = settings_path("corrupt");
+    let corrupt = r#"{ "control": { "slider": 4.0, "option": 1 }, "layout": "#;
+    std::fs::write(&path, corrupt).unwrap();
+
+    let store = SettingsStore::new(&path);
+    assert!(matches!(store.load(), Err(PersistError::Parse { .. })));
+    assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), corrupt);
+    assert!(!store.is_read_only());
+
+    // Saving afterwards keeps the original in the backup
+    store.set(&Control { slider: 1.0, option: 0 });
+    store.flush().unwrap();
+    assert_eq!(document(&path)["control"]["slider"], 1.0);
+    assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), corrupt);
+}
+
+#[test]
+fn a_section_that_fails_to_parse_is_backed_up_before_it_is_replaced() {
+    let path = settings_path("section");
+    let contents = r#"{ "control": { "slider": "loud" }, "colors": { "name": "dark" } }"#;
+    std::fs::write(&path, contents).unwrap();
+
+    let store = SettingsStore::new(&path);
+    store.load().unwrap();
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let seen = errors.clone();
+    store.on_error(move |e| seen.lock().unwrap().push(e.to_string()));
+    assert_eq!(store.get::<Control>(), Control::default());
+    assert_eq!(errors.lock().unwrap().len(), 1);
+    assert!(!backup_path(&path).exists());
+
+    store.set(&Control { slider: 1.0, option: 0 });
+    store.flush().unwrap();
+    assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), contents);
+    assert_eq!(document(&path)["colors"]["name"], "dark");
+}
+
+#[test]
+fn nothing_is_saved_if_the_backup_fails() {
+    let path = settings_path("read_only");
+    std::fs::write(&path, "not json").unwrap();
+    std::fs::create_dir_all(backup_path(&path).join("in_the_way")).unwrap();
+
+    let store = SettingsStore::new(&path);
+    assert!(store.load().is_err());
+    assert!(store.is_read_only());
+
+    store.set(&Control { slider: 1.0, option: 0 });
+    store.flush().unwrap();
+    assert!(!store.has_unsaved_changes());
+    assert_eq!(store.get::<Control>(), Control { slider: 1.0, option: 0 });
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "not json");
+}
@@ -0,0 +1,140 @@
+//! Parsing, rebinding, persistence, conflicts and dispatch of shortcuts.
+use eframe::egui;
+use egui::{Event, Key, KeyboardShortcut, Modifiers};
+use egui_mobius_template::shortcuts::{parse_shortcut, shortcut_to_string, ShortcutManager, ShortcutSettings};
+use egui_mobius_template::testing::Harness;
+use egui_mobius_template::SettingsStore;
+
+const UNDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z);
+
+fn manager() -> ShortcutManager {
+    let mut shortcuts = ShortcutManager::new();
+    shortcuts.register("action:edit.undo", "Edit: Undo", Some(UNDO));
+    shortcuts.register("action:edit.redo", "Edit: Redo", Some(REDO));
+    shortcuts.register("panel:logger", "Open: Logger", None);
+    shortcuts
+}
+
+/// Keys whose commands `shortcuts` dispatches for one frame in which
+/// `pressed` is pressed
+fn dispatched(shortcuts: &ShortcutManager, pressed: KeyboardShortcut) -> Vec<String> {
+    let ctx = egui::Context::default();
+    let input = egui::RawInput {
+        events: vec![Event::Key {
+            key: pressed.logical_key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: pressed.modifiers,
+        }],
+        modifiers: pressed.modifiers,
+        ..Default::default()
+    };
+    let mut keys = Vec::new();
+    let _ = ctx.run(input, |ctx| keys = shortcuts.dispatch(ctx));
+    keys
+}
+
+#[test]
+fn shortcuts_round_trip_as_text() {
+    assert_eq!(shortcut_to_string(&REDO), "Ctrl+Shift+Z");
+    assert_eq!(parse_shortcut("Ctrl+Shift+Z"), Some(REDO));
+    assert_eq!(parse_shortcut("Cmd + Z"), Some(UNDO));
+    assert_eq!(parse_shortcut("F5"), Some(KeyboardShortcut::new(Modifiers::NONE, Key::F5)));
+    assert_eq!(parse_shortcut("Hyper+Z"), None);
+    assert_eq!(parse_shortcut(""), None);
+}
+
+#[test]
+fn rebinding_keeps_only_changes_from_defaults() {
+    let shortcuts = manager();
+    let log = KeyboardShortcut::new(Modifiers::COMMAND, Key::L);
+
+    shortcuts.rebind("panel:logger", Some(log));
+    shortcuts.rebind("action:edit.redo", None);
+    assert_eq!(shortcuts.binding("panel:logger"), Some(log));
+    assert_eq!(shortcuts.binding("action:edit.redo"), None);
+    assert_eq!(shortcuts.settings().get().bindings.len(), 2);
+
+    shortcuts.rebind("action:edit.redo", Some(REDO));
+    shortcuts.reset("panel:logger");
+    assert!(shortcuts.settings().get().bindings.is_empty());
+    assert_eq!(shortcuts.binding("action:edit.redo"), Some(REDO));
+}
+
+#[test]
+fn bindings_survive_the_settings_file() {
+    let dir = std::env::temp_dir().join(format!("shortcuts_{}", std::process::id()));
+    let path = dir.join("settings.json");
+
+    let shortcuts = manager();
+    let store = SettingsStore::new(&path);
+    store.bind(shortcuts.settings());
+    shortcuts.rebind("action:edit.undo", Some(KeyboardShortcut::new(Modifiers::ALT, Key::Backspace)));
+    store.flush().unwrap();
+
+    let reloaded = SettingsStore::new(&path);
+    reloaded.load().unwrap();
+    let saved: ShortcutSettings = reloaded.get();
+    assert_eq!(saved.bindings.get("action:edit.undo").map(String::as_str), Some("Alt+Backspace"));
+
+    let restored = manager();
+    reloaded.bind(restored.settings());
+    assert_eq!(
+        restored.binding("action:edit.undo"),
+        Some(KeyboardShortcut::new(Modifiers::ALT, Key::Backspace)),
+    );
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn conflicts_are_reported_and_first_command_wins() {
+    let shortcuts = manager();
+    assert!(shortcuts.conflicts().is_empty());
+
+    shortcuts.rebind("panel:logger", Some(UNDO));
+    let conflicts = shortcuts.conflicts();
+    assert_eq!(conflicts, [(UNDO, vec!["action:edit.undo", "panel:logger"])]);
+    assert_eq!(dispatched(&shortcuts, UNDO), ["action:edit.undo"]);
+}
+
+#[test]
+fn specific_shortcuts_are_dispatched_first() {
+    let shortcuts = manager();
+    assert_eq!(dispatched(&shortcuts, REDO), ["action:edit.redo"]);
+    assert_eq!(dispatched(&shortcuts, UNDO), ["action:edit.undo"]);
+    assert!(dispatched(&shortcuts, KeyboardShortcut::new(Modifiers::NONE, Key::Z)).is_empty());
+}
+
+#[test]
+fn captured_keys_need_a_modifier_unless_function_keys() {
+    let mut shortcuts = manager();
+    let mut harness = Harness::new();
+    harness.run(|ui| shortcuts.ui(ui));
+
+    // A bare letter would take keys typed into text fields
+    harness.click("—");
+    harness.press_key(Key::L);
+    harness.run(|ui| shortcuts.ui(ui));
+    assert!(harness.contains("Add Ctrl or Alt, or press F1-F35…"));
+    assert_eq!(shortcuts.binding("panel:logger"), None);
+
+    harness.set_modifiers(Modifiers::SHIFT);
+    harness.press_key(Key::L);
+    harness.run(|ui| shortcuts.ui(ui));
+    assert_eq!(shortcuts.binding("panel:logger"), None);
+
+    harness.set_modifiers(Modifiers::ALT);
+    harness.press_key(Key::L);
+    harness.run(|ui| shortcuts.ui(ui));
+    assert_eq!(shortcuts.binding("panel:logger"), Some(KeyboardShortcut::new(Modifiers::ALT, Key::L)));
+    assert!(!harness.contains("Add Ctrl or Alt, or press F1-F35…"));
+
+    harness.set_modifiers(Modifiers::NONE);
+    harness.click("Alt+L");
+    harness.press_key(Key::F5);
+    harness.run(|ui| shortcuts.ui(ui));
+    assert_eq!(shortcuts.binding("panel:logger"), Some(KeyboardShortcut::new(Modifiers::NONE, Key::F5)));
+}
@@ -0,0 +1,38 @@
+//! Status items and the unsaved-changes state they report.
+use egui_mobius_template::{SettingsStore, StatusBar, StatusItem, WindowSettings};
+
+#[test]
+fn items_keep_their_place_when_replaced() {
+    let status = StatusBar::new();
+    status.set(StatusItem::new("clock", "🕐 12:00:00"));
+    status.set(StatusItem::new("run_state", "⏹ Stopped").command("panel:settings"));
+    status.set(StatusItem::new("clock", "🕐 12:00:01"));
+
+    let items = status.items();
+    let ids: Vec<_> = items.iter().map(|item| item.id).collect();
+    assert_eq!(ids, ["clock", "run_state"]);
+    assert_eq!(items[0].text, "🕐 12:00:01");
+    assert_eq!(items[1].command.as_deref(), Some("panel:settings"));
+
+    status.remove("clock");
+    assert_eq!(status.items().len(), 1);
+}
+
+#[test]
+fn settings_report_unsaved_changes_until_written() {
+    let dir = std::env::temp_dir().join(format!("status_bar_{}", std::process::id()));
+    let store = SettingsStore::new(dir.join("settings.json"));
+    assert!(!store.has_unsaved_changes());
+
+    store.set(&WindowSettings { inner_size: [640.0, 480.0] });
+    assert!(store.has_unsaved_changes());
+
+    store.flush().unwrap();
+    assert!(!store.has_unsaved_changes());
+
+    // Setting an unchanged value queues nothing
+    store.set(&WindowSettings { inner_size: [640.0, 480.0] });
+    assert!(!store.has_unsaved_changes());
+
+    std::fs::remove_dir_all(dir).ok();
+}
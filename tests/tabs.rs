@@ -0,0 +1,176 @@
+//! Opening, reopening, duplicating and splitting dock tabs.
+use eframe::egui;
+use egui_dock::{DockState, NodeIndex, Split, SurfaceIndex};
+use egui_mobius_template::tabs::{self, TabAction, Tabs};
+use egui_mobius_template::testing::Harness;
+use egui_mobius_template::{AppContext, Panel, PanelRegistry};
+
+#[derive(Default)]
+struct Logger;
+
+impl Panel for Logger {
+    fn id(&self) -> &'static str {
+        "logger"
+    }
+
+    fn title(&self) -> String {
+        "Logger".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+#[derive(Default)]
+struct Viewer;
+
+impl Panel for Viewer {
+    fn id(&self) -> &'static str {
+        "viewer"
+    }
+
+    fn title(&self) -> String {
+        "Viewer".to_string()
+    }
+
+    fn allows_duplicates(&self) -> bool {
+        true
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui, _ctx: &mut AppContext) {}
+}
+
+fn panels() -> PanelRegistry {
+    PanelRegistry::new().with::<Logger>().with::<Viewer>()
+}
+
+/// Panel ids of every leaf, in leaf order
+fn leaves(tabs: &Tabs) -> Vec<Vec<&'static str>> {
+    tabs.dock.iter_leaves().map(|(_, leaf)| leaf.tabs().iter().map(|tab| tab.id()).collect()).collect()
+}
+
+fn instance(tabs: &Tabs, id: &str) -> usize {
+    tabs.dock.iter_all_tabs().find(|(_, tab)| tab.id() == id).unwrap().1.instance()
+}
+
+const MAIN_LEAF: (SurfaceIndex, NodeIndex) = (SurfaceIndex(0), NodeIndex(0));
+
+#[test]
+fn the_default_layout_skips_unknown_panels() {
+    let tabs = Tabs::new(DockState::new(vec!["logger", "removed", "viewer"]), &panels());
+    assert_eq!(leaves(&tabs), [["logger", "viewer"]]);
+    assert_eq!(tabs.open_panels(), ["logger", "viewer"]);
+    assert!(tabs.lonely_tabs().is_empty());
+}
+
+#[test]
+fn single_instance_panels_are_focused_instead_of_opened_twice() {
+    let panels = panels();
+    let mut tabs = Tabs::new(DockState::new(vec!["logger", "viewer"]), &panels);
+
+    tabs.apply(&panels, TabAction::Open("logger", Some(MAIN_LEAF)));
+    assert_eq!(leaves(&tabs), [["logger", "viewer"]]);
+    assert_eq!(tabs.dock.find_active_focused().unwrap().1.id(), "logger");
+
+    tabs.apply(&panels, TabAction::Open("viewer", Some(MAIN_LEAF)));
+    assert_eq!(leaves(&tabs), [["logger", "viewer", "viewer"]]);
+
+    // Unknown panels are ignored
+    tabs.apply(&panels, TabAction::Open("removed", None));
+    tabs.apply(&panels, TabAction::Show("removed"));
+    assert_eq!(tabs.open_panels().len(), 3);
+}
+
+#[test]
+fn closed_tabs_are_reopened_by_showing_their_panel() {
+    let panels = panels();
+    let mut tabs = Tabs::new(DockState::new(vec!["logger", "viewer"]), &panels);
+    let logger = tabs.dock.find_tab_from(|tab| tab.id() == "logger").unwrap();
+    tabs.dock.remove_tab(logger);
+    assert_eq!(tabs.open_panels(), ["viewer"]);
+
+    tabs.apply(&panels, TabAction::Show("logger"));
+    assert_eq!(tabs.open_panels(), ["viewer", "logger"]);
+    // Showing an open panel only focuses it
+    tabs.apply(&panels, TabAction::Show("viewer"));
+    assert_eq!(tabs.open_panels(), ["viewer", "logger"]);
+    assert_eq!(tabs.dock.find_active_focused().unwrap().1.id(), "viewer");
+}
+
+#[test]
+fn only_panels_allowing_it_are_duplicated() {
+    let panels = panels();
+    let mut tabs = Tabs::new(DockState::new(vec!["logger", "viewer"]), &panels);
+
+    tabs.apply(&panels, TabAction::Duplicate(instance(&tabs, "logger")));
+    assert_eq!(tabs.open_panels(), ["logger", "viewer"]);
+
+    let viewer = instance(&tabs, "viewer");
+    tabs.apply(&panels, TabAction::Duplicate(viewer));
+    assert_eq!(leaves(&tabs), [["logger", "viewer", "viewer"]]);
+    // The copy is a tab of its own
+    let instances: Vec<_> = tabs.dock.iter_all_tabs().map(|(_, tab)| tab.instance()).collect();
+    assert_ne!(instances[1], instances[2]);
+}
+
+#[test]
+fn tabs_move_into_a_new_split_unless_alone() {
+    let panels = panels();
+    let mut tabs = Tabs::new(DockState::new(vec!["logger", "viewer"]), &panels);
+    let viewer = instance(&tabs, "viewer");
+
+    tabs.apply(&panels, TabAction::Split(viewer, Split::Right));
+    assert_eq!(leaves(&tabs), [["logger"], ["viewer"]]);
+    assert_eq!(tabs.lonely_tabs().len(), 2);
+
+    // Nothing to split from
+    tabs.apply(&panels, TabAction::Split(viewer, Split::Below));
+    assert_eq!(leaves(&tabs), [["logger"], ["viewer"]]);
+
+    tabs.apply(&panels, TabAction::ResetLayout);
+    assert_eq!(leaves(&tabs), [["logger", "viewer"]]);
+}
+
+#[test]
+fn the_add_popup_lists_every_panel() {
+    let panels = panels();
+    let mut harness = Harness::new();
+    let mut actions = Vec::new();
+    let open = ["logger"];
+
+    harness.run(|ui| tabs::add_popup(ui, &panels, &open, MAIN_LEAF, &mut actions));
+    assert!(harness.contains("Logger") && harness.contains("Viewer"));
+    harness.ctx().style_mut(|style| style.interaction.tooltip_delay = 0.0);
+    harness.hover("Logger");
+    harness.run(|ui| tabs::add_popup(ui, &panels, &open, MAIN_LEAF, &mut actions));
+    assert!(harness.contains("Already open, click to show it"));
+
+    harness.click("Viewer");
+    harness.run(|ui| tabs::add_popup(ui, &panels, &open, MAIN_LEAF, &mut actions));
+    assert_eq!(actions, [TabAction::Open("viewer", Some(MAIN_LEAF))]);
+}
+
+#[test]
+fn the_context_menu_offers_duplicating_and_splitting() {
+    let panels = panels();
+    let (logger, viewer) = (panels.create("logger").unwrap(), panels.create("viewer").unwrap());
+    let mut harness = Harness::new();
+    let mut actions = Vec::new();
+
+    // Logger may exist once and is alone in its leaf
+    harness.run(|ui| tabs::context_menu(ui, &logger, true, &mut actions));
+    harness.click("Duplicate");
+    harness.click("Move to new split");
+    harness.run(|ui| tabs::context_menu(ui, &logger, true, &mut actions));
+    assert!(actions.is_empty());
+
+    harness.click("Duplicate");
+    harness.run(|ui| tabs::context_menu(ui, &viewer, false, &mut actions));
+    assert_eq!(actions, [TabAction::Duplicate(viewer.instance())]);
+
+    actions.clear();
+    harness.click("Move to new split");
+    harness.run(|ui| tabs::context_menu(ui, &viewer, false, &mut actions));
+    harness.click("Below");
+    harness.run(|ui| tabs::context_menu(ui, &viewer, false, &mut actions));
+    assert_eq!(actions, [TabAction::Split(viewer.instance(), Split::Below)]);
+}
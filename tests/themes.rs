@@ -0,0 +1,145 @@
+//! Exporting, importing and installing theme files.
+use egui::Color32;
+use egui_mobius_template::testing::Harness;
+use egui_mobius_template::themes::{scan, OnConflict, Theme, ThemeError, ThemeEvent, ThemeManager};
+use egui_mobius_template::LogColors;
+use std::path::{Path, PathBuf};
+
+fn theme_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mobius_themes_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn solar() -> Theme {
+    let log_colors = LogColors { slider: Color32::from_rgba_unmultiplied(255, 136, 0, 200), ..LogColors::default() };
+    Theme::new("Solar Flare", log_colors)
+}
+
+/// A theme file in a directory of its own, as shared by someone else
+fn shared(name: &str, theme: &Theme) -> PathBuf {
+    theme.export(&theme_dir(name), false).unwrap()
+}
+
+#[test]
+fn exported_themes_import_unchanged() {
+    let dir = theme_dir("round_trip");
+    let path = shared("round_trip_shared", &solar());
+    assert_eq!(path.file_name().unwrap(), "solar_flare.json");
+
+    let (mut manager, errors) = ThemeManager::new(&dir);
+    assert!(errors.is_empty());
+    let imported = manager.import(&path, OnConflict::Reject).unwrap();
+    assert_eq!(imported, dir.join("solar_flare.json"));
+    assert_eq!(Theme::load(&imported).unwrap(), solar());
+    assert_eq!(manager.themes().len(), 1);
+    assert_eq!(manager.matching(&solar().log_colors), Some(&solar()));
+}
+
+#[test]
+fn invalid_theme_files_are_rejected() {
+    let dir = theme_dir("invalid");
+    let write = |name: &str, contents: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    };
+    let colors = serde_json::to_string(&LogColors::default()).unwrap();
+    let broken = write("broken.json", "{ \"name\": ");
+    let unnamed = write("unnamed.json", &format!(r#"{{ "name": " ", "log_colors": {} }}"#, colors));
+    let invisible = write(
+        "invisible.json",
+        &format!(r#"{{ "name": "Ghost", "log_colors": {} }}"#, colors.replacen("[100,200,255,255]", "\"transparent\"", 1)),
+    );
+    write("notes.txt", "not a theme");
+
+    assert!(matches!(Theme::load(&broken), Err(ThemeError::Persist(_))));
+    assert!(matches!(Theme::load(&unnamed), Err(ThemeError::Invalid { .. })));
+    let error = Theme::load(&invisible).unwrap_err();
+    assert!(error.to_string().contains("fully transparent"), "{}", error);
+
+    // Scanning skips other files and reports every broken theme
+    let (themes, errors) = scan(&dir);
+    assert!(themes.is_empty());
+    assert_eq!(errors.len(), 3);
+
+    // Nothing is installed by a failed import
+    let target = theme_dir("invalid_target");
+    let (mut manager, _) = ThemeManager::new(&target);
+    assert!(manager.import(&broken, OnConflict::KeepBoth).is_err());
+    assert_eq!(std::fs::read_dir(&target).unwrap().count(), 0);
+}
+
+#[test]
+fn installed_themes_are_not_replaced_silently() {
+    let dir = theme_dir("conflict");
+    let (mut manager, _) = ThemeManager::new(&dir);
+    let installed = Theme::new("Solar Flare", LogColors::default());
+    manager.export(installed.clone(), OnConflict::Reject).unwrap();
+    let path = shared("conflict_shared", &solar());
+
+    match manager.import(&path, OnConflict::Reject) {
+        Err(ThemeError::Exists { name, .. }) => assert_eq!(name, "Solar Flare"),
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+    assert_eq!(Theme::load(&dir.join("solar_flare.json")).unwrap(), installed);
+
+    // Keeping both installs the import under a new name
+    let kept = manager.import(&path, OnConflict::KeepBoth).unwrap();
+    assert_eq!(kept, dir.join("solar_flare__2_.json"));
+    assert_eq!(Theme::load(&kept).unwrap().name, "Solar Flare (2)");
+    assert_eq!(manager.unique_name("Solar Flare"), "Solar Flare (3)");
+
+    manager.import(&path, OnConflict::Replace).unwrap();
+    assert_eq!(Theme::load(&dir.join("solar_flare.json")).unwrap(), solar());
+    assert_eq!(manager.themes().len(), 2);
+}
+
+fn import_in_ui(harness: &mut Harness, manager: &mut ThemeManager, path: &Path) -> Vec<ThemeEvent> {
+    let mut events = Vec::new();
+    let current = LogColors::default();
+    harness.run(|ui| events.extend(manager.ui(ui, &current)));
+    // The path stays in the field until the import went through
+    if harness.contains("/path/to/theme.json") {
+        harness.click("/path/to/theme.json");
+        harness.type_text(&path.display().to_string());
+    }
+    harness.click("Import");
+    harness.run(|ui| events.extend(manager.ui(ui, &current)));
+    events
+}
+
+#[test]
+fn the_ui_asks_before_replacing_a_theme() {
+    let dir = theme_dir("ui");
+    let (mut manager, _) = ThemeManager::new(&dir);
+    manager.export(Theme::new("Solar Flare", LogColors::default()), OnConflict::Reject).unwrap();
+    let path = shared("ui_shared", &solar());
+    let current = LogColors::default();
+    let mut harness = Harness::new();
+
+    let events = import_in_ui(&mut harness, &mut manager, &path);
+    assert!(events.is_empty(), "{:?}", events);
+    assert!(harness.contains("A theme named \"Solar Flare\" is already installed"));
+
+    // Cancelling keeps the installed theme
+    let mut events = Vec::new();
+    harness.click("Cancel");
+    harness.run(|ui| events.extend(manager.ui(ui, &current)));
+    assert!(events.is_empty());
+    assert!(!harness.contains("already installed"));
+    assert_eq!(Theme::load(&dir.join("solar_flare.json")).unwrap().log_colors, LogColors::default());
+
+    import_in_ui(&mut harness, &mut manager, &path);
+    harness.click("Keep both as \"Solar Flare (2)\"");
+    harness.run(|ui| events.extend(manager.ui(ui, &current)));
+    assert!(matches!(&events[..], [ThemeEvent::Imported(path)] if path.ends_with("solar_flare__2_.json")));
+
+    import_in_ui(&mut harness, &mut manager, &path);
+    events.clear();
+    harness.click("Replace it");
+    harness.run(|ui| events.extend(manager.ui(ui, &current)));
+    assert!(matches!(&events[..], [ThemeEvent::Imported(path)] if path.ends_with("solar_flare.json")));
+    assert_eq!(Theme::load(&dir.join("solar_flare.json")).unwrap(), solar());
+}
@@ -0,0 +1,169 @@
+//! Staging, committing and rolling back transactions.
+use egui_mobius_reactive::{Dynamic, ReactiveValue};
+use egui_mobius_template::transaction::{transaction, ReactiveScope};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Number of notifications `value` has sent since this was called
+fn notifications(value: &impl ReactiveValue) -> Arc<AtomicUsize> {
+    let count = Arc::new(AtomicUsize::new(0));
+    let seen = count.clone();
+    value.subscribe(Box::new(move || {
+        seen.fetch_add(1, Ordering::SeqCst);
+    }));
+    count
+}
+
+fn count(counter: &Arc<AtomicUsize>) -> usize {
+    counter.load(Ordering::SeqCst)
+}
+
+/// Wait until `done` holds, failing after a few seconds. `Dynamic`
+/// notifications arrive on other threads.
+fn wait_for(done: impl Fn() -> bool) {
+    let started = Instant::now();
+    while !done() {
+        assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn notifications_are_delivered_once_at_commit() {
+    let width = Dynamic::new(2);
+    let height = Dynamic::new(3);
+    let scope = ReactiveScope::new().with(&width).with(&height);
+    let (scope_changes, width_changes, height_changes) =
+        (notifications(&scope), notifications(&width), notifications(&height));
+
+    scope
+        .transaction(|tx| {
+            tx.set(&width, 4);
+            tx.set(&width, 5);
+            tx.update(&height, |h| *h += 1);
+            tx.update(&height, |h| *h += 1);
+            // Nothing is written or notified while staging
+            assert_eq!((width.get(), height.get()), (2, 3));
+            assert_eq!((tx.get(&width), tx.get(&height)), (5, 5));
+            assert_eq!(count(&scope_changes) + count(&width_changes) + count(&height_changes), 0);
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+
+    // The scope is notified when the commit returns, the members'
+    // own notifications arrive later and are not forwarded again
+    assert_eq!((width.get(), height.get()), (5, 5));
+    assert_eq!(count(&scope_changes), 1);
+    wait_for(|| count(&width_changes) == 1 && count(&height_changes) == 1);
+    wait_for(|| scope.is_settled());
+    assert_eq!(count(&scope_changes), 1);
+
+    // Outside of transactions every member change is forwarded
+    width.set(6);
+    height.set(7);
+    wait_for(|| count(&scope_changes) == 3);
+}
+
+#[test]
+fn unchanged_values_are_not_written() {
+    let value = Dynamic::new(1);
+    let scope = ReactiveScope::new().with(&value);
+    let (scope_changes, value_changes) = (notifications(&scope), notifications(&value));
+
+    scope
+        .transaction(|tx| {
+            tx.set(&value, 5);
+            tx.set(&value, 1);
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert!(scope.is_settled());
+    assert_eq!(count(&scope_changes), 0);
+
+    // Unbound transactions notify each changed value once
+    let other = Dynamic::new(0);
+    let other_changes = notifications(&other);
+    transaction(|tx| {
+        tx.set(&value, 2);
+        tx.update(&other, |o| *o += 1);
+        tx.update(&other, |o| *o += 1);
+        Ok::<_, ()>(())
+    })
+    .unwrap();
+    assert_eq!((value.get(), other.get()), (2, 2));
+    wait_for(|| count(&value_changes) == 1 && count(&other_changes) == 1);
+    // Not bound to the scope, which forwards the change as usual
+    wait_for(|| count(&scope_changes) == 1);
+}
+
+#[test]
+fn errors_roll_back_every_staged_write() {
+    let value = Dynamic::new(1);
+    let scope = ReactiveScope::new().with(&value);
+    let (scope_changes, value_changes) = (notifications(&scope), notifications(&value));
+
+    let result: Result<(), &str> = scope.transaction(|tx| {
+        tx.set(&value, 100);
+        Err("too large")
+    });
+    assert_eq!(result, Err("too large"));
+    assert_eq!(value.get(), 1);
+    assert!(scope.is_settled());
+    assert_eq!(count(&scope_changes), 0);
+
+    // The scope keeps working after a rollback
+    scope
+        .transaction(|tx| {
+            tx.set(&value, 2);
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    wait_for(|| count(&value_changes) == 1 && scope.is_settled());
+    assert_eq!(count(&scope_changes), 1);
+}
+
+#[test]
+fn nested_transactions_roll_back_on_their_own() {
+    let (a, b, c) = (Dynamic::new(0), Dynamic::new(0), Dynamic::new(0));
+    let scope = ReactiveScope::new().with(&a).with(&b).with(&c);
+    let scope_changes = notifications(&scope);
+
+    scope
+        .transaction(|tx| {
+            tx.set(&a, 1);
+            let failed: Result<(), &str> = tx.nested(|tx| {
+                tx.set(&a, 10);
+                tx.set(&b, 10);
+                Err("rejected")
+            });
+            assert!(failed.is_err());
+            assert_eq!((tx.get(&a), tx.get(&b)), (1, 0));
+
+            tx.nested(|tx| {
+                tx.update(&c, |c| *c += 3);
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+            // Kept, but only written with the outer transaction
+            assert_eq!((tx.get(&c), c.get()), (3, 0));
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!((a.get(), b.get(), c.get()), (1, 0, 3));
+    wait_for(|| scope.is_settled());
+    assert_eq!(count(&scope_changes), 1);
+
+    // A failing outer transaction drops what nested ones kept
+    let result: Result<(), ()> = scope.transaction(|tx| {
+        tx.nested(|tx| {
+            tx.set(&b, 5);
+            Ok(())
+        })?;
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(b.get(), 0);
+    assert!(scope.is_settled());
+    assert_eq!(count(&scope_changes), 1);
+}
@@ -0,0 +1,142 @@
+//! Watching files and hot-reloading the settings file.
+use egui_mobius_reactive::Dynamic;
+use egui_mobius_template::settings::backup_path;
+use egui_mobius_template::{FileWatcher, SettingsSection, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Control {
+    slider: f32,
+}
+
+impl SettingsSection for Control {
+    const KEY: &'static str = "control";
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Clock {
+    use_24h: bool,
+}
+
+impl SettingsSection for Clock {
+    const KEY: &'static str = "clock";
+}
+
+fn settings_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mobius_watcher_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("settings.json")
+}
+
+fn watcher() -> FileWatcher {
+    FileWatcher::with_interval(Duration::from_millis(5))
+}
+
+/// Wait until `done` holds, failing after a few seconds
+fn wait_for(mut done: impl FnMut() -> bool) {
+    let started = Instant::now();
+    while !done() {
+        assert!(started.elapsed() < Duration::from_secs(5), "timed out");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Contents of `path` seen by a watcher, newest last
+fn observe(watcher: &FileWatcher, path: &Path) -> Arc<Mutex<Vec<String>>> {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    watcher.watch(path, move |contents| sink.lock().unwrap().push(String::from_utf8_lossy(contents).into_owned()));
+    seen
+}
+
+#[test]
+fn watchers_report_changed_contents() {
+    let path = settings_path("contents");
+    std::fs::write(&path, "first").unwrap();
+    let watcher = watcher();
+    let seen = observe(&watcher, &path);
+
+    std::fs::write(&path, "second!").unwrap();
+    wait_for(|| seen.lock().unwrap().len() == 1);
+    assert_eq!(*seen.lock().unwrap(), ["second!"]);
+
+    // Deleted files are ignored until they reappear
+    std::fs::remove_file(&path).unwrap();
+    std::fs::write(&path, "third!!!").unwrap();
+    wait_for(|| seen.lock().unwrap().len() == 2);
+    assert_eq!(seen.lock().unwrap()[1], "third!!!");
+}
+
+#[test]
+fn external_edits_are_reloaded() {
+    let path = settings_path("reload");
+    let store = SettingsStore::new(&path);
+    store.set(&Control { slider: 1.0 });
+    store.set(&Clock { use_24h: true });
+    store.flush().unwrap();
+
+    let slider = Dynamic::new(Control::default());
+    store.bind(&slider);
+    let reloaded = Arc::new(Mutex::new(Vec::new()));
+    let seen = reloaded.clone();
+    store.on_reload(move |clock: Clock| seen.lock().unwrap().push(clock.use_24h));
+    let watcher = watcher();
+    store.watch(&watcher, &egui::Context::default());
+
+    std::fs::write(&path, r#"{ "control": { "slider": 5.0 }, "clock": { "use_24h": true } }"#).unwrap();
+    wait_for(|| store.apply_reload());
+    assert_eq!(slider.get(), Control { slider: 5.0 });
+    assert_eq!(store.get::<Control>(), Control { slider: 5.0 });
+    // Unchanged sections are not reloaded
+    assert!(reloaded.lock().unwrap().is_empty());
+
+    // Sections removed by the edit are reset to their defaults
+    std::fs::write(&path, r#"{ "control": { "slider": 5.0 } }"#).unwrap();
+    wait_for(|| store.apply_reload());
+    assert_eq!(*reloaded.lock().unwrap(), [false]);
+    assert_eq!(store.document().get("clock"), None);
+}
+
+#[test]
+fn the_stores_own_writes_are_ignored() {
+    let path = settings_path("own");
+    let store = SettingsStore::new(&path);
+    let reloaded = Arc::new(Mutex::new(0));
+    let count = reloaded.clone();
+    store.on_reload(move |_: Control| *count.lock().unwrap() += 1);
+    let watcher = watcher();
+    store.watch(&watcher, &egui::Context::default());
+    // Polled after the store's entry, so once it saw a write so did the store
+    let seen = observe(&watcher, &path);
+
+    store.set(&Control { slider: 2.0 });
+    store.flush().unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    wait_for(|| seen.lock().unwrap().contains(&written));
+    assert!(!store.apply_reload());
+    assert_eq!(*reloaded.lock().unwrap(), 0);
+}
+
+#[test]
+fn invalid_edits_keep_the_current_settings() {
+    let path = settings_path("invalid");
+    let store = SettingsStore::new(&path);
+    store.set(&Control { slider: 3.0 });
+    store.flush().unwrap();
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let sink = errors.clone();
+    store.on_error(move |e| sink.lock().unwrap().push(e.to_string()));
+    let watcher = watcher();
+    store.watch(&watcher, &egui::Context::default());
+
+    std::fs::write(&path, r#"{ "control": { "slider": "#).unwrap();
+    wait_for(|| store.apply_reload());
+    assert_eq!(errors.lock().unwrap().len(), 1);
+    assert_eq!(store.get::<Control>(), Control { slider: 3.0 });
+    // The edit is kept before the store saves over it
+    assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), r#"{ "control": { "slider": "#);
+}